@@ -0,0 +1,63 @@
+//! Parse throughput on a 100-event Polygon frame
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use data_ingestion::polygon::{self, PolygonMessage};
+
+const EVENTS: usize = 100;
+
+fn frame() -> String {
+    let events: Vec<String> = (0..EVENTS)
+        .map(|i| {
+            if i % 10 == 0 {
+                format!(
+                    r#"{{"ev":"A","sym":"SYM{}","o":100.1,"h":100.9,"l":99.8,"c":100.4,"v":12000,"s":1760620000000,"e":1760620001000}}"#,
+                    i % 50
+                )
+            } else {
+                format!(
+                    r#"{{"ev":"T","sym":"SYM{}","x":4,"i":"52983525029461","z":3,"p":{:.2},"s":{},"c":[14,41],"t":1760620000{:03},"q":{}}}"#,
+                    i % 50,
+                    100.0 + i as f64 * 0.01,
+                    100 + i,
+                    i,
+                    i
+                )
+            }
+        })
+        .collect();
+    format!("[{}]", events.join(","))
+}
+
+fn bench_polygon_parse(c: &mut Criterion) {
+    let text = frame();
+    let mut group = c.benchmark_group("polygon_parse");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+
+    group.bench_function("serde_json_value", |b| {
+        b.iter(|| {
+            let events: Vec<serde_json::Value> = serde_json::from_str(black_box(&text)).unwrap();
+            events
+                .into_iter()
+                .filter_map(|e| serde_json::from_value::<PolygonMessage>(e).ok())
+                .count()
+        })
+    });
+
+    group.bench_function("serde_json_typed", |b| {
+        b.iter(|| polygon::parse_frame(black_box(&text)).unwrap().len())
+    });
+
+    #[cfg(feature = "simd")]
+    group.bench_function("simd_json_typed", |b| {
+        b.iter_batched_ref(
+            || text.clone().into_bytes(),
+            |buf| polygon::parse_frame_simd(buf).unwrap().len(),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_polygon_parse);
+criterion_main!(benches);
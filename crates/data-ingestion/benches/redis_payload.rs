@@ -0,0 +1,107 @@
+//! Redis publishing through `RedisSink`: queueing, pipelining and delivery.
+//!
+//! Needs a Redis server at `REDIS_URL` (default `redis://127.0.0.1:6379`);
+//! skipped when none is reachable.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use data_ingestion::config::PublishConfig;
+use data_ingestion::sink::{RedisSink, Sink};
+use data_ingestion::{price, MarketData, QuoteData};
+use futures::{Stream, StreamExt};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts allocations so the benchmark can report them alongside timings
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const MESSAGES: usize = 1_000;
+
+fn quotes() -> Vec<MarketData> {
+    (0..MESSAGES)
+        .map(|i| {
+            MarketData::Quote(QuoteData {
+                symbol: format!("SYM{}", i % 50),
+                original_symbol: None,
+                bid_price: price::from_f64(100.0 + (i % 100) as f64 * 0.01),
+                ask_price: price::from_f64(100.01 + (i % 100) as f64 * 0.01),
+                bid_size: 100,
+                ask_size: 200,
+                timestamp: chrono::Utc::now(),
+                secondary_timestamp: None,
+                exchange: "NASDAQ|NYSE".to_string(),
+                bid_exchange: "NASDAQ".to_string(),
+                ask_exchange: "NYSE".to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Publish every message and wait until a subscriber has received them all
+async fn publish_all(
+    sink: &RedisSink,
+    messages: &[MarketData],
+    delivered: &mut (impl Stream<Item = redis::Msg> + Unpin),
+) {
+    for data in messages {
+        sink.publish(data).await.unwrap();
+    }
+    for _ in 0..messages.len() {
+        delivered.next().await.expect("subscription closed");
+    }
+}
+
+fn bench_redis_publish(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+
+    let sink = runtime.block_on(async {
+        let sink = RedisSink::new(&url, PublishConfig::default()).ok()?;
+        sink.ping().await.ok()?;
+        Some(sink)
+    });
+    let Some(sink) = sink else {
+        println!("skipping redis_publish: no Redis at {}", url);
+        return;
+    };
+    let mut pubsub = runtime.block_on(async {
+        let client = redis::Client::open(url.as_str()).unwrap();
+        let mut pubsub = client.get_async_pubsub().await.unwrap();
+        pubsub.subscribe("market_data:quotes").await.unwrap();
+        pubsub
+    });
+    let mut delivered = Box::pin(pubsub.on_message());
+    let messages = quotes();
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    runtime.block_on(publish_all(&sink, &messages, &mut delivered));
+    println!(
+        "allocations per message, publish through delivery: {:.1}",
+        (ALLOCATIONS.load(Ordering::Relaxed) - before) as f64 / MESSAGES as f64
+    );
+
+    let mut group = c.benchmark_group("redis_publish");
+    group.throughput(Throughput::Elements(MESSAGES as u64));
+    group.bench_function("publish", |b| {
+        b.iter(|| runtime.block_on(publish_all(&sink, &messages, &mut delivered)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_redis_publish);
+criterion_main!(benches);
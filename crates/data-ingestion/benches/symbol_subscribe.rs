@@ -0,0 +1,66 @@
+//! Single-symbol subscriber cost under a 50-symbol firehose
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use data_ingestion::{channels::SymbolChannels, price, MarketData, TradeData};
+use tokio::sync::broadcast;
+
+const SYMBOLS: usize = 50;
+const MESSAGES: usize = 10_000;
+
+fn firehose() -> Vec<MarketData> {
+    (0..MESSAGES)
+        .map(|i| {
+            MarketData::Trade(TradeData {
+                symbol: format!("SYM{}", i % SYMBOLS),
+                original_symbol: None,
+                price: price::from_f64(100.0 + (i % 100) as f64 * 0.01),
+                size: 100,
+                timestamp: chrono::Utc::now(),
+                secondary_timestamp: None,
+                exchange: "NASDAQ".to_string(),
+                conditions: vec![],
+            })
+        })
+        .collect()
+}
+
+fn bench_single_symbol_subscriber(c: &mut Criterion) {
+    let messages = firehose();
+    let mut group = c.benchmark_group("single_symbol_subscriber");
+
+    group.bench_function("global_channel_filtered", |b| {
+        let (tx, mut rx) = broadcast::channel(MESSAGES);
+        b.iter(|| {
+            for data in &messages {
+                let _ = tx.send(data.clone());
+            }
+            let mut received = 0;
+            while let Ok(data) = rx.try_recv() {
+                if data.symbol() == "SYM7" {
+                    received += 1;
+                }
+            }
+            black_box(received)
+        });
+    });
+
+    group.bench_function("per_symbol_channel", |b| {
+        let channels = SymbolChannels::new(MESSAGES);
+        let mut rx = channels.subscribe("SYM7");
+        b.iter(|| {
+            for data in &messages {
+                channels.publish(data);
+            }
+            let mut received = 0;
+            while rx.try_recv().is_ok() {
+                received += 1;
+            }
+            black_box(received)
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_single_symbol_subscriber);
+criterion_main!(benches);
@@ -0,0 +1,144 @@
+//! Session analytics accumulated from the trade stream
+
+use crate::price::{self, Price};
+use crate::TradeData;
+use std::collections::{HashMap, HashSet};
+
+/// Polygon condition codes that do not update VWAP: average price, cash,
+/// derivatively priced, prior reference, next day, odd lot, contingent
+/// and qualified contingent trades, plus official open/close prints
+pub const DEFAULT_VWAP_EXCLUDED_CONDITIONS: &[&str] =
+    &["2", "7", "10", "15", "16", "20", "21", "22", "37", "52", "53"];
+
+/// Running totals for a single symbol
+#[derive(Debug, Clone, Default)]
+struct SymbolStats {
+    volume: u64,
+    trade_count: u64,
+    vwap_notional: Price,
+    vwap_volume: u64,
+}
+
+/// Per-symbol VWAP, volume and trade count over a session
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    symbols: HashMap<String, SymbolStats>,
+    vwap_excluded_conditions: HashSet<String>,
+}
+
+impl SessionStats {
+    /// Create session stats using the default VWAP exclusions
+    pub fn new() -> Self {
+        Self::with_excluded_conditions(
+            DEFAULT_VWAP_EXCLUDED_CONDITIONS.iter().map(|c| c.to_string()),
+        )
+    }
+
+    /// Create session stats with custom VWAP-excluded condition codes
+    pub fn with_excluded_conditions(conditions: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            symbols: HashMap::new(),
+            vwap_excluded_conditions: conditions.into_iter().collect(),
+        }
+    }
+
+    /// Accumulate a trade
+    pub fn update(&mut self, trade: &TradeData) {
+        let stats = self.symbols.entry(trade.symbol.clone()).or_default();
+        stats.volume += trade.size;
+        stats.trade_count += 1;
+
+        let excluded = trade
+            .conditions
+            .iter()
+            .any(|c| self.vwap_excluded_conditions.contains(c));
+        if !excluded {
+            stats.vwap_notional += trade.price * price::from_units(trade.size);
+            stats.vwap_volume += trade.size;
+        }
+    }
+
+    /// Volume-weighted average price for the session
+    pub fn vwap(&self, symbol: &str) -> Option<Price> {
+        self.symbols
+            .get(symbol)
+            .filter(|stats| stats.vwap_volume > 0)
+            .map(|stats| stats.vwap_notional / price::from_units(stats.vwap_volume))
+    }
+
+    /// Total traded volume for the session
+    pub fn volume(&self, symbol: &str) -> u64 {
+        self.symbols.get(symbol).map_or(0, |stats| stats.volume)
+    }
+
+    /// Number of trades seen for the session
+    pub fn trade_count(&self, symbol: &str) -> u64 {
+        self.symbols.get(symbol).map_or(0, |stats| stats.trade_count)
+    }
+
+    /// Symbols with at least one trade
+    pub fn symbols(&self) -> Vec<String> {
+        self.symbols.keys().cloned().collect()
+    }
+
+    /// Clear all totals for session rollover
+    pub fn reset(&mut self) {
+        self.symbols.clear();
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn trade(value: f64, size: u64, conditions: &[&str]) -> TradeData {
+        TradeData {
+            symbol: "AAPL".to_string(),
+            original_symbol: None,
+            price: price::from_f64(value),
+            size,
+            timestamp: Utc::now(),
+            secondary_timestamp: None,
+            exchange: "NASDAQ".to_string(),
+            conditions: conditions.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_vwap_is_volume_weighted() {
+        let mut stats = SessionStats::new();
+        stats.update(&trade(100.0, 100, &[]));
+        stats.update(&trade(110.0, 300, &[]));
+
+        assert_eq!(stats.vwap("AAPL"), Some(price::from_f64(107.5)));
+        assert_eq!(stats.volume("AAPL"), 400);
+        assert_eq!(stats.trade_count("AAPL"), 2);
+    }
+
+    #[test]
+    fn test_excluded_conditions_skip_vwap() {
+        let mut stats = SessionStats::new();
+        stats.update(&trade(100.0, 100, &[]));
+        stats.update(&trade(500.0, 100, &["37"]));
+
+        assert_eq!(stats.vwap("AAPL"), Some(price::from_f64(100.0)));
+        assert_eq!(stats.volume("AAPL"), 200);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut stats = SessionStats::new();
+        stats.update(&trade(100.0, 100, &[]));
+        stats.reset();
+
+        assert_eq!(stats.vwap("AAPL"), None);
+        assert_eq!(stats.trade_count("AAPL"), 0);
+    }
+}
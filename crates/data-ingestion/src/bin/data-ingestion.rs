@@ -0,0 +1,240 @@
+//! Tap the live Polygon feed and print market data to stdout
+//!
+//! Useful for checking entitlements and parsing before building on the
+//! feed. Nothing is published downstream.
+//!
+//! ```text
+//! POLYGON_API_KEY=... data-ingestion --symbols AAPL,MSFT --types trades,quotes --format json
+//! ```
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clap::{Parser, ValueEnum};
+use data_ingestion::{
+    config::{DataIngestionConfig, EndpointConfig, FeedMode},
+    polygon::PolygonCluster,
+    price,
+    sink::Sink,
+    DataIngestionManager, MarketData,
+};
+use std::io::Write;
+use tokio::sync::broadcast;
+
+#[derive(Debug, Parser)]
+#[command(name = "data-ingestion", about = "Print live market data from Polygon")]
+struct Args {
+    /// Symbols to subscribe, comma separated
+    #[arg(short, long, value_delimiter = ',', required = true)]
+    symbols: Vec<String>,
+
+    /// Data types to subscribe and print, comma separated
+    #[arg(short, long, value_enum, value_delimiter = ',', default_value = "trades")]
+    types: Vec<DataType>,
+
+    /// Polygon cluster; only clusters sharing the stocks event schemas are
+    /// parsed
+    #[arg(long, value_enum, default_value_t = Cluster::Stocks)]
+    cluster: Cluster,
+
+    /// Use the 15-minute delayed feed
+    #[arg(long)]
+    delayed: bool,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Pretty)]
+    format: Format,
+
+    /// Polygon API key; defaults to `POLYGON_API_KEY`
+    #[arg(long)]
+    api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DataType {
+    Trades,
+    Quotes,
+    Aggregates,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Cluster {
+    Stocks,
+    Options,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// One aligned line per event
+    Pretty,
+    /// One JSON object per line
+    Json,
+}
+
+impl From<Cluster> for PolygonCluster {
+    fn from(cluster: Cluster) -> Self {
+        match cluster {
+            Cluster::Stocks => PolygonCluster::Stocks,
+            Cluster::Options => PolygonCluster::Options,
+        }
+    }
+}
+
+impl DataType {
+    /// Polygon channel prefix for this type; stocks and options share them
+    fn channel(&self) -> &'static str {
+        match self {
+            DataType::Trades => "T",
+            DataType::Quotes => "Q",
+            DataType::Aggregates => "A",
+        }
+    }
+
+    fn matches(&self, data: &MarketData) -> bool {
+        matches!(
+            (self, data),
+            (DataType::Trades, MarketData::Trade(_))
+                | (DataType::Quotes, MarketData::Quote(_))
+                | (DataType::Aggregates, MarketData::Aggregate(_))
+        )
+    }
+}
+
+/// Sink that drops everything; the tap only reads the local broadcast
+struct DiscardSink;
+
+#[async_trait]
+impl Sink for DiscardSink {
+    fn name(&self) -> &'static str {
+        "discard"
+    }
+
+    async fn publish(&self, _data: &MarketData) -> Result<()> {
+        Ok(())
+    }
+
+    async fn ping(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let api_key = match args.api_key.clone() {
+        Some(api_key) => api_key,
+        None => std::env::var("POLYGON_API_KEY").context("Pass --api-key or set POLYGON_API_KEY")?,
+    };
+
+    let channels: Vec<String> = args
+        .types
+        .iter()
+        .map(|data_type| data_type.channel().to_string())
+        .collect();
+
+    let mut config = DataIngestionConfig {
+        polygon_api_key: api_key,
+        symbols: args.symbols.clone(),
+        ..Default::default()
+    };
+    if args.delayed {
+        config.websocket.feed = FeedMode::Delayed;
+    }
+    config.websocket.endpoints = vec![EndpointConfig {
+        name: "tap".to_string(),
+        cluster: args.cluster.into(),
+        url: None,
+        channels,
+        symbols: args.symbols.clone(),
+    }];
+
+    let mut manager = DataIngestionManager::with_sink(config, Box::new(DiscardSink)).await?;
+    let rx = manager.subscribe();
+
+    tokio::select! {
+        result = manager.start() => result,
+        result = print_events(rx, &args) => result,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    }
+}
+
+async fn print_events(mut rx: broadcast::Receiver<MarketData>, args: &Args) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    loop {
+        let data = match rx.recv().await {
+            Ok(data) => data,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                eprintln!("Output fell behind, skipped {} events", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => return Ok(()),
+        };
+        if !args.types.iter().any(|data_type| data_type.matches(&data)) {
+            continue;
+        }
+
+        match args.format {
+            Format::Json => writeln!(stdout, "{}", serde_json::to_string(&data)?)?,
+            Format::Pretty => writeln!(stdout, "{}", pretty(&data))?,
+        }
+    }
+}
+
+fn pretty(data: &MarketData) -> String {
+    let time = data.timestamp().format("%H:%M:%S%.3f");
+    match data {
+        MarketData::Trade(trade) => format!(
+            "{} TRADE {:<10} {:>12.4} x {:<8} ex={} c={:?}",
+            time,
+            trade.symbol,
+            price::to_f64(trade.price),
+            trade.size,
+            trade.exchange,
+            trade.conditions
+        ),
+        MarketData::Quote(quote) => format!(
+            "{} QUOTE {:<10} {:>12.4} x {:<8} / {:.4} x {}",
+            time,
+            quote.symbol,
+            price::to_f64(quote.bid_price),
+            quote.bid_size,
+            price::to_f64(quote.ask_price),
+            quote.ask_size
+        ),
+        MarketData::Aggregate(agg) => format!(
+            "{} AGG   {:<10} o={:.4} h={:.4} l={:.4} c={:.4} v={} ({})",
+            time,
+            agg.symbol,
+            price::to_f64(agg.open),
+            price::to_f64(agg.high),
+            price::to_f64(agg.low),
+            price::to_f64(agg.close),
+            agg.volume,
+            agg.timespan
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_parsed_clusters_are_accepted() {
+        let args = Args::try_parse_from(["data-ingestion", "-s", "O:AAPL240119C00190000", "--cluster", "options"])
+            .unwrap();
+        assert!(matches!(args.cluster, Cluster::Options));
+
+        // Crypto, forex and index events use schemas the tap does not parse
+        for cluster in ["crypto", "forex", "indices"] {
+            assert!(Args::try_parse_from(["data-ingestion", "-s", "X", "--cluster", cluster]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_args_split_comma_lists() {
+        let args = Args::try_parse_from(["data-ingestion", "-s", "AAPL,MSFT", "-t", "trades,quotes"]).unwrap();
+
+        assert_eq!(args.symbols, vec!["AAPL", "MSFT"]);
+        assert_eq!(args.types, vec![DataType::Trades, DataType::Quotes]);
+    }
+}
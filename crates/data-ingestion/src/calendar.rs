@@ -0,0 +1,199 @@
+//! Market session calendar
+//!
+//! Classifies timestamps into closed, pre-market, regular and post-market
+//! sessions using an embedded US equity holiday table. Dates outside the
+//! table only account for weekends.
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Embedded US equity holiday and early-close table
+const US_EQUITY_CALENDAR: &str = include_str!("../data/us_equity_calendar.csv");
+
+/// Venues following the US equity schedule
+const US_EQUITY_EXCHANGES: &[&str] = &[
+    "NYSE",
+    "NASDAQ",
+    "NYSE_ARCA",
+    "NASDAQ_OMX_BX",
+    "NYSE_NATIONAL",
+    "CBOE_EDGX",
+    "CBOE_EDGA",
+    "CBOE_BZX",
+    "CBOE_BYX",
+    "IEX",
+    "NYSE_CHICAGO",
+    "NASDAQ_PSX",
+];
+
+/// Trading session state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionState {
+    Closed,
+    PreMarket,
+    Regular,
+    PostMarket,
+}
+
+/// Kind of non-standard trading day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Holiday {
+    Closed,
+    EarlyClose,
+}
+
+/// Market calendar for session lookups
+pub struct MarketCalendar {
+    holidays: HashMap<NaiveDate, Holiday>,
+}
+
+impl MarketCalendar {
+    /// Shared US equity calendar built from the embedded table
+    pub fn us_equity() -> &'static MarketCalendar {
+        static CALENDAR: OnceLock<MarketCalendar> = OnceLock::new();
+        CALENDAR.get_or_init(|| Self::from_table(US_EQUITY_CALENDAR))
+    }
+
+    /// Build a calendar from `date,kind,name` lines
+    fn from_table(table: &str) -> Self {
+        let holidays = table
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, ',');
+                let date = NaiveDate::parse_from_str(fields.next()?, "%Y-%m-%d").ok()?;
+                let kind = match fields.next()? {
+                    "closed" => Holiday::Closed,
+                    "early_close" => Holiday::EarlyClose,
+                    _ => return None,
+                };
+                Some((date, kind))
+            })
+            .collect();
+
+        Self { holidays }
+    }
+
+    /// Session state for a timestamp on the given exchange.
+    ///
+    /// Exchanges outside the US equity venues are treated as always open
+    /// (e.g. crypto).
+    pub fn session_state(&self, timestamp: DateTime<Utc>, exchange: &str) -> SessionState {
+        if !US_EQUITY_EXCHANGES.contains(&exchange) {
+            return SessionState::Regular;
+        }
+
+        let local = timestamp.with_timezone(&eastern_offset(timestamp));
+        let date = local.date_naive();
+
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return SessionState::Closed;
+        }
+
+        let early_close = match self.holidays.get(&date) {
+            Some(Holiday::Closed) => return SessionState::Closed,
+            Some(Holiday::EarlyClose) => true,
+            None => false,
+        };
+
+        let time = local.time();
+        let (regular_close, post_close) = if early_close {
+            (hm(13, 0), hm(17, 0))
+        } else {
+            (hm(16, 0), hm(20, 0))
+        };
+
+        if time < hm(4, 0) {
+            SessionState::Closed
+        } else if time < hm(9, 30) {
+            SessionState::PreMarket
+        } else if time < regular_close {
+            SessionState::Regular
+        } else if time < post_close {
+            SessionState::PostMarket
+        } else {
+            SessionState::Closed
+        }
+    }
+
+    /// Whether the regular session is open
+    pub fn is_regular_session(&self, timestamp: DateTime<Utc>, exchange: &str) -> bool {
+        self.session_state(timestamp, exchange) == SessionState::Regular
+    }
+}
+
+fn hm(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+/// US Eastern offset, switching on the second Sunday of March and the
+/// first Sunday of November at 02:00 local time
+fn eastern_offset(timestamp: DateTime<Utc>) -> FixedOffset {
+    let year = timestamp.year();
+    let dst_start = NaiveDate::from_weekday_of_month_opt(year, 3, Weekday::Sun, 2)
+        .and_then(|date| date.and_hms_opt(7, 0, 0))
+        .map(|dt| dt.and_utc());
+    let dst_end = NaiveDate::from_weekday_of_month_opt(year, 11, Weekday::Sun, 1)
+        .and_then(|date| date.and_hms_opt(6, 0, 0))
+        .map(|dt| dt.and_utc());
+
+    let in_dst = match (dst_start, dst_end) {
+        (Some(start), Some(end)) => timestamp >= start && timestamp < end,
+        _ => false,
+    };
+
+    let hours = if in_dst { -4 } else { -5 };
+    FixedOffset::east_opt(hours * 3600).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_regular_session_in_summer() {
+        let calendar = MarketCalendar::us_equity();
+        // 10:00 EDT
+        assert_eq!(calendar.session_state(utc(2026, 7, 15, 14, 0), "NYSE"), SessionState::Regular);
+        // 08:00 EDT
+        assert_eq!(calendar.session_state(utc(2026, 7, 15, 12, 0), "NYSE"), SessionState::PreMarket);
+        // 17:00 EDT
+        assert_eq!(calendar.session_state(utc(2026, 7, 15, 21, 0), "NYSE"), SessionState::PostMarket);
+    }
+
+    #[test]
+    fn test_regular_session_in_winter() {
+        let calendar = MarketCalendar::us_equity();
+        // 09:00 EST is still pre-market, 09:30 EST opens
+        assert_eq!(calendar.session_state(utc(2026, 1, 14, 14, 0), "NASDAQ"), SessionState::PreMarket);
+        assert_eq!(calendar.session_state(utc(2026, 1, 14, 14, 30), "NASDAQ"), SessionState::Regular);
+    }
+
+    #[test]
+    fn test_weekends_and_holidays_closed() {
+        let calendar = MarketCalendar::us_equity();
+        assert_eq!(calendar.session_state(utc(2026, 10, 17, 15, 0), "NYSE"), SessionState::Closed);
+        assert_eq!(calendar.session_state(utc(2026, 11, 26, 15, 0), "NYSE"), SessionState::Closed);
+    }
+
+    #[test]
+    fn test_early_close() {
+        let calendar = MarketCalendar::us_equity();
+        // 13:30 EST on the day after Thanksgiving
+        assert_eq!(calendar.session_state(utc(2026, 11, 27, 18, 30), "NYSE"), SessionState::PostMarket);
+    }
+
+    #[test]
+    fn test_non_us_equity_exchange_always_open() {
+        let calendar = MarketCalendar::us_equity();
+        assert_eq!(calendar.session_state(utc(2026, 10, 17, 3, 0), "CRYPTO"), SessionState::Regular);
+    }
+}
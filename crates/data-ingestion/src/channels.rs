@@ -0,0 +1,53 @@
+//! Per-symbol broadcast channels
+
+use crate::MarketData;
+use dashmap::DashMap;
+use tokio::sync::broadcast;
+
+/// Lazily created broadcast channels keyed by symbol
+pub struct SymbolChannels {
+    channels: DashMap<String, broadcast::Sender<MarketData>>,
+    capacity: usize,
+}
+
+impl SymbolChannels {
+    /// Create an empty channel set with the given per-channel capacity
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            channels: DashMap::new(),
+            capacity,
+        }
+    }
+
+    /// Subscribe to a single symbol, creating its channel if needed
+    pub fn subscribe(&self, symbol: &str) -> broadcast::Receiver<MarketData> {
+        self.channels
+            .entry(symbol.to_string())
+            .or_insert_with(|| broadcast::channel(self.capacity).0)
+            .subscribe()
+    }
+
+    /// Deliver data to its symbol's subscribers, if any
+    pub fn publish(&self, data: &MarketData) {
+        let symbol = data.symbol();
+        let delivered = match self.channels.get(symbol) {
+            Some(tx) => tx.send(data.clone()).is_ok(),
+            None => return,
+        };
+
+        // Drop channels whose subscribers have all gone away
+        if !delivered {
+            self.channels.remove_if(symbol, |_, tx| tx.receiver_count() == 0);
+        }
+    }
+
+    /// Number of symbols with an open channel
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Whether no symbol channels are open
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+}
@@ -1,7 +1,10 @@
 //! Configuration for data ingestion
 
+use crate::polygon::PolygonCluster;
+use crate::symbols::InstrumentType;
+use crate::validation::{Strictness, ValidationRule};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Data ingestion configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +12,10 @@ pub struct DataIngestionConfig {
     /// Redis connection URL
     pub redis_url: String,
     
+    /// Fallback Redis instances used while `redis_url` is unavailable
+    #[serde(default)]
+    pub redis_failover: RedisFailoverConfig,
+    
     /// Polygon.io API key
     pub polygon_api_key: String,
     
@@ -20,6 +27,80 @@ pub struct DataIngestionConfig {
     
     /// Data validation settings
     pub validation: ValidationConfig,
+    
+    /// Stale feed watchdog settings
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
+    
+    /// Publishing target and payload settings
+    #[serde(default)]
+    pub publish: PublishConfig,
+    
+    /// Latest-value snapshot settings
+    #[serde(default)]
+    pub snapshot: SnapshotConfig,
+    
+    /// Aggregate gap detection settings
+    #[serde(default)]
+    pub gap_detection: GapDetectionConfig,
+    
+    /// Handling of frames that fail to parse
+    #[serde(default)]
+    pub dead_letter: DeadLetterConfig,
+    
+    /// Clock drift monitoring settings
+    #[serde(default)]
+    pub clock_drift: ClockDriftConfig,
+    
+    /// Record validated data to disk when set
+    #[serde(default)]
+    pub recording: Option<RecordingConfig>,
+    
+    /// Mapping of incoming symbols to canonical form
+    #[serde(default)]
+    pub symbol_normalization: SymbolNormalizationConfig,
+    
+    /// Which time is stored in each event's `timestamp`; the other is kept
+    /// in `secondary_timestamp`
+    #[serde(default)]
+    pub timestamp_source: TimestampSource,
+    
+    /// Trade condition codes dropped before publishing
+    #[serde(default)]
+    pub conditions_filter: ConditionsFilterConfig,
+    
+    /// Per-symbol sampling of local broadcasts; the sink still receives
+    /// every message
+    #[serde(default)]
+    pub sampling: SamplingConfig,
+}
+
+/// Origin of event timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampSource {
+    /// Time the exchange reported for the event (Polygon's `t`, or bar start
+    /// for aggregates)
+    #[default]
+    Exchange,
+    /// Time the frame was received by this process
+    Ingest,
+}
+
+impl TimestampSource {
+    /// Exchange time of an event given its `timestamp` and
+    /// `secondary_timestamp`; falls back to `timestamp` when no secondary
+    /// time was recorded
+    pub fn exchange_time(
+        self,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        secondary: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> chrono::DateTime<chrono::Utc> {
+        match self {
+            TimestampSource::Exchange => timestamp,
+            TimestampSource::Ingest => secondary.unwrap_or(timestamp),
+        }
+    }
 }
 
 /// WebSocket configuration
@@ -36,6 +117,155 @@ pub struct WebSocketConfig {
     
     /// Buffer size for incoming messages
     pub buffer_size: usize,
+    
+    /// Symbols per subscribe message
+    #[serde(default = "default_subscribe_batch_size")]
+    pub subscribe_batch_size: usize,
+    
+    /// Pause between subscribe batches in milliseconds
+    #[serde(default = "default_subscribe_batch_delay_ms")]
+    pub subscribe_batch_delay_ms: u64,
+    
+    /// Sustained rate of control messages (auth, subscribe, unsubscribe)
+    #[serde(default = "default_control_messages_per_second")]
+    pub control_messages_per_second: f64,
+    
+    /// Control messages that may be sent back to back before rate limiting
+    #[serde(default = "default_control_burst")]
+    pub control_burst: u32,
+    
+    /// Real-time, delayed, or local mock feed
+    #[serde(default)]
+    pub feed: FeedMode,
+    
+    /// Overrides the feed's base URL that cluster paths are appended to
+    #[serde(default)]
+    pub base_url: Option<String>,
+    
+    /// Cluster of the default endpoint used when `endpoints` is empty
+    #[serde(default)]
+    pub cluster: PolygonCluster,
+    
+    /// Endpoints to connect to concurrently; when empty a single endpoint
+    /// on `cluster` subscribes to `symbols`
+    #[serde(default)]
+    pub endpoints: Vec<EndpointConfig>,
+    
+    /// Whether to reconnect after the server closes the connection
+    #[serde(default)]
+    pub close_policy: ClosePolicyConfig,
+}
+
+/// Response to the server closing a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseAction {
+    /// Reconnect with the usual backoff
+    #[default]
+    Reconnect,
+    /// Stop the connection and report a fatal error
+    GiveUp,
+}
+
+/// Maps close frames to a reconnect or give-up decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosePolicyConfig {
+    /// Action per close code
+    #[serde(default)]
+    pub codes: HashMap<u16, CloseAction>,
+    
+    /// Action when the close reason contains this text (case-insensitive);
+    /// takes precedence over `codes`
+    #[serde(default)]
+    pub reasons: HashMap<String, CloseAction>,
+    
+    /// Action for close frames matching neither, or closes without a frame
+    #[serde(default)]
+    pub default_action: CloseAction,
+}
+
+impl ClosePolicyConfig {
+    /// Action for a close frame with this code and reason
+    pub fn action(&self, code: u16, reason: &str) -> CloseAction {
+        let reason = reason.to_ascii_lowercase();
+        self.reasons
+            .iter()
+            .find(|(text, _)| reason.contains(&text.to_ascii_lowercase()))
+            .map(|(_, action)| *action)
+            .or_else(|| self.codes.get(&code).copied())
+            .unwrap_or(self.default_action)
+    }
+}
+
+/// Source of the WebSocket feed
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeedMode {
+    /// Live feed at `wss://socket.polygon.io`
+    #[default]
+    RealTime,
+    /// 15-minute delayed feed at `wss://delayed.polygon.io`
+    Delayed,
+    /// Built-in local server replaying frames from a file, one per line
+    Mock { frames_path: String, interval_ms: u64 },
+}
+
+impl WebSocketConfig {
+    /// Base URL for the configured feed; mock feeds get theirs at startup
+    pub fn base_url(&self) -> &str {
+        if let Some(base_url) = &self.base_url {
+            return base_url;
+        }
+        
+        match self.feed {
+            FeedMode::Delayed => "wss://delayed.polygon.io",
+            FeedMode::RealTime | FeedMode::Mock { .. } => "wss://socket.polygon.io",
+        }
+    }
+}
+
+/// A WebSocket endpoint and the symbols subscribed on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointConfig {
+    /// Unique name used in logs and status
+    pub name: String,
+    
+    /// Polygon cluster
+    #[serde(default)]
+    pub cluster: PolygonCluster,
+    
+    /// Full WebSocket URL; defaults to the cluster path under `base_url`
+    #[serde(default)]
+    pub url: Option<String>,
+    
+    /// Channel prefixes to subscribe (e.g. `T`, `Q`, `XT`); defaults to the
+    /// cluster's trade channel
+    #[serde(default)]
+    pub channels: Vec<String>,
+    
+    /// Symbols subscribed on this connection
+    pub symbols: Vec<String>,
+}
+
+impl EndpointConfig {
+    /// URL to connect to
+    pub fn resolved_url(&self, base_url: &str) -> String {
+        self.url.clone().unwrap_or_else(|| self.cluster.url(base_url))
+    }
+
+    /// Subscription channels, e.g. `XT.X:BTC-USD`
+    pub fn subscription_channels(&self) -> Vec<String> {
+        let prefixes: Vec<&str> = if self.channels.is_empty() {
+            self.cluster.default_channels().to_vec()
+        } else {
+            self.channels.iter().map(String::as_str).collect()
+        };
+        
+        prefixes
+            .iter()
+            .flat_map(|prefix| self.symbols.iter().map(move |symbol| format!("{}.{}", prefix, symbol)))
+            .collect()
+    }
 }
 
 /// Data validation configuration
@@ -47,22 +277,391 @@ pub struct ValidationConfig {
     /// Maximum timestamp lag in seconds
     pub max_timestamp_lag: i64,
     
-    /// Enable strict validation
+    /// Enable strict validation; when off, rules without an explicit
+    /// setting warn instead of rejecting
     pub strict_validation: bool,
+    
+    /// Per-rule strictness overriding the defaults
+    #[serde(default)]
+    pub rules: HashMap<ValidationRule, Strictness>,
+    
+    /// Tick and lot sizes by symbol; symbols without a spec skip these checks
+    #[serde(default)]
+    pub symbol_specs: HashMap<String, SymbolSpec>,
+    
+    /// Sane price range by instrument type; types without bounds only
+    /// need a positive price
+    #[serde(default = "default_price_bounds")]
+    pub price_bounds: HashMap<InstrumentType, PriceBounds>,
+}
+
+/// Inclusive price range for an instrument type
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PriceBounds {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl PriceBounds {
+    /// Whether a price lies within the range
+    pub fn contains(&self, price: f64) -> bool {
+        (self.min..=self.max).contains(&price)
+    }
+}
+
+/// Exchange trading increments for a symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolSpec {
+    /// Minimum price increment
+    pub tick_size: f64,
+    
+    /// Round-lot size in shares
+    pub lot_size: u64,
+}
+
+impl SymbolSpec {
+    /// Whether a price is a whole number of ticks, within float tolerance
+    pub fn is_on_tick(&self, price: f64) -> bool {
+        if self.tick_size <= 0.0 {
+            return true;
+        }
+        let ticks = price / self.tick_size;
+        (ticks - ticks.round()).abs() < 1e-6
+    }
+
+    /// Whether a size is not a multiple of the round lot
+    pub fn is_odd_lot(&self, size: u64) -> bool {
+        self.lot_size > 1 && size % self.lot_size != 0
+    }
+}
+
+impl DataIngestionConfig {
+    /// Primary Redis URL followed by the failover URLs
+    pub fn redis_urls(&self) -> Vec<String> {
+        std::iter::once(self.redis_url.clone())
+            .chain(self.redis_failover.fallback_urls.iter().cloned())
+            .collect()
+    }
+
+    /// Endpoints to connect to, falling back to one on the configured cluster
+    pub fn endpoints(&self) -> Vec<EndpointConfig> {
+        if !self.websocket.endpoints.is_empty() {
+            return self.websocket.endpoints.clone();
+        }
+        
+        let cluster = self.websocket.cluster;
+        vec![EndpointConfig {
+            name: cluster.as_str().to_string(),
+            cluster,
+            url: None,
+            channels: Vec::new(),
+            symbols: self.symbols.clone(),
+        }]
+    }
+}
+
+impl ValidationConfig {
+    /// Effective strictness for a rule
+    pub fn strictness(&self, rule: ValidationRule) -> Strictness {
+        if let Some(strictness) = self.rules.get(&rule) {
+            return *strictness;
+        }
+        
+        match rule.default_strictness() {
+            Strictness::Reject if !self.strict_validation => Strictness::Warn,
+            strictness => strictness,
+        }
+    }
+}
+
+/// Stale feed watchdog configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchdogConfig {
+    /// Enable the watchdog
+    pub enabled: bool,
+    
+    /// Maximum seconds without data before forcing a reconnect
+    pub max_idle_seconds: u64,
+    
+    /// How often to check for an idle feed, in seconds
+    pub check_interval_seconds: u64,
+    
+    /// Only act on stocks, options and indices endpoints during market hours
+    /// so overnight silence is ignored; crypto and forex are always watched
+    pub market_hours_only: bool,
+}
+
+/// Payload encoding for Redis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SerdeFormat {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+impl SerdeFormat {
+    /// Format name recorded on stream entries
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SerdeFormat::Json => "json",
+            SerdeFormat::MessagePack => "message_pack",
+        }
+    }
+
+    /// Channel suffix telling consumers how to decode; JSON keeps the bare name
+    pub fn channel_suffix(&self) -> &'static str {
+        match self {
+            SerdeFormat::Json => "",
+            SerdeFormat::MessagePack => ":msgpack",
+        }
+    }
+}
+
+/// Where market data is published in Redis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishMode {
+    /// Pub/sub channels; disconnected consumers miss messages
+    #[default]
+    PubSub,
+    /// Per-type Redis Streams (`<channel>:stream`) for consumer groups and replay
+    Stream,
+    /// Both pub/sub and streams
+    Both,
+}
+
+impl PublishMode {
+    /// Whether pub/sub channels receive messages
+    pub fn publishes_pubsub(&self) -> bool {
+        matches!(self, PublishMode::PubSub | PublishMode::Both)
+    }
+
+    /// Whether Redis Streams receive messages
+    pub fn publishes_stream(&self) -> bool {
+        matches!(self, PublishMode::Stream | PublishMode::Both)
+    }
+}
+
+/// Messaging backend receiving validated market data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SinkKind {
+    #[default]
+    Redis,
+    /// Requires the `kafka` feature
+    Kafka,
+}
+
+/// Kafka producer configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KafkaConfig {
+    /// Comma-separated bootstrap servers
+    pub brokers: String,
+    
+    /// Topic prefix; topics are `<prefix>trades`, `<prefix>quotes`, `<prefix>aggregates`
+    pub topic_prefix: String,
+    
+    /// Delivery timeout in milliseconds
+    pub delivery_timeout_ms: u64,
+    
+    /// Extra librdkafka producer properties
+    pub properties: HashMap<String, String>,
+}
+
+/// Publishing configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishConfig {
+    /// Messaging backend
+    #[serde(default)]
+    pub sink: SinkKind,
+    
+    /// Kafka settings, used when `sink` is Kafka
+    #[serde(default)]
+    pub kafka: KafkaConfig,
+    
+    /// Payload encoding
+    #[serde(default)]
+    pub format: SerdeFormat,
+    
+    /// Redis pub/sub, streams, or both
+    #[serde(default)]
+    pub mode: PublishMode,
+    
+    /// Approximate maximum entries kept per Redis stream
+    #[serde(default = "default_stream_maxlen")]
+    pub stream_maxlen: usize,
+}
+
+/// Redis failover configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisFailoverConfig {
+    /// Fallback URLs, tried in order after the primary `redis_url`
+    pub fallback_urls: Vec<String>,
+    
+    /// Seconds between attempts to return to the primary after failing over
+    pub primary_retry_seconds: u64,
+    
+    /// Messages buffered while Redis is unavailable; the oldest are
+    /// dropped once it is full
+    pub queue_capacity: usize,
+    
+    /// Pause before retrying after every endpoint failed, in milliseconds
+    pub retry_interval_ms: u64,
+}
+
+/// Latest-value snapshot configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotConfig {
+    /// Seconds after which the last trade is reported as stale
+    pub trade_stale_seconds: i64,
+    
+    /// Seconds without a trade before a symbol is evicted
+    pub evict_after_seconds: i64,
+}
+
+/// Symbol normalization applied to incoming events
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolNormalizationConfig {
+    /// Provider symbol to canonical symbol, e.g. `BRK-B` -> `BRK.B`
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    
+    /// Prefixes removed before alias lookup, e.g. `X:` for crypto pairs
+    #[serde(default)]
+    pub strip_prefixes: Vec<String>,
+}
+
+/// Broadcast sampling configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SamplingConfig {
+    /// Minimum milliseconds between broadcasts per symbol; 0 disables
+    /// sampling for that symbol
+    #[serde(default)]
+    pub intervals_ms: HashMap<String, u64>,
+    
+    /// Interval for symbols not listed in `intervals_ms`
+    #[serde(default)]
+    pub default_interval_ms: Option<u64>,
+}
+
+/// Trade condition filtering applied after validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConditionsFilterConfig {
+    /// Trades carrying any of these codes are dropped
+    #[serde(default)]
+    pub exclude: HashSet<String>,
+    
+    /// When set, trades are kept only if every code is in this set;
+    /// trades without conditions always pass
+    #[serde(default)]
+    pub allow: Option<HashSet<String>>,
+}
+
+impl ConditionsFilterConfig {
+    /// Whether a trade with these condition codes should be published
+    pub fn allows(&self, conditions: &[String]) -> bool {
+        conditions.iter().all(|code| {
+            let allowed = match &self.allow {
+                Some(allow) => allow.contains(code),
+                None => true,
+            };
+            allowed && !self.exclude.contains(code)
+        })
+    }
+}
+
+/// Aggregate gap detection configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GapDetectionConfig {
+    /// Check aggregate sequences for missing bars
+    pub enabled: bool,
+    
+    /// Broadcast a gap event to `subscribe_gaps()` receivers
+    pub emit_events: bool,
+}
+
+/// Where frames that fail to parse are stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeadLetterTarget {
+    /// Append JSON lines to a file
+    File { path: String },
+    /// Push onto a Redis list capped at `max_entries`
+    Redis { key: String, max_entries: usize },
+}
+
+/// Parse failure diagnostics configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterConfig {
+    /// Characters of the raw frame included in parse errors
+    pub max_frame_len: usize,
+    
+    /// Optional store for the full failed frames
+    pub target: Option<DeadLetterTarget>,
+}
+
+/// Clock drift monitoring configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockDriftConfig {
+    /// Recent trades and quotes used for the estimate
+    pub window: usize,
+    
+    /// Warn when the estimated offset exceeds this many milliseconds
+    pub warn_threshold_ms: i64,
+}
+
+/// Market data recording configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingConfig {
+    /// Output file
+    pub path: String,
+    
+    /// Codec; inferred from the extension (`.gz`, `.zst`) when not set, and
+    /// must match it when set
+    pub compression: Option<crate::recorder::Compression>,
 }
 
 impl Default for DataIngestionConfig {
     fn default() -> Self {
         Self {
             redis_url: "redis://localhost:6379".to_string(),
+            redis_failover: RedisFailoverConfig::default(),
             polygon_api_key: String::new(),
             websocket: WebSocketConfig::default(),
             symbols: vec!["SPY".to_string(), "QQQ".to_string()],
             validation: ValidationConfig::default(),
+            watchdog: WatchdogConfig::default(),
+            publish: PublishConfig::default(),
+            snapshot: SnapshotConfig::default(),
+            gap_detection: GapDetectionConfig::default(),
+            dead_letter: DeadLetterConfig::default(),
+            clock_drift: ClockDriftConfig::default(),
+            recording: None,
+            symbol_normalization: SymbolNormalizationConfig::default(),
+            timestamp_source: TimestampSource::Exchange,
+            conditions_filter: ConditionsFilterConfig::default(),
+            sampling: SamplingConfig::default(),
         }
     }
 }
 
+fn default_subscribe_batch_size() -> usize {
+    500
+}
+
+fn default_subscribe_batch_delay_ms() -> u64 {
+    100
+}
+
+fn default_control_messages_per_second() -> f64 {
+    5.0
+}
+
+fn default_control_burst() -> u32 {
+    10
+}
+
 impl Default for WebSocketConfig {
     fn default() -> Self {
         Self {
@@ -70,16 +669,145 @@ impl Default for WebSocketConfig {
             max_reconnect_attempts: 5,
             heartbeat_interval: 30,
             buffer_size: 10000,
+            subscribe_batch_size: default_subscribe_batch_size(),
+            subscribe_batch_delay_ms: default_subscribe_batch_delay_ms(),
+            control_messages_per_second: default_control_messages_per_second(),
+            control_burst: default_control_burst(),
+            feed: FeedMode::default(),
+            base_url: None,
+            cluster: PolygonCluster::default(),
+            endpoints: Vec::new(),
+            close_policy: ClosePolicyConfig::default(),
         }
     }
 }
 
+impl Default for ClosePolicyConfig {
+    fn default() -> Self {
+        Self {
+            codes: HashMap::from([
+                (1001, CloseAction::Reconnect), // going away, e.g. server restart
+                (1002, CloseAction::GiveUp),    // protocol error
+                (1003, CloseAction::GiveUp),    // unsupported data
+                (1008, CloseAction::GiveUp),    // policy violation
+                (1011, CloseAction::Reconnect), // internal server error
+                (1012, CloseAction::Reconnect), // service restart
+                (1013, CloseAction::Reconnect), // try again later
+            ]),
+            reasons: HashMap::from([
+                ("authentication failed".to_string(), CloseAction::GiveUp),
+                ("not authorized".to_string(), CloseAction::GiveUp),
+            ]),
+            default_action: CloseAction::default(),
+        }
+    }
+}
+
+fn default_price_bounds() -> HashMap<InstrumentType, PriceBounds> {
+    HashMap::from([
+        (InstrumentType::Equity, PriceBounds { min: 0.0001, max: 1_000_000.0 }),
+        (InstrumentType::Etf, PriceBounds { min: 0.01, max: 100_000.0 }),
+        (InstrumentType::Option, PriceBounds { min: 0.01, max: 100_000.0 }),
+        (InstrumentType::Crypto, PriceBounds { min: 1e-8, max: 10_000_000.0 }),
+        (InstrumentType::Forex, PriceBounds { min: 1e-5, max: 1_000_000.0 }),
+        (InstrumentType::Index, PriceBounds { min: 0.01, max: 1_000_000.0 }),
+    ])
+}
+
 impl Default for ValidationConfig {
     fn default() -> Self {
         Self {
             max_price_deviation: 10.0, // 10%
             max_timestamp_lag: 5, // 5 seconds
             strict_validation: true,
+            rules: HashMap::new(),
+            symbol_specs: HashMap::new(),
+            price_bounds: default_price_bounds(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_idle_seconds: 30,
+            check_interval_seconds: 5,
+            market_hours_only: true,
+        }
+    }
+}
+
+fn default_stream_maxlen() -> usize {
+    100_000
+}
+
+impl Default for PublishConfig {
+    fn default() -> Self {
+        Self {
+            sink: SinkKind::default(),
+            kafka: KafkaConfig::default(),
+            format: SerdeFormat::default(),
+            mode: PublishMode::default(),
+            stream_maxlen: default_stream_maxlen(),
+        }
+    }
+}
+
+impl Default for KafkaConfig {
+    fn default() -> Self {
+        Self {
+            brokers: "localhost:9092".to_string(),
+            topic_prefix: "market_data.".to_string(),
+            delivery_timeout_ms: 5000,
+            properties: HashMap::new(),
+        }
+    }
+}
+
+impl Default for RedisFailoverConfig {
+    fn default() -> Self {
+        Self {
+            fallback_urls: Vec::new(),
+            primary_retry_seconds: 30,
+            queue_capacity: 10_000,
+            retry_interval_ms: 500,
+        }
+    }
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            trade_stale_seconds: 60,
+            evict_after_seconds: 86_400, // 1 day
+        }
+    }
+}
+
+impl Default for GapDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            emit_events: false,
+        }
+    }
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> Self {
+        Self {
+            max_frame_len: 512,
+            target: None,
+        }
+    }
+}
+
+impl Default for ClockDriftConfig {
+    fn default() -> Self {
+        Self {
+            window: 1000,
+            warn_threshold_ms: 250,
+        }
+    }
+}
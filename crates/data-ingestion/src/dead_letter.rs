@@ -0,0 +1,69 @@
+//! Dead-letter storage for frames that fail to parse
+
+use crate::config::DeadLetterTarget;
+use anyhow::Result;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+
+/// Record written for each rejected frame
+#[derive(Debug, Serialize)]
+struct DeadLetter<'a> {
+    received_at: chrono::DateTime<chrono::Utc>,
+    error: String,
+    frame: &'a str,
+}
+
+/// Writes failed frames to a JSON-lines file or a capped Redis list
+pub enum DeadLetterSink {
+    File(std::path::PathBuf),
+    Redis {
+        client: redis::Client,
+        key: String,
+        max_entries: usize,
+    },
+}
+
+impl DeadLetterSink {
+    /// Build a sink for the configured target
+    pub fn new(target: &DeadLetterTarget, redis_url: &str) -> Result<Self> {
+        Ok(match target {
+            DeadLetterTarget::File { path } => Self::File(path.into()),
+            DeadLetterTarget::Redis { key, max_entries } => Self::Redis {
+                client: redis::Client::open(redis_url)?,
+                key: key.clone(),
+                max_entries: *max_entries,
+            },
+        })
+    }
+
+    /// Store the full frame along with the parse error
+    pub async fn record(&self, frame: &str, error: &dyn std::fmt::Display) -> Result<()> {
+        let line = serde_json::to_string(&DeadLetter {
+            received_at: chrono::Utc::now(),
+            error: error.to_string(),
+            frame,
+        })?;
+
+        match self {
+            Self::File(path) => {
+                let mut file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await?;
+                file.write_all(line.as_bytes()).await?;
+                file.write_all(b"\n").await?;
+            }
+            Self::Redis { client, key, max_entries } => {
+                let mut conn = client.get_async_connection().await?;
+                redis::pipe()
+                    .cmd("LPUSH").arg(key).arg(line).ignore()
+                    .cmd("LTRIM").arg(key).arg(0).arg(max_entries.saturating_sub(1)).ignore()
+                    .query_async::<_, ()>(&mut conn)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+//! Gap detection in aggregate bar sequences
+
+use crate::calendar::{MarketCalendar, SessionState};
+use crate::config::TimestampSource;
+use crate::AggregateData;
+use chrono::{DateTime, Duration, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+
+/// Missing bars between two consecutive aggregates for a symbol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateGap {
+    pub symbol: String,
+    /// Start of the first missing bar
+    pub expected: DateTime<Utc>,
+    /// Start of the bar that arrived instead
+    pub received: DateTime<Utc>,
+    pub missing_bars: u64,
+}
+
+/// Per-symbol check that each bar starts where the previous one ended.
+///
+/// Gaps that span a closed market (overnight, weekends, holidays) are
+/// expected and not reported. Polygon omits bars for minutes without
+/// trades, so illiquid symbols can report gaps during the session too.
+/// Bars are placed by their start time whichever timestamp source is stored.
+#[derive(Default)]
+pub struct GapDetector {
+    last_bar: DashMap<String, DateTime<Utc>>,
+    source: TimestampSource,
+}
+
+impl GapDetector {
+    /// Create a detector with no history for bars stamped from `source`
+    pub fn new(source: TimestampSource) -> Self {
+        Self {
+            last_bar: DashMap::new(),
+            source,
+        }
+    }
+
+    /// Record a bar, returning the gap before it if any. Late or repeated
+    /// bars leave the symbol's latest bar unchanged.
+    pub fn check(&self, agg: &AggregateData) -> Option<AggregateGap> {
+        let bar = timespan_duration(&agg.timespan);
+        let start = self.source.exchange_time(agg.timestamp, agg.secondary_timestamp);
+        let previous = {
+            let mut last = self.last_bar.entry(agg.symbol.clone()).or_insert(start);
+            let previous = *last;
+            if start <= previous {
+                return None;
+            }
+            *last = start;
+            previous
+        };
+
+        let expected = previous + bar;
+        if start <= expected {
+            return None;
+        }
+
+        let calendar = MarketCalendar::us_equity();
+        if calendar.session_state(expected, "NYSE") == SessionState::Closed {
+            return None;
+        }
+
+        let missing_bars = (start - expected).num_seconds() / bar.num_seconds().max(1);
+        Some(AggregateGap {
+            symbol: agg.symbol.clone(),
+            expected,
+            received: start,
+            missing_bars: missing_bars.max(1) as u64,
+        })
+    }
+}
+
+/// Bar length for a timespan such as `1s`, `1m` or `5m`; defaults to one minute
+fn timespan_duration(timespan: &str) -> Duration {
+    let (count, unit) = timespan.split_at(timespan.len().saturating_sub(1));
+    let count: i64 = count.parse().unwrap_or(1);
+    match unit {
+        "s" => Duration::seconds(count),
+        "h" => Duration::hours(count),
+        _ => Duration::minutes(count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+    use chrono::TimeZone;
+
+    fn bar(timestamp: DateTime<Utc>) -> AggregateData {
+        AggregateData {
+            symbol: "SPY".to_string(),
+            original_symbol: None,
+            open: price::from_f64(500.0),
+            high: price::from_f64(500.5),
+            low: price::from_f64(499.5),
+            close: price::from_f64(500.2),
+            volume: 10_000,
+            timestamp,
+            secondary_timestamp: None,
+            timespan: "1m".to_string(),
+            vwap: None,
+            transactions: None,
+        }
+    }
+
+    #[test]
+    fn test_missing_minutes_are_reported() {
+        let detector = GapDetector::new(TimestampSource::Exchange);
+        // 10:00 and 10:04 EDT
+        let start = Utc.with_ymd_and_hms(2026, 7, 15, 14, 0, 0).unwrap();
+        assert!(detector.check(&bar(start)).is_none());
+        assert!(detector.check(&bar(start + Duration::minutes(1))).is_none());
+
+        let gap = detector.check(&bar(start + Duration::minutes(4))).unwrap();
+        assert_eq!(gap.expected, start + Duration::minutes(2));
+        assert_eq!(gap.missing_bars, 2);
+    }
+
+    #[test]
+    fn test_late_bar_does_not_rewind_sequence() {
+        let detector = GapDetector::new(TimestampSource::Exchange);
+        let start = Utc.with_ymd_and_hms(2026, 7, 15, 14, 0, 0).unwrap();
+        detector.check(&bar(start));
+        detector.check(&bar(start + Duration::minutes(1)));
+        detector.check(&bar(start + Duration::minutes(2)));
+
+        // A late copy of an earlier bar is neither a gap nor the new latest
+        assert!(detector.check(&bar(start)).is_none());
+        assert!(detector.check(&bar(start + Duration::minutes(3))).is_none());
+    }
+
+    #[test]
+    fn test_overnight_gap_is_expected() {
+        let detector = GapDetector::new(TimestampSource::Exchange);
+        // 19:59 EDT, then 04:00 EDT the next day
+        detector.check(&bar(Utc.with_ymd_and_hms(2026, 7, 15, 23, 59, 0).unwrap()));
+
+        assert!(detector
+            .check(&bar(Utc.with_ymd_and_hms(2026, 7, 16, 8, 0, 0).unwrap()))
+            .is_none());
+    }
+
+    #[test]
+    fn test_ingest_stamped_bars_use_bar_start() {
+        let detector = GapDetector::new(TimestampSource::Ingest);
+        let start = Utc.with_ymd_and_hms(2026, 7, 15, 14, 0, 0).unwrap();
+        let ingested = |bar_start: DateTime<Utc>, delay_ms: i64| AggregateData {
+            timestamp: bar_start + Duration::minutes(1) + Duration::milliseconds(delay_ms),
+            secondary_timestamp: Some(bar_start),
+            ..bar(bar_start)
+        };
+
+        // Delivery jitter alone is not a gap
+        assert!(detector.check(&ingested(start, 50)).is_none());
+        assert!(detector.check(&ingested(start + Duration::minutes(1), 900)).is_none());
+
+        let gap = detector.check(&ingested(start + Duration::minutes(4), 10)).unwrap();
+        assert_eq!(gap.expected, start + Duration::minutes(2));
+        assert_eq!(gap.received, start + Duration::minutes(4));
+        assert_eq!(gap.missing_bars, 2);
+    }
+}
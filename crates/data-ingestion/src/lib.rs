@@ -11,16 +11,35 @@
 //! ## Architecture
 //! - WebSocket client for real-time feeds
 //! - Data validation and normalization pipeline
-//! - Redis publishing for downstream services
+//! - Redis or Kafka publishing for downstream services
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tokio::sync::broadcast;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
 use tracing::{debug, error, info, warn};
 
+pub use price::Price;
+
+pub mod analytics;
+pub mod calendar;
+pub mod channels;
 pub mod config;
+pub mod dead_letter;
+pub mod gaps;
+pub mod metrics;
+pub mod mock_server;
 pub mod polygon;
+pub mod price;
+pub mod rate_limit;
+pub mod recorder;
+pub mod sampling;
+pub mod sink;
+pub mod snapshot;
+pub mod symbols;
+pub mod tick;
 pub mod validation;
 pub mod websocket;
 
@@ -32,13 +51,102 @@ pub enum MarketData {
     Aggregate(AggregateData),
 }
 
+impl MarketData {
+    /// Symbol the data refers to
+    pub fn symbol(&self) -> &str {
+        match self {
+            MarketData::Trade(trade) => &trade.symbol,
+            MarketData::Quote(quote) => &quote.symbol,
+            MarketData::Aggregate(agg) => &agg.symbol,
+        }
+    }
+
+    /// Event time selected by `timestamp_source`: exchange time by default,
+    /// or ingest time with `TimestampSource::Ingest`
+    pub fn timestamp(&self) -> chrono::DateTime<chrono::Utc> {
+        match self {
+            MarketData::Trade(trade) => trade.timestamp,
+            MarketData::Quote(quote) => quote.timestamp,
+            MarketData::Aggregate(agg) => agg.timestamp,
+        }
+    }
+
+    /// Exchange timestamp of the event, given the source `timestamp` holds
+    pub fn exchange_timestamp(&self, source: config::TimestampSource) -> Option<chrono::DateTime<chrono::Utc>> {
+        match source {
+            config::TimestampSource::Exchange => Some(self.timestamp()),
+            config::TimestampSource::Ingest => self.secondary_timestamp(),
+        }
+    }
+
+    /// Timestamp not selected by the configured source, if recorded
+    pub fn secondary_timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        match self {
+            MarketData::Trade(trade) => trade.secondary_timestamp,
+            MarketData::Quote(quote) => quote.secondary_timestamp,
+            MarketData::Aggregate(agg) => agg.secondary_timestamp,
+        }
+    }
+
+    /// Record when the event was received, storing it as `timestamp` or as
+    /// the secondary timestamp depending on `source`
+    pub fn stamp_ingest_time(&mut self, received_at: chrono::DateTime<chrono::Utc>, source: config::TimestampSource) {
+        let (timestamp, secondary) = match self {
+            MarketData::Trade(trade) => (&mut trade.timestamp, &mut trade.secondary_timestamp),
+            MarketData::Quote(quote) => (&mut quote.timestamp, &mut quote.secondary_timestamp),
+            MarketData::Aggregate(agg) => (&mut agg.timestamp, &mut agg.secondary_timestamp),
+        };
+        *secondary = Some(match source {
+            config::TimestampSource::Exchange => received_at,
+            config::TimestampSource::Ingest => std::mem::replace(timestamp, received_at),
+        });
+    }
+
+    /// Serialize as JSON into `buffer`, replacing its contents
+    pub fn write_json(&self, buffer: &mut Vec<u8>) -> serde_json::Result<()> {
+        buffer.clear();
+        serde_json::to_writer(buffer, self)
+    }
+
+    /// Serialize in the given format into `buffer`, replacing its contents.
+    ///
+    /// MessagePack payloads keep field names so consumers can decode them
+    /// like the JSON form.
+    pub fn write_payload(&self, format: config::SerdeFormat, buffer: &mut Vec<u8>) -> Result<()> {
+        match format {
+            config::SerdeFormat::Json => self.write_json(buffer)?,
+            config::SerdeFormat::MessagePack => {
+                buffer.clear();
+                rmp_serde::encode::write_named(buffer, self)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Short name of the message type, used as a metric label
+    pub fn message_type(&self) -> &'static str {
+        match self {
+            MarketData::Trade(_) => "trade",
+            MarketData::Quote(_) => "quote",
+            MarketData::Aggregate(_) => "aggregate",
+        }
+    }
+}
+
 /// Trade data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeData {
     pub symbol: String,
-    pub price: f64,
+    /// Symbol as received, when normalization changed it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_symbol: Option<String>,
+    pub price: Price,
     pub size: u64,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// The timestamp not selected by `timestamp_source`: ingest time when
+    /// `timestamp` holds exchange time, and vice versa
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_timestamp: Option<chrono::DateTime<chrono::Utc>>,
     pub exchange: String,
     pub conditions: Vec<String>,
 }
@@ -47,109 +155,651 @@ pub struct TradeData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuoteData {
     pub symbol: String,
-    pub bid_price: f64,
-    pub ask_price: f64,
+    /// Symbol as received, when normalization changed it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_symbol: Option<String>,
+    pub bid_price: Price,
+    pub ask_price: Price,
     pub bid_size: u64,
     pub ask_size: u64,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// The timestamp not selected by `timestamp_source`: ingest time when
+    /// `timestamp` holds exchange time, and vice versa
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// Bid and ask venues joined as `bid|ask`; kept for existing consumers,
+    /// prefer `bid_exchange` and `ask_exchange`
     pub exchange: String,
+    /// Venue of the best bid
+    #[serde(default)]
+    pub bid_exchange: String,
+    /// Venue of the best ask
+    #[serde(default)]
+    pub ask_exchange: String,
 }
 
 /// Aggregate data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AggregateData {
     pub symbol: String,
-    pub open: f64,
-    pub high: f64,
-    pub low: f64,
-    pub close: f64,
+    /// Symbol as received, when normalization changed it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_symbol: Option<String>,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
     pub volume: u64,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// The timestamp not selected by `timestamp_source`: ingest time when
+    /// `timestamp` holds exchange time, and vice versa
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_timestamp: Option<chrono::DateTime<chrono::Utc>>,
     pub timespan: String,
+    /// Volume-weighted average price of the bar, when the feed reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vwap: Option<Price>,
+    /// Number of trades in the bar, when the feed reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transactions: Option<u64>,
+}
+
+/// Health status of the ingestion pipeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub websocket_connected: bool,
+    pub last_message_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub sink: String,
+    /// Reported as `redis_reachable` before sinks were pluggable
+    #[serde(alias = "redis_reachable")]
+    pub sink_reachable: bool,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// Data ingestion manager
 pub struct DataIngestionManager {
     config: config::DataIngestionConfig,
-    redis_client: redis::Client,
+    sink: Box<dyn sink::Sink>,
     market_data_tx: broadcast::Sender<MarketData>,
+    symbol_channels: channels::SymbolChannels,
     websocket_manager: websocket::WebSocketManager,
+    latency: metrics::LatencyTracker,
+    quotes: snapshot::QuoteSnapshots,
+    trades: snapshot::TradeSnapshots,
+    gap_detector: gaps::GapDetector,
+    gap_tx: broadcast::Sender<gaps::AggregateGap>,
+    custom_validators: Vec<validation::CustomValidator>,
+    clock_drift: metrics::ClockDriftEstimator,
+    recorder: Option<parking_lot::Mutex<recorder::Recorder>>,
+    sampler: sampling::Sampler,
 }
 
 impl DataIngestionManager {
-    /// Create new data ingestion manager
+    /// Create new data ingestion manager publishing to the configured sink
     pub async fn new(config: config::DataIngestionConfig) -> Result<Self> {
-        let redis_client = redis::Client::open(config.redis_url.clone())?;
+        let sink = sink::from_config(&config)?;
+        Self::with_sink(config, sink).await
+    }
+
+    /// Create a manager publishing to an explicit sink
+    pub async fn with_sink(config: config::DataIngestionConfig, sink: Box<dyn sink::Sink>) -> Result<Self> {
         let (market_data_tx, _) = broadcast::channel(10000);
+        let trades = snapshot::TradeSnapshots::new(
+            chrono::Duration::seconds(config.snapshot.trade_stale_seconds),
+            chrono::Duration::seconds(config.snapshot.evict_after_seconds),
+            config.timestamp_source,
+        );
+        let quotes = snapshot::QuoteSnapshots::new(config.timestamp_source);
+        let gap_detector = gaps::GapDetector::new(config.timestamp_source);
+        let clock_drift = metrics::ClockDriftEstimator::new(config.clock_drift.window);
+        let recorder = match &config.recording {
+            Some(recording) => {
+                let recorder = match recording.compression {
+                    Some(compression) => recorder::Recorder::with_compression(&recording.path, compression)?,
+                    None => recorder::Recorder::create(&recording.path)?,
+                };
+                Some(parking_lot::Mutex::new(recorder))
+            }
+            None => None,
+        };
+        let symbol_channels = channels::SymbolChannels::new(config.websocket.buffer_size);
         let websocket_manager = websocket::WebSocketManager::new(&config).await?;
+        let sampler = sampling::Sampler::new(&config.sampling);
 
         Ok(Self {
             config,
-            redis_client,
+            sink,
             market_data_tx,
+            symbol_channels,
             websocket_manager,
+            latency: metrics::LatencyTracker::new(),
+            quotes,
+            trades,
+            gap_detector,
+            gap_tx: broadcast::channel(1000).0,
+            custom_validators: Vec::new(),
+            clock_drift,
+            recorder,
+            sampler,
         })
     }
 
-    /// Start data ingestion
+    /// Register a validator run after the built-in rules.
+    ///
+    /// Failures are rejected or logged according to `strictness`.
+    pub fn add_validator<F>(&mut self, name: &str, strictness: validation::Strictness, check: F)
+    where
+        F: Fn(&MarketData) -> validation::Result<()> + Send + Sync + 'static,
+    {
+        self.custom_validators.push(validation::CustomValidator {
+            name: name.to_string(),
+            strictness,
+            check: Box::new(check),
+        });
+    }
+
+    /// Start data ingestion.
+    ///
+    /// Runs until the feed ends, or fails with `websocket::ServerError` if
+    /// Polygon rejects the API key or closes the connection for good.
     pub async fn start(&mut self) -> Result<()> {
         info!("Starting data ingestion manager");
         
+        // Subscribe before connecting so an early auth failure or the first
+        // frames are not missed
+        let rx = self.websocket_manager.subscribe();
+        let fatal_rx = self.websocket_manager.subscribe_fatal();
+        
         // Start WebSocket connections
         self.websocket_manager.start().await?;
         
         // Start data processing loop
-        self.process_data().await?;
+        self.process_data(rx, fatal_rx).await?;
         
         Ok(())
     }
 
     /// Process incoming market data
-    async fn process_data(&mut self) -> Result<()> {
-        let mut rx = self.websocket_manager.subscribe();
+    async fn process_data(
+        &mut self,
+        mut rx: broadcast::Receiver<MarketData>,
+        mut fatal_rx: broadcast::Receiver<websocket::ServerError>,
+    ) -> Result<()> {
+        let mut watchdog = tokio::time::interval(std::time::Duration::from_secs(
+            self.config.watchdog.check_interval_seconds.max(1),
+        ));
+        let mut sample_flush = tokio::time::interval(
+            self.sampler.tick_interval().unwrap_or(std::time::Duration::from_secs(1)),
+        );
         
-        while let Ok(data) = rx.recv().await {
-            // Validate data
-            if let Err(e) = validation::validate_market_data(&data) {
-                warn!("Invalid market data: {:?}", e);
-                continue;
+        loop {
+            tokio::select! {
+                received = rx.recv() => {
+                    let Ok(data) = received else { break };
+                    self.handle_market_data(data).await;
+                }
+                Ok(e) = fatal_rx.recv() => return Err(e.into()),
+                _ = sample_flush.tick() => {
+                    for data in self.sampler.flush_due(std::time::Instant::now()) {
+                        self.broadcast(data);
+                    }
+                }
+                _ = watchdog.tick() => {
+                    self.check_feed_idle();
+                    self.check_clock_drift();
+                    if let Some(recorder) = &self.recorder {
+                        if let Err(e) = recorder.lock().flush() {
+                            error!("Failed to flush recording: {:?}", e);
+                        }
+                    }
+                    let evicted = self.trades.evict_expired(chrono::Utc::now());
+                    if evicted > 0 {
+                        debug!("Evicted {} inactive symbols from the trade cache", evicted);
+                    }
+                }
             }
-            
-            // Publish to Redis
-            if let Err(e) = self.publish_to_redis(&data).await {
-                error!("Failed to publish to Redis: {:?}", e);
+        }
+        
+        Ok(())
+    }
+
+    /// Validate, publish and broadcast a single message
+    #[tracing::instrument(skip_all, fields(symbol = %data.symbol()))]
+    async fn handle_market_data(&self, data: MarketData) {
+        // Aggregates are stamped with bar start, so only ticks inform drift
+        let exchange_timestamp = data.exchange_timestamp(self.config.timestamp_source);
+        if let Some(timestamp) = exchange_timestamp.filter(|_| !matches!(data, MarketData::Aggregate(_))) {
+            self.clock_drift.record(timestamp, chrono::Utc::now());
+        }
+        
+        // Validate data
+        let validated = validation::validate_market_data(&data, &self.config.validation)
+            .and_then(|_| validation::run_custom_validators(&data, &self.custom_validators));
+        if let Err(e) = validated {
+            warn!("Invalid market data: {:?}", e);
+            return;
+        }
+        if let MarketData::Trade(trade) = &data {
+            if !self.config.conditions_filter.allows(&trade.conditions) {
+                debug!("Dropping {} trade with conditions {:?}", trade.symbol, trade.conditions);
+                metrics::record_filtered_trade();
+                return;
             }
-            
-            // Broadcast to local subscribers
-            if let Err(e) = self.market_data_tx.send(data) {
-                warn!("Failed to broadcast market data: {:?}", e);
+        }
+        
+        match &data {
+            MarketData::Quote(quote) => self.quotes.update(quote),
+            MarketData::Trade(trade) => self.trades.update(trade),
+            MarketData::Aggregate(agg) => self.check_aggregate_gap(agg),
+        }
+        
+        if let Some(recorder) = &self.recorder {
+            if let Err(e) = recorder.lock().record(&data) {
+                error!("Failed to record market data: {:?}", e);
             }
         }
         
-        Ok(())
+        // Publish downstream
+        if let Err(e) = self.sink.publish(&data).await {
+            error!("Failed to publish to {}: {:?}", self.sink.name(), e);
+        }
+        if let Some(timestamp) = exchange_timestamp {
+            self.latency.record(data.message_type(), timestamp, chrono::Utc::now());
+        }
+        
+        // Broadcast to local subscribers, sampled per symbol
+        if let Some(data) = self.sampler.offer(data, std::time::Instant::now()) {
+            self.broadcast(data);
+        }
+    }
+
+    /// Send a message to local subscribers
+    fn broadcast(&self, data: MarketData) {
+        self.symbol_channels.publish(&data);
+        if let Err(e) = self.market_data_tx.send(data) {
+            warn!("Failed to broadcast market data: {:?}", e);
+        }
     }
 
-    /// Publish market data to Redis
-    async fn publish_to_redis(&self, data: &MarketData) -> Result<()> {
-        let mut conn = self.redis_client.get_async_connection().await?;
-        let channel = match data {
-            MarketData::Trade(_) => "market_data:trades",
-            MarketData::Quote(_) => "market_data:quotes", 
-            MarketData::Aggregate(_) => "market_data:aggregates",
+    /// Flag missing bars in a symbol's aggregate sequence
+    fn check_aggregate_gap(&self, agg: &AggregateData) {
+        let gap_detection = &self.config.gap_detection;
+        if !gap_detection.enabled {
+            return;
+        }
+        
+        let Some(gap) = self.gap_detector.check(agg) else {
+            return;
         };
         
-        let payload = serde_json::to_string(data)?;
-        redis::cmd("PUBLISH")
-            .arg(channel)
-            .arg(payload)
-            .query_async(&mut conn)
-            .await?;
+        warn!(
+            "{} missing {} aggregate bars from {}",
+            gap.symbol, gap.missing_bars, gap.expected
+        );
+        metrics::record_aggregate_gap(gap.missing_bars);
+        
+        if gap_detection.emit_events {
+            // No receivers is fine
+            let _ = self.gap_tx.send(gap);
+        }
+    }
+
+    /// Warn when the local clock appears to have drifted from exchange time
+    fn check_clock_drift(&self) {
+        let Some(offset_ms) = self.clock_drift.estimate_ms() else {
+            return;
+        };
+        if offset_ms.abs() > self.config.clock_drift.warn_threshold_ms {
+            warn!("Local clock is {}ms off exchange time; check NTP", offset_ms);
+        }
+    }
+
+    /// Force a reconnect of each endpoint whose feed has gone silent
+    fn check_feed_idle(&mut self) {
+        let watchdog = &self.config.watchdog;
+        if !watchdog.enabled {
+            return;
+        }
+        
+        let now = chrono::Utc::now();
+        let equity_session = calendar::MarketCalendar::us_equity().is_regular_session(now, "NYSE");
+        let max_idle = watchdog.max_idle_seconds as i64;
+        for (endpoint, cluster, idle) in self.websocket_manager.idle_endpoints(now) {
+            // Overnight silence is expected only where trading stops
+            if watchdog.market_hours_only && cluster.follows_equity_hours() && !equity_session {
+                continue;
+            }
+            if idle.num_seconds() < max_idle {
+                continue;
+            }
             
-        Ok(())
+            warn!(endpoint = %endpoint, "No market data for {}s, forcing reconnect", idle.num_seconds());
+            metrics::record_watchdog_reconnect();
+            
+            if let Err(e) = self.websocket_manager.reconnect_endpoint(&endpoint) {
+                error!("Watchdog reconnect of {} failed: {:?}", endpoint, e);
+            }
+        }
     }
 
     /// Subscribe to market data
     pub fn subscribe(&self) -> broadcast::Receiver<MarketData> {
         self.market_data_tx.subscribe()
     }
-}
\ No newline at end of file
+
+    /// Market data as an async `Stream` for use with stream combinators.
+    ///
+    /// Backed by the same broadcast channel as `subscribe()`: a consumer
+    /// that falls more than the channel capacity behind skips the oldest
+    /// messages instead of slowing ingestion. Skips are logged and counted
+    /// in `metrics::STREAM_LAGGED_MESSAGES`, and the stream resumes with the
+    /// oldest message still buffered.
+    pub fn stream(&self) -> impl Stream<Item = MarketData> + Send + 'static {
+        BroadcastStream::new(self.market_data_tx.subscribe()).filter_map(|item| match item {
+            Ok(data) => Some(data),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                warn!("Market data stream lagged, skipped {} messages", skipped);
+                metrics::record_stream_lag(skipped);
+                None
+            }
+        })
+    }
+
+    /// Subscribe to aggregate gap events (requires `gap_detection.emit_events`)
+    pub fn subscribe_gaps(&self) -> broadcast::Receiver<gaps::AggregateGap> {
+        self.gap_tx.subscribe()
+    }
+
+    /// Subscribe to market data for a single symbol
+    pub fn subscribe_symbol(&self, symbol: &str) -> broadcast::Receiver<MarketData> {
+        self.symbol_channels.subscribe(symbol)
+    }
+
+    /// Latest quote seen for a symbol
+    pub fn latest_quote(&self, symbol: &str) -> Option<QuoteData> {
+        self.quotes.latest(symbol)
+    }
+
+    /// Last trade for a symbol and whether it is older than the staleness window
+    pub fn latest_trade(&self, symbol: &str) -> Option<(TradeData, bool)> {
+        self.trades.latest(symbol, chrono::Utc::now())
+    }
+
+    /// Estimated local clock offset from exchange time in milliseconds;
+    /// negative means the local clock is behind
+    pub fn clock_offset_ms(&self) -> Option<i64> {
+        self.clock_drift.estimate_ms()
+    }
+
+    /// Exchange-to-publish latency percentiles by message type
+    pub fn latency_stats(&self) -> HashMap<String, metrics::LatencyStats> {
+        self.latency.stats()
+    }
+
+    /// Report connection and sink health for readiness probes
+    pub async fn health(&self) -> HealthReport {
+        let websocket_connected = self.websocket_manager.is_connected();
+        let sink_reachable = self.sink.ping().await.is_ok();
+
+        HealthReport {
+            healthy: websocket_connected && sink_reachable,
+            websocket_connected,
+            last_message_at: self.websocket_manager.last_message_at(),
+            sink: self.sink.name().to_string(),
+            sink_reachable,
+            checked_at: chrono::Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_report_accepts_legacy_redis_field() {
+        let report: HealthReport = serde_json::from_str(
+            r#"{"healthy":true,"websocket_connected":true,"last_message_at":null,
+                "sink":"redis","redis_reachable":true,"checked_at":"2026-10-16T00:00:00Z"}"#,
+        )
+        .unwrap();
+
+        assert!(report.sink_reachable);
+    }
+
+    #[test]
+    fn test_message_pack_payload_round_trips_and_is_smaller() {
+        let data = MarketData::Quote(QuoteData {
+            symbol: "AAPL".to_string(),
+            original_symbol: None,
+            bid_price: price::from_f64(189.5),
+            ask_price: price::from_f64(189.52),
+            bid_size: 300,
+            ask_size: 100,
+            timestamp: chrono::Utc::now(),
+            secondary_timestamp: None,
+            exchange: "NASDAQ|NYSE".to_string(),
+            bid_exchange: "NASDAQ".to_string(),
+            ask_exchange: "NYSE".to_string(),
+        });
+
+        let mut json = Vec::new();
+        data.write_payload(config::SerdeFormat::Json, &mut json).unwrap();
+        let mut msgpack = Vec::new();
+        data.write_payload(config::SerdeFormat::MessagePack, &mut msgpack).unwrap();
+
+        let decoded: MarketData = rmp_serde::from_slice(&msgpack).unwrap();
+        assert_eq!(decoded.symbol(), "AAPL");
+        assert!(msgpack.len() < json.len());
+    }
+
+    #[test]
+    fn test_timestamp_source_selects_primary_timestamp() {
+        let exchange_time = chrono::Utc::now() - chrono::Duration::milliseconds(250);
+        let received_at = chrono::Utc::now();
+        let trade = MarketData::Trade(TradeData {
+            symbol: "AAPL".to_string(),
+            original_symbol: None,
+            price: price::from_f64(189.5),
+            size: 100,
+            timestamp: exchange_time,
+            secondary_timestamp: None,
+            exchange: "NASDAQ".to_string(),
+            conditions: vec![],
+        });
+
+        let mut exchange = trade.clone();
+        exchange.stamp_ingest_time(received_at, config::TimestampSource::Exchange);
+        assert_eq!(exchange.timestamp(), exchange_time);
+        assert_eq!(exchange.secondary_timestamp(), Some(received_at));
+
+        let mut ingest = trade;
+        ingest.stamp_ingest_time(received_at, config::TimestampSource::Ingest);
+        assert_eq!(ingest.timestamp(), received_at);
+        assert_eq!(ingest.exchange_timestamp(config::TimestampSource::Ingest), Some(exchange_time));
+    }
+
+    #[test]
+    fn test_config_sections_default_when_missing() {
+        for key in [
+            "watchdog",
+            "publish",
+            "publish.sink",
+            "publish.kafka",
+            "publish.format",
+            "publish.mode",
+            "publish.stream_maxlen",
+            "snapshot",
+            "gap_detection",
+            "dead_letter",
+            "clock_drift",
+            "recording",
+            "websocket.subscribe_batch_size",
+            "websocket.subscribe_batch_delay_ms",
+            "websocket.control_messages_per_second",
+            "websocket.control_burst",
+            "websocket.cluster",
+            "websocket.feed",
+            "websocket.close_policy.default_action",
+        ] {
+            let mut value = serde_json::to_value(config::DataIngestionConfig::default()).unwrap();
+            let (parent, field) = key.rsplit_once('.').unwrap_or(("", key));
+            let section = parent.split('.').filter(|s| !s.is_empty()).fold(&mut value, |v, s| &mut v[s]);
+            section.as_object_mut().unwrap().remove(field);
+
+            if let Err(e) = serde_json::from_value::<config::DataIngestionConfig>(value) {
+                panic!("config without `{}` was rejected: {}", key, e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_conditions_filter_excludes_and_allows_codes() {
+        let codes = |codes: &[&str]| codes.iter().map(|code| code.to_string()).collect::<Vec<_>>();
+        let exclude = config::ConditionsFilterConfig {
+            exclude: ["12", "41"].iter().map(|code| code.to_string()).collect(),
+            allow: None,
+        };
+        assert!(exclude.allows(&[]));
+        assert!(exclude.allows(&codes(&["14"])));
+        assert!(!exclude.allows(&codes(&["14", "41"])));
+
+        let allow = config::ConditionsFilterConfig {
+            exclude: Default::default(),
+            allow: Some(["14"].iter().map(|code| code.to_string()).collect()),
+        };
+        assert!(allow.allows(&[]));
+        assert!(allow.allows(&codes(&["14"])));
+        assert!(!allow.allows(&codes(&["14", "37"])));
+    }
+
+    async fn mock_feed(frames: Vec<String>, interval: std::time::Duration) -> mock_server::MockServer {
+        mock_server::MockServer::start(mock_server::MockServerConfig {
+            frames,
+            interval,
+            close_after_replay: false,
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Manager with every endpoint connected
+    async fn connected_manager(config: config::DataIngestionConfig) -> DataIngestionManager {
+        let sink = Box::new(sink::RedisSink::new("redis://127.0.0.1:6379", Default::default()).unwrap());
+        let mut manager = DataIngestionManager::with_sink(config, sink).await.unwrap();
+        manager.websocket_manager.start().await.unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(10), async {
+            while !manager.websocket_manager.is_connected() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("timed out connecting to the mock feed");
+        manager
+    }
+
+    async fn wait_for_connections(server: &mock_server::MockServer, count: usize) {
+        tokio::time::timeout(std::time::Duration::from_secs(10), async {
+            while server.connection_count() < count {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("watchdog did not reconnect");
+    }
+
+    /// Manager connected to a mock feed that never sends market data
+    async fn silent_feed_manager(
+        watchdog: config::WatchdogConfig,
+    ) -> (DataIngestionManager, mock_server::MockServer) {
+        let server = mock_feed(Vec::new(), std::time::Duration::ZERO).await;
+        let mut config = config::DataIngestionConfig::default();
+        config.symbols = vec!["AAPL".to_string()];
+        config.websocket.base_url = Some(server.base_url());
+        config.watchdog = watchdog;
+        (connected_manager(config).await, server)
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_reconnects_idle_feed() {
+        let (mut manager, server) = silent_feed_manager(config::WatchdogConfig {
+            enabled: true,
+            max_idle_seconds: 0,
+            check_interval_seconds: 1,
+            market_hours_only: false,
+        })
+        .await;
+        assert_eq!(server.connection_count(), 1);
+
+        manager.check_feed_idle();
+
+        wait_for_connections(&server, 2).await;
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_reconnects_only_the_silent_endpoint() {
+        let trade = format!(
+            r#"[{{"ev":"T","sym":"AAPL","x":4,"p":190.5,"s":100,"c":[],"t":{}}}]"#,
+            chrono::Utc::now().timestamp_millis()
+        );
+        let busy = mock_feed(vec![trade; 200], std::time::Duration::from_millis(50)).await;
+        let silent = mock_feed(Vec::new(), std::time::Duration::ZERO).await;
+        let mut config = config::DataIngestionConfig::default();
+        config.websocket.endpoints = [("busy", &busy), ("silent", &silent)]
+            .into_iter()
+            .map(|(name, server)| config::EndpointConfig {
+                name: name.to_string(),
+                cluster: polygon::PolygonCluster::Stocks,
+                url: Some(format!("{}/stocks", server.base_url())),
+                channels: Vec::new(),
+                symbols: vec!["AAPL".to_string()],
+            })
+            .collect();
+        config.watchdog = config::WatchdogConfig {
+            max_idle_seconds: 1,
+            market_hours_only: false,
+            ..Default::default()
+        };
+        let mut manager = connected_manager(config).await;
+
+        tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+        manager.check_feed_idle();
+
+        wait_for_connections(&silent, 2).await;
+        assert_eq!(busy.connection_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_watches_crypto_outside_market_hours() {
+        let server = mock_feed(Vec::new(), std::time::Duration::ZERO).await;
+        let mut config = config::DataIngestionConfig::default();
+        config.symbols = vec!["X:BTC-USD".to_string()];
+        config.websocket.base_url = Some(server.base_url());
+        config.websocket.cluster = polygon::PolygonCluster::Crypto;
+        config.watchdog = config::WatchdogConfig { max_idle_seconds: 0, ..Default::default() };
+        let mut manager = connected_manager(config).await;
+
+        // Gated on market hours, but the session does not apply to crypto
+        assert!(manager.config.watchdog.market_hours_only);
+        manager.check_feed_idle();
+
+        wait_for_connections(&server, 2).await;
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_leaves_feed_alone_when_disabled_or_within_limit() {
+        for watchdog in [
+            config::WatchdogConfig { enabled: false, max_idle_seconds: 0, ..Default::default() },
+            config::WatchdogConfig { max_idle_seconds: 3600, market_hours_only: false, ..Default::default() },
+        ] {
+            let (mut manager, server) = silent_feed_manager(watchdog).await;
+
+            manager.check_feed_idle();
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+            assert_eq!(server.connection_count(), 1);
+        }
+    }
+}
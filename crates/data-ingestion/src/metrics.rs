@@ -0,0 +1,238 @@
+//! Metrics emitted by the ingestion pipeline
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Reconnects forced by the stale feed watchdog
+pub const WATCHDOG_RECONNECTS: &str = "data_ingestion_watchdog_reconnects_total";
+
+/// End-to-end latency from exchange timestamp to publish, in seconds
+pub const INGESTION_LATENCY: &str = "data_ingestion_latency_seconds";
+
+/// Messages whose exchange timestamp was ahead of the local clock
+pub const CLOCK_SKEW_EVENTS: &str = "data_ingestion_clock_skew_total";
+
+/// Messages skipped by `stream()` consumers that fell behind
+pub const STREAM_LAGGED_MESSAGES: &str = "data_ingestion_stream_lagged_messages_total";
+
+/// Aggregate sequence gaps detected during a session
+pub const AGGREGATE_GAPS: &str = "data_ingestion_aggregate_gaps_total";
+
+/// Aggregate bars missing across all detected gaps
+pub const AGGREGATE_MISSING_BARS: &str = "data_ingestion_aggregate_missing_bars_total";
+
+/// Trades dropped by the conditions filter
+pub const FILTERED_TRADES: &str = "data_ingestion_filtered_trades_total";
+
+/// Switches between Redis endpoints, including returns to the primary
+pub const REDIS_FAILOVERS: &str = "data_ingestion_redis_failovers_total";
+
+/// Messages dropped before reaching the sink
+pub const PUBLISH_DROPPED: &str = "data_ingestion_publish_dropped_total";
+
+/// Messages waiting in the Redis publish queue
+pub const PUBLISH_QUEUE_DEPTH: &str = "data_ingestion_publish_queue_depth";
+
+/// Estimated offset of the local clock from exchange time, in milliseconds
+pub const CLOCK_OFFSET_MS: &str = "data_ingestion_clock_offset_ms";
+
+/// Samples retained per message type for percentile reporting
+const LATENCY_WINDOW: usize = 10_000;
+
+/// Record a reconnect forced by the stale feed watchdog
+pub fn record_watchdog_reconnect() {
+    ::metrics::counter!(WATCHDOG_RECONNECTS).increment(1);
+}
+
+/// Record messages a lagging stream consumer skipped
+pub fn record_stream_lag(skipped: u64) {
+    ::metrics::counter!(STREAM_LAGGED_MESSAGES).increment(skipped);
+}
+
+/// Record a gap in a symbol's aggregate sequence
+pub fn record_aggregate_gap(missing_bars: u64) {
+    ::metrics::counter!(AGGREGATE_GAPS).increment(1);
+    ::metrics::counter!(AGGREGATE_MISSING_BARS).increment(missing_bars);
+}
+
+/// Record a trade dropped by the conditions filter
+pub fn record_filtered_trade() {
+    ::metrics::counter!(FILTERED_TRADES).increment(1);
+}
+
+/// Record a switch to another Redis endpoint
+pub fn record_redis_failover() {
+    ::metrics::counter!(REDIS_FAILOVERS).increment(1);
+}
+
+/// Record a message dropped before publishing
+pub fn record_publish_dropped() {
+    ::metrics::counter!(PUBLISH_DROPPED).increment(1);
+}
+
+/// Record the current Redis publish queue depth
+pub fn record_publish_queue_depth(depth: usize) {
+    ::metrics::gauge!(PUBLISH_QUEUE_DEPTH).set(depth as f64);
+}
+
+/// Latency percentiles for one message type, in microseconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub p50_us: u64,
+    pub p99_us: u64,
+    pub max_us: u64,
+}
+
+/// Rolling per-message-type latency samples
+#[derive(Default)]
+pub struct LatencyTracker {
+    samples: Mutex<HashMap<&'static str, VecDeque<u64>>>,
+}
+
+impl LatencyTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the latency between an exchange timestamp and now.
+    ///
+    /// Timestamps ahead of the local clock are counted as skew and
+    /// recorded as zero latency.
+    pub fn record(
+        &self,
+        message_type: &'static str,
+        exchange_time: chrono::DateTime<chrono::Utc>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        let latency_us = match now.signed_duration_since(exchange_time).num_microseconds() {
+            Some(us) if us >= 0 => us as u64,
+            Some(_) => {
+                ::metrics::counter!(CLOCK_SKEW_EVENTS).increment(1);
+                0
+            }
+            None => u64::MAX,
+        };
+
+        ::metrics::histogram!(INGESTION_LATENCY, "type" => message_type)
+            .record(latency_us as f64 / 1_000_000.0);
+
+        let mut samples = self.samples.lock();
+        let window = samples.entry(message_type).or_default();
+        if window.len() >= LATENCY_WINDOW {
+            window.pop_front();
+        }
+        window.push_back(latency_us);
+    }
+
+    /// Percentiles over the retained samples, keyed by message type
+    pub fn stats(&self) -> HashMap<String, LatencyStats> {
+        self.samples
+            .lock()
+            .iter()
+            .filter(|(_, window)| !window.is_empty())
+            .map(|(message_type, window)| {
+                let mut sorted: Vec<u64> = window.iter().copied().collect();
+                sorted.sort_unstable();
+                let percentile = |p: f64| sorted[((sorted.len() - 1) as f64 * p).round() as usize];
+
+                let stats = LatencyStats {
+                    samples: sorted.len(),
+                    p50_us: percentile(0.50),
+                    p99_us: percentile(0.99),
+                    max_us: sorted[sorted.len() - 1],
+                };
+                (message_type.to_string(), stats)
+            })
+            .collect()
+    }
+}
+
+/// Estimates local clock offset from exchange timestamps.
+///
+/// Each sample is `received - exchange_time` = network latency + clock
+/// offset. Latency is never negative and its floor is small, so a low
+/// percentile of the samples approximates the offset: a negative estimate
+/// means the local clock is behind the exchange.
+pub struct ClockDriftEstimator {
+    samples_ms: Mutex<VecDeque<i64>>,
+    window: usize,
+}
+
+impl ClockDriftEstimator {
+    /// Keep the most recent `window` samples
+    pub fn new(window: usize) -> Self {
+        Self {
+            samples_ms: Mutex::new(VecDeque::with_capacity(window)),
+            window: window.max(1),
+        }
+    }
+
+    /// Record a message's exchange timestamp against its local receive time
+    pub fn record(&self, exchange_time: chrono::DateTime<chrono::Utc>, received: chrono::DateTime<chrono::Utc>) {
+        let mut samples = self.samples_ms.lock();
+        if samples.len() >= self.window {
+            samples.pop_front();
+        }
+        samples.push_back(received.signed_duration_since(exchange_time).num_milliseconds());
+    }
+
+    /// Estimated clock offset in milliseconds (1st percentile of samples),
+    /// also published as a gauge
+    pub fn estimate_ms(&self) -> Option<i64> {
+        let mut sorted: Vec<i64> = self.samples_ms.lock().iter().copied().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_unstable();
+        let offset = sorted[(sorted.len() - 1) / 100];
+        ::metrics::gauge!(CLOCK_OFFSET_MS).set(offset as f64);
+        Some(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_percentiles_per_message_type() {
+        let tracker = LatencyTracker::new();
+        let now = Utc::now();
+        for ms in 1..=100 {
+            tracker.record("trade", now - Duration::milliseconds(ms), now);
+        }
+        tracker.record("quote", now - Duration::milliseconds(5), now);
+
+        let stats = tracker.stats();
+        assert_eq!(stats["trade"].samples, 100);
+        assert_eq!(stats["trade"].p50_us, 51_000);
+        assert_eq!(stats["trade"].p99_us, 99_000);
+        assert_eq!(stats["quote"].max_us, 5_000);
+    }
+
+    #[test]
+    fn test_future_timestamps_clamp_to_zero() {
+        let tracker = LatencyTracker::new();
+        let now = Utc::now();
+        tracker.record("trade", now + Duration::seconds(2), now);
+
+        assert_eq!(tracker.stats()["trade"].max_us, 0);
+    }
+
+    #[test]
+    fn test_clock_drift_uses_latency_floor() {
+        let estimator = ClockDriftEstimator::new(100);
+        let now = Utc::now();
+        // Local clock 200ms behind with 5..55ms of network latency
+        for latency in 0..50 {
+            let received = now + Duration::milliseconds(5 + latency);
+            estimator.record(now + Duration::milliseconds(200), received);
+        }
+
+        assert_eq!(estimator.estimate_ms(), Some(-195));
+    }
+}
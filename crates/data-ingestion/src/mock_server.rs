@@ -0,0 +1,157 @@
+//! Local Polygon-compatible WebSocket server replaying canned frames.
+//!
+//! Accepts the auth/subscribe handshake with any key and acknowledges each
+//! subscription, so the full ingestion path can run without a network or
+//! entitlement.
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tracing::{debug, warn};
+
+/// Replay behaviour of the mock server
+#[derive(Debug, Clone)]
+pub struct MockServerConfig {
+    /// Frames sent in order after the first subscription
+    pub frames: Vec<String>,
+
+    /// Pause between frames
+    pub interval: Duration,
+
+    /// Close each connection once its frames have been sent
+    pub close_after_replay: bool,
+}
+
+/// Running mock server; stops when dropped
+pub struct MockServer {
+    addr: SocketAddr,
+    connections: Arc<AtomicUsize>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Bind to an ephemeral local port and start accepting connections
+    pub async fn start(config: MockServerConfig) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let connections = Arc::new(AtomicUsize::new(0));
+        let config = Arc::new(config);
+
+        let accepted = connections.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, peer)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::Relaxed);
+                let config = config.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = Self::serve(stream, &config).await {
+                        debug!("Mock connection from {} ended: {:?}", peer, e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self { addr, connections, task })
+    }
+
+    /// Start a server replaying a file with one JSON frame per line
+    pub async fn from_file(path: impl AsRef<Path>, interval: Duration) -> Result<Self> {
+        let path = path.as_ref();
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Failed to read mock frames from {}", path.display()))?;
+        let frames = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        Self::start(MockServerConfig {
+            frames,
+            interval,
+            close_after_replay: false,
+        })
+        .await
+    }
+
+    /// Base URL to use in place of `wss://socket.polygon.io`; any cluster
+    /// path is accepted
+    pub fn base_url(&self) -> String {
+        format!("ws://{}", self.addr)
+    }
+
+    /// Connections accepted so far
+    pub fn connection_count(&self) -> usize {
+        self.connections.load(Ordering::Relaxed)
+    }
+
+    async fn serve(stream: TcpStream, config: &MockServerConfig) -> Result<()> {
+        let mut ws = tokio_tungstenite::accept_async(stream).await?;
+        ws.send(status("connected", "Connected Successfully")).await?;
+
+        let mut replayed = false;
+        while let Some(msg) = ws.next().await {
+            let Message::Text(text) = msg? else {
+                continue;
+            };
+            let control: serde_json::Value = match serde_json::from_str(&text) {
+                Ok(control) => control,
+                Err(e) => {
+                    warn!("Mock server ignoring malformed control message: {}", e);
+                    continue;
+                }
+            };
+            let params = control["params"].as_str().unwrap_or_default();
+
+            match control["action"].as_str() {
+                Some("auth") => ws.send(status("auth_success", "authenticated")).await?,
+                Some("subscribe") => {
+                    for channel in params.split(',').filter(|channel| !channel.is_empty()) {
+                        ws.send(status("success", &format!("subscribed to: {}", channel))).await?;
+                    }
+                    if replayed {
+                        continue;
+                    }
+                    replayed = true;
+
+                    for frame in &config.frames {
+                        if !config.interval.is_zero() {
+                            tokio::time::sleep(config.interval).await;
+                        }
+                        ws.send(Message::Text(frame.clone())).await?;
+                    }
+                    if config.close_after_replay {
+                        ws.close(None).await?;
+                        return Ok(());
+                    }
+                }
+                Some("unsubscribe") => {
+                    for channel in params.split(',').filter(|channel| !channel.is_empty()) {
+                        ws.send(status("success", &format!("unsubscribed to: {}", channel))).await?;
+                    }
+                }
+                _ => ws.send(status("error", "unknown action")).await?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn status(status: &str, message: &str) -> Message {
+    let frame = serde_json::json!([{ "ev": "status", "status": status, "message": message }]);
+    Message::Text(frame.to_string())
+}
@@ -1,9 +1,57 @@
 //! Polygon.io specific implementations
 
 use anyhow::Result;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Polygon WebSocket cluster; each has its own endpoint and message schemas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolygonCluster {
+    #[default]
+    Stocks,
+    Crypto,
+    Forex,
+    Options,
+    Indices,
+}
+
+impl PolygonCluster {
+    /// Path segment of the cluster endpoint
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolygonCluster::Stocks => "stocks",
+            PolygonCluster::Crypto => "crypto",
+            PolygonCluster::Forex => "forex",
+            PolygonCluster::Options => "options",
+            PolygonCluster::Indices => "indices",
+        }
+    }
+
+    /// Endpoint URL under a base such as `wss://socket.polygon.io`
+    pub fn url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url.trim_end_matches('/'), self.as_str())
+    }
+
+    /// Whether the cluster only trades in the US equity regular session;
+    /// crypto and forex trade around the clock
+    pub fn follows_equity_hours(&self) -> bool {
+        matches!(self, PolygonCluster::Stocks | PolygonCluster::Options | PolygonCluster::Indices)
+    }
+
+    /// Default subscription channel prefixes: trades, or quotes/values where
+    /// the cluster has no trade feed
+    pub fn default_channels(&self) -> &'static [&'static str] {
+        match self {
+            PolygonCluster::Stocks | PolygonCluster::Options => &["T"],
+            PolygonCluster::Crypto => &["XT"],
+            PolygonCluster::Forex => &["C"],
+            PolygonCluster::Indices => &["V"],
+        }
+    }
+}
+
 /// Polygon.io WebSocket message types
 #[derive(Debug, Deserialize)]
 #[serde(tag = "ev")]
@@ -16,6 +64,9 @@ pub enum PolygonMessage {
     Aggregate(PolygonAggregate),
     #[serde(rename = "status")]
     Status(PolygonStatus),
+    /// Event types we don't consume (e.g. `AM`, `LULD`)
+    #[serde(other)]
+    Unknown,
 }
 
 /// Polygon.io trade message
@@ -25,8 +76,9 @@ pub struct PolygonTrade {
     pub x: i32,          // Exchange ID
     pub p: f64,          // Price
     pub s: u64,          // Size
+    #[serde(default)]
     pub c: Vec<i32>,     // Conditions
-    pub t: u64,          // Timestamp (nanoseconds)
+    pub t: u64,          // Timestamp (Unix ms)
 }
 
 /// Polygon.io quote message
@@ -38,8 +90,9 @@ pub struct PolygonQuote {
     pub bp: f64,         // Bid price
     pub ap: f64,         // Ask price
     pub bs: u64,         // Bid size
+    #[serde(rename = "as")]
     pub as_: u64,        // Ask size
-    pub t: u64,          // Timestamp (nanoseconds)
+    pub t: u64,          // Timestamp (Unix ms)
 }
 
 /// Polygon.io aggregate message
@@ -53,6 +106,10 @@ pub struct PolygonAggregate {
     pub v: u64,          // Volume
     pub s: u64,          // Start timestamp
     pub e: u64,          // End timestamp
+    #[serde(default)]
+    pub vw: Option<f64>, // Volume-weighted average price
+    #[serde(default)]
+    pub n: Option<u64>,  // Number of transactions
 }
 
 /// Polygon.io status message
@@ -62,6 +119,60 @@ pub struct PolygonStatus {
     pub message: String,
 }
 
+impl PolygonMessage {
+    /// Convert to market data; status and unknown events yield `None`
+    pub fn into_market_data(self) -> Option<crate::MarketData> {
+        match self {
+            PolygonMessage::Trade(trade) => Some(crate::MarketData::Trade(trade.into())),
+            PolygonMessage::Quote(quote) => Some(crate::MarketData::Quote(quote.into())),
+            PolygonMessage::Aggregate(agg) => Some(crate::MarketData::Aggregate(agg.into())),
+            PolygonMessage::Status(_) | PolygonMessage::Unknown => None,
+        }
+    }
+}
+
+/// Whether a frame's first non-whitespace byte opens an array of events
+fn is_event_array(bytes: &[u8]) -> bool {
+    bytes.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'[')
+}
+
+/// Deserialize a frame's events in order. Frames are normally an array, but
+/// auth and status replies may arrive as a single object; the opening byte
+/// picks the shape so the frame is decoded once without buffering.
+pub fn parse_events<T: DeserializeOwned>(text: &str) -> serde_json::Result<Vec<T>> {
+    if is_event_array(text.as_bytes()) {
+        serde_json::from_str(text)
+    } else {
+        serde_json::from_str(text).map(|event| vec![event])
+    }
+}
+
+/// Parse a frame of events straight into typed messages
+pub fn parse_frame(text: &str) -> Result<Vec<PolygonMessage>> {
+    Ok(parse_events(text)?)
+}
+
+/// Parse a frame with simd-json; the buffer is modified in place
+#[cfg(feature = "simd")]
+pub fn parse_frame_simd(buf: &mut [u8]) -> Result<Vec<PolygonMessage>> {
+    if is_event_array(buf) {
+        Ok(simd_json::serde::from_slice(buf)?)
+    } else {
+        Ok(vec![simd_json::serde::from_slice(buf)?])
+    }
+}
+
+/// Bar length label (`5s`, `1m`, `1h`) for a span in milliseconds, in the
+/// largest unit that divides it exactly; an empty span falls back to `1m`
+pub fn timespan_label(span_ms: u64) -> String {
+    match span_ms {
+        0 => "1m".to_string(),
+        ms if ms % 3_600_000 == 0 => format!("{}h", ms / 3_600_000),
+        ms if ms % 60_000 == 0 => format!("{}m", ms / 60_000),
+        ms => format!("{}s", ms.div_ceil(1000)),
+    }
+}
+
 /// Exchange ID to name mapping
 pub fn exchange_id_to_name(id: i32) -> &'static str {
     match id {
@@ -81,9 +192,16 @@ pub fn exchange_id_to_name(id: i32) -> &'static str {
     }
 }
 
-/// Convert Polygon timestamp to chrono DateTime
-pub fn polygon_timestamp_to_datetime(timestamp_ns: u64) -> chrono::DateTime<chrono::Utc> {
-    let timestamp_ms = timestamp_ns / 1_000_000;
+/// Convert Polygon timestamp to chrono DateTime.
+///
+/// WebSocket feeds send Unix milliseconds while REST responses use
+/// nanoseconds; values beyond the millisecond range are treated as nanoseconds.
+pub fn polygon_timestamp_to_datetime(timestamp: u64) -> chrono::DateTime<chrono::Utc> {
+    let timestamp_ms = if timestamp >= 1_000_000_000_000_000 {
+        timestamp / 1_000_000
+    } else {
+        timestamp
+    };
     chrono::DateTime::from_timestamp_millis(timestamp_ms as i64)
         .unwrap_or_else(chrono::Utc::now)
 }
@@ -93,9 +211,11 @@ impl From<PolygonTrade> for crate::TradeData {
     fn from(trade: PolygonTrade) -> Self {
         Self {
             symbol: trade.sym,
-            price: trade.p,
+            original_symbol: None,
+            price: crate::price::from_f64(trade.p),
             size: trade.s,
             timestamp: polygon_timestamp_to_datetime(trade.t),
+            secondary_timestamp: None,
             exchange: exchange_id_to_name(trade.x).to_string(),
             conditions: trade.c.into_iter().map(|c| c.to_string()).collect(),
         }
@@ -107,16 +227,20 @@ impl From<PolygonQuote> for crate::QuoteData {
     fn from(quote: PolygonQuote) -> Self {
         Self {
             symbol: quote.sym,
-            bid_price: quote.bp,
-            ask_price: quote.ap,
+            original_symbol: None,
+            bid_price: crate::price::from_f64(quote.bp),
+            ask_price: crate::price::from_f64(quote.ap),
             bid_size: quote.bs,
             ask_size: quote.as_,
             timestamp: polygon_timestamp_to_datetime(quote.t),
+            secondary_timestamp: None,
             exchange: format!(
                 "{}|{}",
                 exchange_id_to_name(quote.bx),
                 exchange_id_to_name(quote.ax)
             ),
+            bid_exchange: exchange_id_to_name(quote.bx).to_string(),
+            ask_exchange: exchange_id_to_name(quote.ax).to_string(),
         }
     }
 }
@@ -126,13 +250,92 @@ impl From<PolygonAggregate> for crate::AggregateData {
     fn from(agg: PolygonAggregate) -> Self {
         Self {
             symbol: agg.sym,
-            open: agg.o,
-            high: agg.h,
-            low: agg.l,
-            close: agg.c,
+            original_symbol: None,
+            open: crate::price::from_f64(agg.o),
+            high: crate::price::from_f64(agg.h),
+            low: crate::price::from_f64(agg.l),
+            close: crate::price::from_f64(agg.c),
             volume: agg.v,
             timestamp: polygon_timestamp_to_datetime(agg.s),
-            timespan: "1m".to_string(), // Default to 1 minute
+            secondary_timestamp: None,
+            timespan: timespan_label(agg.e.saturating_sub(agg.s)),
+            vwap: agg.vw.map(crate::price::from_f64),
+            transactions: agg.n,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_timespan_comes_from_bar_bounds() {
+        let frame = r#"[{"ev":"A","sym":"SPY","o":1,"h":1,"l":1,"c":1,"v":10,"s":1700000000000,"e":1700000005000}]"#;
+        let Some(crate::MarketData::Aggregate(agg)) = parse_frame(frame).unwrap().pop().unwrap().into_market_data() else {
+            panic!("expected an aggregate");
+        };
+        assert_eq!(agg.timespan, "5s");
+
+        assert_eq!(timespan_label(60_000), "1m");
+        assert_eq!(timespan_label(300_000), "5m");
+        assert_eq!(timespan_label(3_600_000), "1h");
+        assert_eq!(timespan_label(1_500), "2s");
+    }
+
+    #[test]
+    fn test_single_object_and_array_frames_parse() {
+        let status = r#"  {"ev":"status","status":"auth_success","message":"authenticated"}"#;
+        let Some(PolygonMessage::Status(status)) = parse_frame(status).unwrap().pop() else {
+            panic!("expected a status");
+        };
+        assert_eq!(status.status, "auth_success");
+
+        let frame = r#"
+            [{"ev":"T","sym":"AAPL","x":4,"p":150.0,"s":100,"t":1700000000000},
+             {"ev":"status","status":"success","message":"subscribed to: T.AAPL"}]"#;
+        let messages = parse_frame(frame).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(messages[0], PolygonMessage::Trade(_)));
+        assert!(matches!(messages[1], PolygonMessage::Status(_)));
+
+        assert!(parse_frame("").is_err());
+    }
+
+    #[test]
+    fn test_real_quote_frame_parses_every_field() {
+        let frame = r#"[{"ev":"Q","sym":"MSFT","bx":4,"bp":114.125,"bs":100,"ax":7,"ap":114.128,"as":160,"c":0,"i":[604],"t":1536036818784,"q":50385480,"z":3}]"#;
+        let Some(PolygonMessage::Quote(quote)) = parse_frame(frame).unwrap().pop() else {
+            panic!("expected a quote");
+        };
+
+        assert_eq!(quote.sym, "MSFT");
+        assert_eq!((quote.bx, quote.ax), (4, 7));
+        assert_eq!((quote.bp, quote.ap), (114.125, 114.128));
+        assert_eq!((quote.bs, quote.as_), (100, 160));
+        assert_eq!(quote.t, 1536036818784);
+    }
+
+    #[test]
+    fn test_quote_keeps_bid_and_ask_venues() {
+        let frame = r#"[{"ev":"Q","sym":"MSFT","bx":4,"bp":114.125,"bs":100,"ax":7,"ap":114.128,"as":160,"t":1536036818784}]"#;
+        let Some(crate::MarketData::Quote(quote)) = parse_frame(frame).unwrap().pop().unwrap().into_market_data() else {
+            panic!("expected a quote");
+        };
+
+        assert_eq!(quote.bid_exchange, "NASDAQ_OMX_BX");
+        assert_eq!(quote.ask_exchange, "CBOE_EDGA");
+        assert_eq!(quote.exchange, "NASDAQ_OMX_BX|CBOE_EDGA");
+    }
+
+    #[test]
+    fn test_aggregate_keeps_vwap_and_transactions() {
+        let frame = r#"[{"ev":"A","sym":"SPY","o":1,"h":2,"l":1,"c":2,"v":10,"vw":1.4567,"n":3,"s":0,"e":1000}]"#;
+        let Some(crate::MarketData::Aggregate(agg)) = parse_frame(frame).unwrap().pop().unwrap().into_market_data() else {
+            panic!("expected an aggregate");
+        };
+
+        assert_eq!(agg.vwap, Some(crate::price::from_f64(1.4567)));
+        assert_eq!(agg.transactions, Some(3));
+    }
+}
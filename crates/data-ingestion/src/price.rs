@@ -0,0 +1,52 @@
+//! Price representation
+//!
+//! Prices are `f64` by default. With the `decimal` feature they are
+//! `rust_decimal::Decimal`, converted once at parse time so downstream
+//! arithmetic and equality checks are exact.
+
+#[cfg(feature = "decimal")]
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Price type used in market data structs
+#[cfg(not(feature = "decimal"))]
+pub type Price = f64;
+
+/// Price type used in market data structs
+#[cfg(feature = "decimal")]
+pub type Price = rust_decimal::Decimal;
+
+/// Convert a wire `f64` into a `Price`
+#[cfg(not(feature = "decimal"))]
+pub fn from_f64(value: f64) -> Price {
+    value
+}
+
+/// Convert a wire `f64` into a `Price`, yielding zero for non-finite input
+#[cfg(feature = "decimal")]
+pub fn from_f64(value: f64) -> Price {
+    Price::from_f64(value).unwrap_or_default()
+}
+
+/// Convert a `Price` into an `f64`
+#[cfg(not(feature = "decimal"))]
+pub fn to_f64(price: Price) -> f64 {
+    price
+}
+
+/// Convert a `Price` into an `f64`
+#[cfg(feature = "decimal")]
+pub fn to_f64(price: Price) -> f64 {
+    price.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Convert a share or contract count into a `Price` for notional math
+#[cfg(not(feature = "decimal"))]
+pub fn from_units(units: u64) -> Price {
+    units as f64
+}
+
+/// Convert a share or contract count into a `Price` for notional math
+#[cfg(feature = "decimal")]
+pub fn from_units(units: u64) -> Price {
+    Price::from(units)
+}
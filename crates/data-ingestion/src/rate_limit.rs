@@ -0,0 +1,81 @@
+//! Token-bucket rate limiting for outbound control messages
+
+use std::time::{Duration, Instant};
+
+/// Token bucket allowing bursts up to `capacity` and refilling at a fixed rate
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket; `refill_per_sec` must be positive or the
+    /// bucket never refills
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        Self {
+            capacity: capacity.max(1) as f64,
+            tokens: capacity.max(1) as f64,
+            refill_per_sec,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Take a token if one is available at `now`, otherwise report how long
+    /// until the next one
+    pub fn try_acquire_at(&mut self, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+
+        if self.refill_per_sec <= 0.0 {
+            return Err(Duration::MAX);
+        }
+        Err(Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_sec))
+    }
+
+    /// Wait until a token is available and take it
+    pub async fn acquire(&mut self) {
+        while let Err(wait) = self.try_acquire_at(Instant::now()) {
+            tokio::time::sleep(wait.min(Duration::from_secs(1))).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_refill() {
+        let mut bucket = TokenBucket::new(2, 4.0);
+        let start = bucket.last_refill;
+
+        assert!(bucket.try_acquire_at(start).is_ok());
+        assert!(bucket.try_acquire_at(start).is_ok());
+        let wait = bucket.try_acquire_at(start).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(250));
+
+        assert!(bucket.try_acquire_at(start + Duration::from_millis(250)).is_ok());
+        assert!(bucket.try_acquire_at(start + Duration::from_millis(250)).is_err());
+    }
+
+    #[test]
+    fn test_refill_capped_at_capacity() {
+        let mut bucket = TokenBucket::new(1, 10.0);
+        let later = bucket.last_refill + Duration::from_secs(60);
+
+        assert!(bucket.try_acquire_at(later).is_ok());
+        assert!(bucket.try_acquire_at(later).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Per-symbol sampling of the local broadcast path
+
+use crate::config::SamplingConfig;
+use crate::MarketData;
+use dashmap::DashMap;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Last emission and held-back message for one symbol and message type
+struct SampleState {
+    last_emit: Option<Instant>,
+    pending: Option<MarketData>,
+}
+
+impl SampleState {
+    fn is_due(&self, now: Instant, interval: Duration) -> bool {
+        match self.last_emit {
+            Some(last_emit) => now.saturating_duration_since(last_emit) >= interval,
+            None => true,
+        }
+    }
+}
+
+/// Limits each symbol to one message per interval and message type.
+///
+/// Messages arriving within the interval replace the held one, so the
+/// latest is emitted once the interval has passed.
+#[derive(Default)]
+pub struct Sampler {
+    intervals: HashMap<String, Duration>,
+    default_interval: Option<Duration>,
+    state: DashMap<(String, &'static str), SampleState>,
+}
+
+impl Sampler {
+    /// Build from configuration
+    pub fn new(config: &SamplingConfig) -> Self {
+        Self {
+            intervals: config
+                .intervals_ms
+                .iter()
+                .map(|(symbol, ms)| (symbol.clone(), Duration::from_millis(*ms)))
+                .collect(),
+            default_interval: config.default_interval_ms.map(Duration::from_millis),
+            state: DashMap::new(),
+        }
+    }
+
+    /// Sampling interval for a symbol, or `None` if every message is emitted
+    pub fn interval(&self, symbol: &str) -> Option<Duration> {
+        self.intervals
+            .get(symbol)
+            .copied()
+            .or(self.default_interval)
+            .filter(|interval| !interval.is_zero())
+    }
+
+    /// Shortest configured interval, used to schedule `flush_due`
+    pub fn tick_interval(&self) -> Option<Duration> {
+        self.intervals
+            .values()
+            .copied()
+            .chain(self.default_interval)
+            .filter(|interval| !interval.is_zero())
+            .min()
+    }
+
+    /// Pass a message through, returning it if it should be emitted now
+    pub fn offer(&self, data: MarketData, now: Instant) -> Option<MarketData> {
+        let Some(interval) = self.interval(data.symbol()) else {
+            return Some(data);
+        };
+
+        let key = (data.symbol().to_string(), data.message_type());
+        let mut state = self.state.entry(key).or_insert_with(|| SampleState {
+            last_emit: None,
+            pending: None,
+        });
+        if state.is_due(now, interval) {
+            state.last_emit = Some(now);
+            state.pending = None;
+            Some(data)
+        } else {
+            state.pending = Some(data);
+            None
+        }
+    }
+
+    /// Held messages whose interval has passed
+    pub fn flush_due(&self, now: Instant) -> Vec<MarketData> {
+        let mut due = Vec::new();
+        for mut entry in self.state.iter_mut() {
+            let interval = self.interval(&entry.key().0).unwrap_or_default();
+            let state = entry.value_mut();
+            if state.pending.is_some() && state.is_due(now, interval) {
+                state.last_emit = Some(now);
+                due.extend(state.pending.take());
+            }
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(symbol: &str, size: u64) -> MarketData {
+        MarketData::Trade(crate::TradeData {
+            symbol: symbol.to_string(),
+            original_symbol: None,
+            price: crate::price::from_f64(100.0),
+            size,
+            timestamp: chrono::Utc::now(),
+            secondary_timestamp: None,
+            exchange: "4".to_string(),
+            conditions: vec![],
+        })
+    }
+
+    fn size(data: &MarketData) -> u64 {
+        let MarketData::Trade(trade) = data else { unreachable!() };
+        trade.size
+    }
+
+    #[test]
+    fn test_sampled_symbol_emits_latest_once_per_interval() {
+        let sampler = Sampler::new(&SamplingConfig {
+            intervals_ms: HashMap::from([("SPY".to_string(), 100)]),
+            default_interval_ms: None,
+        });
+        let start = Instant::now();
+
+        assert!(sampler.offer(trade("SPY", 1), start).is_some());
+        assert!(sampler.offer(trade("SPY", 2), start + Duration::from_millis(10)).is_none());
+        assert!(sampler.offer(trade("SPY", 3), start + Duration::from_millis(20)).is_none());
+        assert!(sampler.flush_due(start + Duration::from_millis(50)).is_empty());
+
+        let due = sampler.flush_due(start + Duration::from_millis(100));
+        assert_eq!(due.iter().map(size).collect::<Vec<_>>(), vec![3]);
+
+        // Unsampled symbols pass straight through
+        assert!(sampler.offer(trade("AAPL", 1), start).is_some());
+        assert!(sampler.offer(trade("AAPL", 2), start).is_some());
+    }
+}
@@ -0,0 +1,643 @@
+//! Publishing targets for validated market data
+
+use crate::config::{DataIngestionConfig, PublishConfig, RedisFailoverConfig, SinkKind};
+use crate::{metrics, MarketData};
+use anyhow::Result;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use redis::aio::MultiplexedConnection;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaSink;
+
+#[cfg(feature = "kafka")]
+thread_local! {
+    /// Scratch buffer for payloads, reused across messages
+    static PAYLOAD_BUFFER: std::cell::RefCell<Vec<u8>> = std::cell::RefCell::new(Vec::with_capacity(512));
+}
+
+/// Destination for market data leaving the ingestion pipeline
+#[async_trait]
+pub trait Sink: Send + Sync {
+    /// Short name for logs and health reports
+    fn name(&self) -> &'static str;
+
+    /// Publish a single message
+    async fn publish(&self, data: &MarketData) -> Result<()>;
+
+    /// Check that the backend is reachable
+    async fn ping(&self) -> Result<()>;
+}
+
+/// Build the sink selected in the configuration
+pub fn from_config(config: &DataIngestionConfig) -> Result<Box<dyn Sink>> {
+    match config.publish.sink {
+        SinkKind::Redis => Ok(Box::new(RedisSink::with_failover(
+            &config.redis_urls(),
+            config.publish.clone(),
+            config.redis_failover.clone(),
+        )?)),
+        #[cfg(feature = "kafka")]
+        SinkKind::Kafka => Ok(Box::new(KafkaSink::new(config.publish.clone())?)),
+        #[cfg(not(feature = "kafka"))]
+        SinkKind::Kafka => Err(anyhow::anyhow!("Kafka sink requires the `kafka` feature")),
+    }
+}
+
+/// Per-type name suffix shared by Redis channels and Kafka topics
+fn type_name(data: &MarketData) -> &'static str {
+    match data {
+        MarketData::Trade(_) => TYPE_NAMES[0],
+        MarketData::Quote(_) => TYPE_NAMES[1],
+        MarketData::Aggregate(_) => TYPE_NAMES[2],
+    }
+}
+
+/// Every name `type_name` returns
+const TYPE_NAMES: [&str; 3] = ["trades", "quotes", "aggregates"];
+
+/// Redis pub/sub and Streams sink.
+///
+/// Messages are handed to a background publisher through a bounded queue so
+/// a slow or failing Redis never blocks ingestion. While no endpoint accepts
+/// them, messages stay queued and the oldest are dropped once it is full.
+/// The publisher fails over through the configured URLs in order and
+/// periodically tries to return to the primary.
+pub struct RedisSink {
+    queue: Arc<PublishQueue>,
+    endpoints: Arc<RedisEndpoints>,
+    worker: JoinHandle<()>,
+}
+
+impl RedisSink {
+    /// Create a sink for the given Redis URL
+    pub fn new(redis_url: &str, publish: PublishConfig) -> Result<Self> {
+        Self::with_failover(&[redis_url.to_string()], publish, RedisFailoverConfig::default())
+    }
+
+    /// Create a sink publishing to the first reachable of `redis_urls`
+    pub fn with_failover(
+        redis_urls: &[String],
+        publish: PublishConfig,
+        failover: RedisFailoverConfig,
+    ) -> Result<Self> {
+        if redis_urls.is_empty() {
+            anyhow::bail!("At least one Redis URL is required");
+        }
+        let clients = redis_urls
+            .iter()
+            .map(|url| redis::Client::open(url.as_str()))
+            .collect::<redis::RedisResult<_>>()?;
+        let endpoints = Arc::new(RedisEndpoints::new(clients, publish));
+
+        let queue = Arc::new(PublishQueue::new(failover.queue_capacity));
+        let worker = tokio::spawn(run_redis_publisher(endpoints.clone(), queue.clone(), failover));
+
+        Ok(Self { queue, endpoints, worker })
+    }
+
+    /// Index of the endpoint currently published to; 0 is the primary
+    pub fn active_endpoint(&self) -> usize {
+        self.endpoints.active.load(Ordering::Relaxed)
+    }
+
+    /// Messages waiting to be published
+    pub fn queue_depth(&self) -> usize {
+        self.queue.messages.lock().len()
+    }
+}
+
+impl Drop for RedisSink {
+    fn drop(&mut self) {
+        self.worker.abort();
+    }
+}
+
+#[async_trait]
+impl Sink for RedisSink {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    /// Queue a message for the background publisher
+    async fn publish(&self, data: &MarketData) -> Result<()> {
+        if self.worker.is_finished() {
+            anyhow::bail!("Redis publisher stopped");
+        }
+        self.queue.push(data.clone());
+        Ok(())
+    }
+
+    /// Check that the active Redis endpoint responds to PING
+    async fn ping(&self) -> Result<()> {
+        self.endpoints.ping(self.active_endpoint()).await
+    }
+}
+
+/// Bounded FIFO of messages awaiting publication, dropping the oldest
+struct PublishQueue {
+    messages: Mutex<VecDeque<MarketData>>,
+    capacity: usize,
+    notify: Notify,
+}
+
+impl PublishQueue {
+    fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            messages: Mutex::new(VecDeque::with_capacity(capacity.min(1024))),
+            capacity,
+            notify: Notify::new(),
+        }
+    }
+
+    /// Append a message, evicting the oldest when full
+    fn push(&self, data: MarketData) {
+        let mut messages = self.messages.lock();
+        if messages.len() == self.capacity {
+            if let Some(dropped) = messages.pop_front() {
+                debug!("Redis publish queue is full, dropping oldest {} message", dropped.symbol());
+                metrics::record_publish_dropped();
+            }
+        }
+        messages.push_back(data);
+        metrics::record_publish_queue_depth(messages.len());
+        drop(messages);
+        self.notify.notify_one();
+    }
+
+    /// Take up to `max` of the oldest messages
+    fn pop_batch(&self, max: usize) -> Vec<MarketData> {
+        let mut messages = self.messages.lock();
+        let batch: Vec<MarketData> = messages.drain(..max.min(messages.len())).collect();
+        metrics::record_publish_queue_depth(messages.len());
+        batch
+    }
+
+    /// Return a failed batch to the front of the queue in order, dropping
+    /// its oldest messages when newer ones have taken the space
+    fn requeue_batch(&self, batch: Vec<MarketData>) {
+        let mut messages = self.messages.lock();
+        let room = self.capacity.saturating_sub(messages.len());
+        let dropped = batch.len().saturating_sub(room);
+        for data in batch.into_iter().skip(dropped).rev() {
+            messages.push_front(data);
+        }
+        for _ in 0..dropped {
+            metrics::record_publish_dropped();
+        }
+        metrics::record_publish_queue_depth(messages.len());
+    }
+}
+
+/// Most queued messages sent to Redis in one pipeline
+const PUBLISH_BATCH: usize = 256;
+
+/// Pipeline and payload buffer the publisher reuses across batches
+struct PublishBuffers {
+    pipeline: redis::Pipeline,
+    payload: Vec<u8>,
+}
+
+impl PublishBuffers {
+    fn new() -> Self {
+        Self {
+            pipeline: redis::pipe(),
+            payload: Vec::with_capacity(512),
+        }
+    }
+}
+
+/// Redis clients in failover order, shared with the publisher task
+struct RedisEndpoints {
+    clients: Vec<redis::Client>,
+    /// Open connection per client, reused until it fails
+    connections: Vec<tokio::sync::Mutex<Option<MultiplexedConnection>>>,
+    active: AtomicUsize,
+    publish: PublishConfig,
+    /// Pub/sub channel and stream key per type name
+    keys: HashMap<&'static str, (String, String)>,
+}
+
+impl RedisEndpoints {
+    fn new(clients: Vec<redis::Client>, publish: PublishConfig) -> Self {
+        let keys = TYPE_NAMES
+            .into_iter()
+            .map(|name| {
+                let base = format!("market_data:{}", name);
+                let channel = format!("{}{}", base, publish.format.channel_suffix());
+                (name, (channel, format!("{}:stream", base)))
+            })
+            .collect();
+        Self {
+            connections: clients.iter().map(|_| tokio::sync::Mutex::new(None)).collect(),
+            clients,
+            active: AtomicUsize::new(0),
+            publish,
+            keys,
+        }
+    }
+
+    /// Shared connection to an endpoint, opened on first use
+    async fn connection(&self, index: usize) -> Result<MultiplexedConnection> {
+        let mut connection = self.connections[index].lock().await;
+        if let Some(connection) = connection.as_ref() {
+            return Ok(connection.clone());
+        }
+        let opened = self.clients[index].get_multiplexed_async_connection().await?;
+        *connection = Some(opened.clone());
+        Ok(opened)
+    }
+
+    /// Forget an endpoint's connection after an error so the next use reconnects
+    async fn reset(&self, index: usize) {
+        self.connections[index].lock().await.take();
+    }
+
+    /// Publish a batch to the active endpoint in one pipeline, failing over
+    /// through the rest
+    async fn publish(&self, batch: &[MarketData], buffers: &mut PublishBuffers) -> Result<()> {
+        self.build_pipeline(batch, buffers)?;
+        
+        let active = self.active.load(Ordering::Relaxed);
+        let mut last_error = None;
+        for offset in 0..self.clients.len() {
+            let index = (active + offset) % self.clients.len();
+            match self.publish_to(index, &buffers.pipeline).await {
+                Ok(()) => {
+                    if index != active {
+                        warn!("Redis failed over from endpoint {} to {}", active, index);
+                        self.active.store(index, Ordering::Relaxed);
+                        metrics::record_redis_failover();
+                    }
+                    return Ok(());
+                }
+                Err(e) => {
+                    debug!("Publish to Redis endpoint {} failed: {}", index, e);
+                    self.reset(index).await;
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No Redis endpoints configured")))
+    }
+
+    /// Replace the buffered pipeline with the commands publishing `batch`
+    fn build_pipeline(&self, batch: &[MarketData], buffers: &mut PublishBuffers) -> Result<()> {
+        buffers.pipeline.clear();
+        for data in batch {
+            self.add_commands(&mut buffers.pipeline, &mut buffers.payload, data)?;
+        }
+        Ok(())
+    }
+
+    /// Send a built pipeline to one endpoint
+    async fn publish_to(&self, index: usize, pipeline: &redis::Pipeline) -> Result<()> {
+        let mut conn = self.connection(index).await?;
+        pipeline.query_async::<_, ()>(&mut conn).await?;
+        Ok(())
+    }
+
+    /// Append the commands publishing one message to Redis pub/sub, a Redis
+    /// Stream, or both. The payload is serialized into `payload`, reused
+    /// across messages, and copied into the pipeline.
+    fn add_commands(
+        &self,
+        pipeline: &mut redis::Pipeline,
+        payload: &mut Vec<u8>,
+        data: &MarketData,
+    ) -> Result<()> {
+        let publish = &self.publish;
+        let (channel, stream) = &self.keys[type_name(data)];
+        data.write_payload(publish.format, payload)?;
+        
+        if publish.mode.publishes_pubsub() {
+            pipeline.cmd("PUBLISH").arg(channel.as_str()).arg(payload.as_slice()).ignore();
+        }
+        if publish.mode.publishes_stream() {
+            // Approximate trimming keeps XADD O(1)
+            pipeline
+                .cmd("XADD")
+                .arg(stream.as_str())
+                .arg("MAXLEN")
+                .arg("~")
+                .arg(publish.stream_maxlen)
+                .arg("*")
+                .arg("format")
+                .arg(publish.format.as_str())
+                .arg("data")
+                .arg(payload.as_slice())
+                .ignore();
+        }
+        Ok(())
+    }
+
+    /// Check that an endpoint responds to PING
+    async fn ping(&self, index: usize) -> Result<()> {
+        let mut conn = self.connection(index).await?;
+        if let Err(e) = redis::cmd("PING").query_async::<_, String>(&mut conn).await {
+            self.reset(index).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Switch back to the primary if it is reachable again
+    async fn try_restore_primary(&self) {
+        let active = self.active.load(Ordering::Relaxed);
+        if active == 0 || self.ping(0).await.is_err() {
+            return;
+        }
+        info!("Redis primary is reachable again, leaving endpoint {}", active);
+        self.active.store(0, Ordering::Relaxed);
+        metrics::record_redis_failover();
+    }
+}
+
+/// Drain queued messages into Redis in pipelined batches, holding them
+/// while no endpoint is up
+async fn run_redis_publisher(
+    endpoints: Arc<RedisEndpoints>,
+    queue: Arc<PublishQueue>,
+    failover: RedisFailoverConfig,
+) {
+    let primary_retry = Duration::from_secs(failover.primary_retry_seconds.max(1));
+    let retry_interval = Duration::from_millis(failover.retry_interval_ms.max(1));
+    let mut last_primary_check = Instant::now();
+    let mut buffers = PublishBuffers::new();
+
+    loop {
+        let batch = queue.pop_batch(PUBLISH_BATCH);
+        if batch.is_empty() {
+            queue.notify.notified().await;
+            continue;
+        }
+        if last_primary_check.elapsed() >= primary_retry {
+            last_primary_check = Instant::now();
+            endpoints.try_restore_primary().await;
+        }
+        if let Err(e) = endpoints.publish(&batch, &mut buffers).await {
+            warn!("No Redis endpoint accepted {} messages, retrying: {}", batch.len(), e);
+            queue.requeue_batch(batch);
+            tokio::time::sleep(retry_interval).await;
+        }
+    }
+}
+
+#[cfg(feature = "kafka")]
+mod kafka {
+    use super::{type_name, Sink, PAYLOAD_BUFFER};
+    use crate::config::PublishConfig;
+    use crate::{metrics, MarketData};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use rdkafka::config::ClientConfig;
+    use rdkafka::producer::future_producer::DeliveryFuture;
+    use rdkafka::producer::{FutureProducer, FutureRecord, Producer};
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio::task::JoinHandle;
+    use tracing::warn;
+
+    /// Kafka sink with a topic per data type, keyed by symbol so each
+    /// symbol stays ordered within one partition.
+    ///
+    /// Publishing only enqueues with librdkafka; a background task awaits
+    /// delivery reports and counts failed messages as dropped. librdkafka
+    /// bounds the messages in flight, so enqueueing fails once it is full.
+    pub struct KafkaSink {
+        producer: FutureProducer,
+        publish: PublishConfig,
+        deliveries: mpsc::UnboundedSender<DeliveryFuture>,
+        worker: JoinHandle<()>,
+    }
+
+    impl KafkaSink {
+        /// Create a producer from the Kafka settings
+        pub fn new(publish: PublishConfig) -> Result<Self> {
+            let mut client_config = ClientConfig::new();
+            client_config
+                .set("bootstrap.servers", &publish.kafka.brokers)
+                .set("message.timeout.ms", publish.kafka.delivery_timeout_ms.to_string());
+            for (key, value) in &publish.kafka.properties {
+                client_config.set(key, value);
+            }
+
+            let (deliveries, pending) = mpsc::unbounded_channel();
+            Ok(Self {
+                producer: client_config.create()?,
+                publish,
+                deliveries,
+                worker: tokio::spawn(collect_deliveries(pending)),
+            })
+        }
+    }
+
+    impl Drop for KafkaSink {
+        fn drop(&mut self) {
+            self.worker.abort();
+        }
+    }
+
+    /// Await delivery reports in enqueue order
+    async fn collect_deliveries(mut pending: mpsc::UnboundedReceiver<DeliveryFuture>) {
+        while let Some(delivery) = pending.recv().await {
+            let failure = match delivery.await {
+                Ok(Ok(_)) => continue,
+                Ok(Err((e, _))) => e.to_string(),
+                Err(_) => "delivery canceled".to_string(),
+            };
+            warn!("Kafka message not delivered: {}", failure);
+            metrics::record_publish_dropped();
+        }
+    }
+
+    #[async_trait]
+    impl Sink for KafkaSink {
+        fn name(&self) -> &'static str {
+            "kafka"
+        }
+
+        /// Enqueue a message; its delivery is awaited in the background
+        async fn publish(&self, data: &MarketData) -> Result<()> {
+            let topic = format!("{}{}", self.publish.kafka.topic_prefix, type_name(data));
+
+            // librdkafka copies the payload on enqueue, so the buffer is free
+            // again as soon as `send_result` returns
+            let delivery = PAYLOAD_BUFFER.with(|buffer| -> Result<_> {
+                let mut buffer = buffer.borrow_mut();
+                data.write_payload(self.publish.format, &mut buffer)?;
+                let record = FutureRecord::to(&topic)
+                    .key(data.symbol())
+                    .payload(buffer.as_slice());
+                self.producer
+                    .send_result(record)
+                    .map_err(|(e, _)| anyhow::Error::from(e))
+            })?;
+
+            if self.deliveries.send(delivery).is_err() {
+                anyhow::bail!("Kafka delivery collector stopped");
+            }
+            Ok(())
+        }
+
+        async fn ping(&self) -> Result<()> {
+            let producer = self.producer.clone();
+            let timeout = Duration::from_millis(self.publish.kafka.delivery_timeout_ms);
+            tokio::task::spawn_blocking(move || {
+                producer.client().fetch_metadata(None, timeout).map(|_| ())
+            })
+            .await??;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(symbol: &str) -> MarketData {
+        MarketData::Trade(crate::TradeData {
+            symbol: symbol.to_string(),
+            original_symbol: None,
+            price: crate::price::from_f64(190.0),
+            size: 100,
+            timestamp: chrono::Utc::now(),
+            secondary_timestamp: None,
+            exchange: "4".to_string(),
+            conditions: vec![],
+        })
+    }
+
+    #[tokio::test]
+    async fn test_publish_does_not_block_when_redis_is_down() {
+        let failover = RedisFailoverConfig {
+            fallback_urls: Vec::new(),
+            primary_retry_seconds: 1,
+            queue_capacity: 10,
+            retry_interval_ms: 50,
+        };
+        let sink = RedisSink::with_failover(
+            &["redis://127.0.0.1:1".to_string(), "redis://127.0.0.1:2".to_string()],
+            PublishConfig::default(),
+            failover,
+        )
+        .unwrap();
+        let data = trade("AAPL");
+
+        let published = tokio::time::timeout(Duration::from_secs(1), async {
+            for _ in 0..100 {
+                sink.publish(&data).await.unwrap();
+            }
+        })
+        .await;
+
+        assert!(published.is_ok());
+        assert!(sink.ping().await.is_err());
+        // Held for when Redis recovers; a failed batch is put back between retries
+        let held = tokio::time::timeout(Duration::from_secs(1), async {
+            while sink.queue_depth() < 10 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await;
+        assert!(held.is_ok());
+    }
+
+    #[test]
+    fn test_full_queue_drops_oldest() {
+        let queue = PublishQueue::new(2);
+        for symbol in ["A", "B", "C"] {
+            queue.push(trade(symbol));
+        }
+
+        let oldest = queue.pop_batch(1);
+        assert_eq!(oldest[0].symbol(), "B");
+        queue.requeue_batch(oldest);
+        let remaining = queue.pop_batch(10);
+        assert_eq!(remaining.iter().map(MarketData::symbol).collect::<Vec<_>>(), vec!["B", "C"]);
+        assert!(queue.pop_batch(10).is_empty());
+    }
+
+    #[test]
+    fn test_failed_batch_is_requeued_in_order() {
+        let queue = PublishQueue::new(3);
+        for symbol in ["A", "B", "C"] {
+            queue.push(trade(symbol));
+        }
+
+        let batch = queue.pop_batch(2);
+        assert_eq!(batch.len(), 2);
+        queue.push(trade("D"));
+        // Only one slot is free, so the older of the batch is dropped
+        queue.requeue_batch(batch);
+
+        let order = queue.pop_batch(10);
+        assert_eq!(order.iter().map(MarketData::symbol).collect::<Vec<_>>(), vec!["B", "C", "D"]);
+    }
+
+    #[test]
+    fn test_pipeline_buffers_are_reused_across_batches() {
+        let publish = PublishConfig {
+            format: crate::config::SerdeFormat::MessagePack,
+            mode: crate::config::PublishMode::Both,
+            ..PublishConfig::default()
+        };
+        let endpoints = RedisEndpoints::new(Vec::new(), publish);
+        let mut buffers = PublishBuffers::new();
+
+        let mut build = |batch: &[MarketData]| {
+            endpoints.build_pipeline(batch, &mut buffers).unwrap();
+            let packed = String::from_utf8_lossy(&buffers.pipeline.get_packed_pipeline()).into_owned();
+            (packed, buffers.payload.capacity())
+        };
+
+        let (first, capacity) = build(&[trade("AAPL"), trade("MSFT")]);
+        assert_eq!(first.matches("market_data:trades:msgpack").count(), 2);
+        assert_eq!(first.matches("market_data:trades:stream").count(), 2);
+
+        // A rebuilt pipeline carries nothing from the previous batch
+        let (second, reused_capacity) = build(&[trade("TSLA")]);
+        assert_eq!(second.matches("PUBLISH").count(), 1);
+        assert!(!second.contains("AAPL"));
+        assert_eq!(reused_capacity, capacity);
+    }
+
+    #[cfg(feature = "kafka")]
+    #[tokio::test]
+    async fn test_kafka_publish_does_not_wait_for_delivery() {
+        let mut publish = PublishConfig::default();
+        publish.kafka.brokers = "127.0.0.1:1".to_string();
+        publish.kafka.delivery_timeout_ms = 5_000;
+        let sink = KafkaSink::new(publish).unwrap();
+        let data = trade("AAPL");
+
+        // Each delivery would take the full timeout against a dead broker
+        let published = tokio::time::timeout(Duration::from_secs(1), async {
+            for _ in 0..100 {
+                sink.publish(&data).await.unwrap();
+            }
+        })
+        .await;
+        assert!(published.is_ok());
+    }
+
+    #[test]
+    fn test_redis_urls_start_with_primary() {
+        let mut config = DataIngestionConfig::default();
+        config.redis_failover.fallback_urls = vec!["redis://replica:6379".to_string()];
+
+        assert_eq!(
+            config.redis_urls(),
+            vec!["redis://localhost:6379".to_string(), "redis://replica:6379".to_string()]
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Latest-value snapshots per symbol
+
+use crate::config::TimestampSource;
+use crate::{QuoteData, TradeData};
+use chrono::{DateTime, Duration, Utc};
+use dashmap::DashMap;
+
+/// Latest quote (top of book) per symbol, ordered by exchange time
+#[derive(Default)]
+pub struct QuoteSnapshots {
+    quotes: DashMap<String, QuoteData>,
+    source: TimestampSource,
+}
+
+impl QuoteSnapshots {
+    /// Create an empty snapshot set for quotes stamped from `source`
+    pub fn new(source: TimestampSource) -> Self {
+        Self {
+            quotes: DashMap::new(),
+            source,
+        }
+    }
+
+    /// Exchange time of a quote, whichever timestamp source is stored
+    fn exchange_time(&self, quote: &QuoteData) -> DateTime<Utc> {
+        self.source.exchange_time(quote.timestamp, quote.secondary_timestamp)
+    }
+
+    /// Store a quote unless a newer one is already held for its symbol
+    pub fn update(&self, quote: &QuoteData) {
+        match self.quotes.get_mut(&quote.symbol) {
+            Some(mut current) => {
+                if self.exchange_time(quote) >= self.exchange_time(&current) {
+                    *current = quote.clone();
+                }
+            }
+            None => {
+                self.quotes.insert(quote.symbol.clone(), quote.clone());
+            }
+        }
+    }
+
+    /// Latest quote for a symbol
+    pub fn latest(&self, symbol: &str) -> Option<QuoteData> {
+        self.quotes.get(symbol).map(|quote| quote.clone())
+    }
+
+    /// Number of symbols with a quote
+    pub fn len(&self) -> usize {
+        self.quotes.len()
+    }
+
+    /// Whether no quotes have been seen
+    pub fn is_empty(&self) -> bool {
+        self.quotes.is_empty()
+    }
+}
+
+/// Last trade per symbol with a staleness window, both by exchange time
+pub struct TradeSnapshots {
+    trades: DashMap<String, TradeData>,
+    stale_after: Duration,
+    evict_after: Duration,
+    source: TimestampSource,
+}
+
+impl TradeSnapshots {
+    /// Trades older than `stale_after` are flagged stale; older than
+    /// `evict_after` they are dropped by `evict_expired`
+    pub fn new(stale_after: Duration, evict_after: Duration, source: TimestampSource) -> Self {
+        Self {
+            trades: DashMap::new(),
+            stale_after,
+            evict_after,
+            source,
+        }
+    }
+
+    /// Exchange time of a trade, whichever timestamp source is stored
+    fn exchange_time(&self, trade: &TradeData) -> DateTime<Utc> {
+        self.source.exchange_time(trade.timestamp, trade.secondary_timestamp)
+    }
+
+    /// Store a trade unless a newer one is already held for its symbol
+    pub fn update(&self, trade: &TradeData) {
+        match self.trades.get_mut(&trade.symbol) {
+            Some(mut current) => {
+                if self.exchange_time(trade) >= self.exchange_time(&current) {
+                    *current = trade.clone();
+                }
+            }
+            None => {
+                self.trades.insert(trade.symbol.clone(), trade.clone());
+            }
+        }
+    }
+
+    /// Last trade for a symbol and whether it is stale as of `now`
+    pub fn latest(&self, symbol: &str, now: DateTime<Utc>) -> Option<(TradeData, bool)> {
+        self.trades.get(symbol).map(|trade| {
+            let stale = now.signed_duration_since(self.exchange_time(&trade)) > self.stale_after;
+            (trade.clone(), stale)
+        })
+    }
+
+    /// Drop symbols with no trade within the eviction window, returning how many
+    pub fn evict_expired(&self, now: DateTime<Utc>) -> usize {
+        let before = self.trades.len();
+        self.trades
+            .retain(|_, trade| now.signed_duration_since(self.exchange_time(trade)) <= self.evict_after);
+        before - self.trades.len()
+    }
+
+    /// Number of symbols with a trade
+    pub fn len(&self) -> usize {
+        self.trades.len()
+    }
+
+    /// Whether no trades are held
+    pub fn is_empty(&self) -> bool {
+        self.trades.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    fn quote(bid: f64, timestamp: DateTime<Utc>) -> QuoteData {
+        QuoteData {
+            symbol: "AAPL".to_string(),
+            original_symbol: None,
+            bid_price: price::from_f64(bid),
+            ask_price: price::from_f64(bid + 0.01),
+            bid_size: 100,
+            ask_size: 100,
+            timestamp,
+            secondary_timestamp: None,
+            exchange: "NASDAQ|NASDAQ".to_string(),
+            bid_exchange: "NASDAQ".to_string(),
+            ask_exchange: "NASDAQ".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_late_quote_does_not_overwrite_newer() {
+        let snapshots = QuoteSnapshots::new(TimestampSource::Exchange);
+        let now = Utc::now();
+        snapshots.update(&quote(100.0, now));
+        snapshots.update(&quote(99.0, now - Duration::seconds(1)));
+
+        assert_eq!(snapshots.latest("AAPL").unwrap().bid_price, price::from_f64(100.0));
+
+        snapshots.update(&quote(101.0, now + Duration::seconds(1)));
+        assert_eq!(snapshots.latest("AAPL").unwrap().bid_price, price::from_f64(101.0));
+        assert!(snapshots.latest("MSFT").is_none());
+    }
+
+    #[test]
+    fn test_ingest_stamped_quotes_are_ordered_by_exchange_time() {
+        let snapshots = QuoteSnapshots::new(TimestampSource::Ingest);
+        let now = Utc::now();
+        let ingested = |bid: f64, exchange_time: DateTime<Utc>, received_at: DateTime<Utc>| QuoteData {
+            timestamp: received_at,
+            secondary_timestamp: Some(exchange_time),
+            ..quote(bid, exchange_time)
+        };
+
+        snapshots.update(&ingested(100.0, now, now + Duration::milliseconds(5)));
+        // Older on the exchange but received later
+        snapshots.update(&ingested(99.0, now - Duration::seconds(1), now + Duration::milliseconds(10)));
+
+        assert_eq!(snapshots.latest("AAPL").unwrap().bid_price, price::from_f64(100.0));
+    }
+
+    fn trade(symbol: &str, timestamp: DateTime<Utc>) -> TradeData {
+        TradeData {
+            symbol: symbol.to_string(),
+            original_symbol: None,
+            price: price::from_f64(150.25),
+            size: 100,
+            timestamp,
+            secondary_timestamp: None,
+            exchange: "NYSE".to_string(),
+            conditions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_trade_staleness_and_eviction() {
+        let snapshots = TradeSnapshots::new(Duration::seconds(60), Duration::hours(1), TimestampSource::Exchange);
+        let now = Utc::now();
+        snapshots.update(&trade("AAPL", now - Duration::seconds(10)));
+        snapshots.update(&trade("MSFT", now - Duration::minutes(5)));
+        snapshots.update(&trade("DLST", now - Duration::hours(2)));
+
+        assert!(!snapshots.latest("AAPL", now).unwrap().1);
+        assert!(snapshots.latest("MSFT", now).unwrap().1);
+
+        assert_eq!(snapshots.evict_expired(now), 1);
+        assert!(snapshots.latest("DLST", now).is_none());
+        assert_eq!(snapshots.len(), 2);
+    }
+}
@@ -0,0 +1,230 @@
+//! Canonical symbol mapping applied at ingestion
+
+use crate::{config::SymbolNormalizationConfig, MarketData};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maps provider-specific tickers to one canonical form
+#[derive(Debug, Clone, Default)]
+pub struct SymbolNormalizer {
+    aliases: HashMap<String, String>,
+    strip_prefixes: Vec<String>,
+}
+
+impl SymbolNormalizer {
+    /// Build from configuration
+    pub fn new(config: &SymbolNormalizationConfig) -> Self {
+        Self {
+            aliases: config.aliases.clone(),
+            strip_prefixes: config.strip_prefixes.clone(),
+        }
+    }
+
+    /// Whether any mapping is configured
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty() && self.strip_prefixes.is_empty()
+    }
+
+    /// Canonical form of a symbol, or `None` if it is already canonical.
+    ///
+    /// An alias for the raw symbol wins; otherwise the first matching
+    /// prefix is stripped and the remainder looked up in the alias table.
+    pub fn normalize(&self, symbol: &str) -> Option<String> {
+        if let Some(canonical) = self.aliases.get(symbol) {
+            return Some(canonical.clone());
+        }
+
+        let stripped = self
+            .strip_prefixes
+            .iter()
+            .find_map(|prefix| symbol.strip_prefix(prefix.as_str()))?;
+        Some(self.aliases.get(stripped).map_or(stripped, String::as_str).to_string())
+    }
+
+    /// Rewrite an event's symbol to canonical form, keeping the original
+    pub fn apply(&self, data: &mut MarketData) {
+        let (symbol, original) = match data {
+            MarketData::Trade(trade) => (&mut trade.symbol, &mut trade.original_symbol),
+            MarketData::Quote(quote) => (&mut quote.symbol, &mut quote.original_symbol),
+            MarketData::Aggregate(agg) => (&mut agg.symbol, &mut agg.original_symbol),
+        };
+        if let Some(canonical) = self.normalize(symbol) {
+            *original = Some(std::mem::replace(symbol, canonical));
+        }
+    }
+}
+
+/// Broad instrument class of a symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstrumentType {
+    Equity,
+    Etf,
+    Option,
+    Crypto,
+    Forex,
+    Index,
+}
+
+/// Widely traded ETFs; nothing in the ticker format tells them apart from
+/// common stock, so anything else is treated as an equity
+const KNOWN_ETFS: &[&str] = &[
+    "SPY", "IVV", "VOO", "VTI", "QQQ", "IWM", "DIA", "EFA", "EEM", "AGG", "BND", "TLT", "IEF",
+    "SHY", "LQD", "HYG", "GLD", "SLV", "USO", "UNG", "VNQ", "VEA", "VWO", "XLB", "XLE", "XLF",
+    "XLI", "XLK", "XLP", "XLU", "XLV", "XLY", "XLC", "XLRE", "SMH", "ARKK", "TQQQ", "SQQQ",
+    "UVXY", "VXX",
+];
+
+/// Currencies recognised as either leg of an unprefixed forex pair
+const FIAT_CURRENCIES: &[&str] = &[
+    "USD", "EUR", "JPY", "GBP", "CHF", "AUD", "NZD", "CAD", "SEK", "NOK", "DKK", "HKD", "SGD",
+    "CNH", "MXN", "ZAR", "TRY", "PLN",
+];
+
+/// Coins recognised as the base of an unprefixed crypto pair
+const CRYPTO_ASSETS: &[&str] = &[
+    "BTC", "ETH", "SOL", "XRP", "ADA", "DOGE", "DOT", "LTC", "BCH", "AVAX", "LINK", "MATIC",
+    "USDT", "USDC",
+];
+
+/// Classify a symbol by its format.
+///
+/// Polygon prefixes (`O:`, `X:`, `C:`, `I:`) are authoritative. Without
+/// one, OCC option symbols, `BTC-USD` style coin pairs and six-letter
+/// currency pairs such as `EURUSD` are recognised; everything else is an
+/// equity unless it is a known ETF.
+pub fn classify_symbol(symbol: &str) -> InstrumentType {
+    let symbol = symbol.trim();
+    if let Some((prefix, _)) = symbol.split_once(':') {
+        match prefix {
+            "O" => return InstrumentType::Option,
+            "X" => return InstrumentType::Crypto,
+            "C" => return InstrumentType::Forex,
+            "I" => return InstrumentType::Index,
+            _ => {}
+        }
+    }
+
+    // Ticker formats are ASCII; anything else is left as a plain equity
+    if !symbol.is_ascii() {
+        return InstrumentType::Equity;
+    }
+    if is_occ_option(symbol) {
+        return InstrumentType::Option;
+    }
+    if let Some((base, quote)) = split_pair(symbol) {
+        if contains_ignore_case(CRYPTO_ASSETS, base) {
+            return InstrumentType::Crypto;
+        }
+        if contains_ignore_case(FIAT_CURRENCIES, base) && contains_ignore_case(FIAT_CURRENCIES, quote) {
+            return InstrumentType::Forex;
+        }
+    }
+    if contains_ignore_case(KNOWN_ETFS, symbol) {
+        return InstrumentType::Etf;
+    }
+    InstrumentType::Equity
+}
+
+fn contains_ignore_case(list: &[&str], symbol: &str) -> bool {
+    list.iter().any(|entry| entry.eq_ignore_ascii_case(symbol))
+}
+
+/// OCC option symbol: a root of up to six characters (optionally space
+/// padded), expiry as `YYMMDD`, `C` or `P`, then the strike times 1000 in
+/// eight digits. Expects an ASCII symbol.
+fn is_occ_option(symbol: &str) -> bool {
+    let Some(split) = symbol.len().checked_sub(15) else {
+        return false;
+    };
+    let (root, contract) = symbol.as_bytes().split_at(split);
+    let root = root.trim_ascii_end();
+
+    (1..=6).contains(&root.len())
+        && root.iter().all(|b| b.is_ascii_alphanumeric() || *b == b'.')
+        && contract[..6].iter().all(u8::is_ascii_digit)
+        && matches!(contract[6], b'C' | b'P' | b'c' | b'p')
+        && contract[7..].iter().all(u8::is_ascii_digit)
+}
+
+/// Base and quote of an ASCII currency pair written `BASE-QUOTE`,
+/// `BASE/QUOTE` or as a bare concatenation ending in a known quote currency
+fn split_pair(symbol: &str) -> Option<(&str, &str)> {
+    if let Some(pair) = symbol.split_once(['-', '/']) {
+        return Some(pair);
+    }
+    ["USDT", "USDC", "USD", "EUR", "BTC"].iter().find_map(|quote| {
+        let split = symbol.len().checked_sub(quote.len()).filter(|split| *split >= 3)?;
+        symbol[split..].eq_ignore_ascii_case(quote).then(|| symbol.split_at(split))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalizer() -> SymbolNormalizer {
+        SymbolNormalizer::new(&SymbolNormalizationConfig {
+            aliases: HashMap::from([
+                ("BRK-B".to_string(), "BRK.B".to_string()),
+                ("XBTUSD".to_string(), "BTCUSD".to_string()),
+            ]),
+            strip_prefixes: vec!["X:".to_string()],
+        })
+    }
+
+    #[test]
+    fn test_aliases_and_prefixes_map_to_canonical_symbols() {
+        let normalizer = normalizer();
+
+        assert_eq!(normalizer.normalize("BRK-B").as_deref(), Some("BRK.B"));
+        assert_eq!(normalizer.normalize("X:BTCUSD").as_deref(), Some("BTCUSD"));
+        assert_eq!(normalizer.normalize("X:XBTUSD").as_deref(), Some("BTCUSD"));
+        assert_eq!(normalizer.normalize("BRK.B"), None);
+        assert_eq!(normalizer.normalize("AAPL"), None);
+    }
+
+    #[test]
+    fn test_apply_keeps_original_symbol() {
+        let mut data = MarketData::Trade(crate::TradeData {
+            symbol: "X:BTCUSD".to_string(),
+            original_symbol: None,
+            price: crate::price::from_f64(65_000.0),
+            size: 1,
+            timestamp: chrono::Utc::now(),
+            secondary_timestamp: None,
+            exchange: "1".to_string(),
+            conditions: vec![],
+        });
+
+        normalizer().apply(&mut data);
+
+        let MarketData::Trade(trade) = data else { unreachable!() };
+        assert_eq!(trade.symbol, "BTCUSD");
+        assert_eq!(trade.original_symbol.as_deref(), Some("X:BTCUSD"));
+    }
+
+    #[test]
+    fn test_classify_symbol_by_format() {
+        assert_eq!(classify_symbol("AAPL"), InstrumentType::Equity);
+        assert_eq!(classify_symbol("BRK.B"), InstrumentType::Equity);
+        assert_eq!(classify_symbol("SPY"), InstrumentType::Etf);
+        assert_eq!(classify_symbol("O:AAPL240119C00190000"), InstrumentType::Option);
+        assert_eq!(classify_symbol("SPY   240621P00500000"), InstrumentType::Option);
+        assert_eq!(classify_symbol("X:BTCUSD"), InstrumentType::Crypto);
+        assert_eq!(classify_symbol("ETH-USD"), InstrumentType::Crypto);
+        assert_eq!(classify_symbol("SOLUSDT"), InstrumentType::Crypto);
+        assert_eq!(classify_symbol("C:EURUSD"), InstrumentType::Forex);
+        assert_eq!(classify_symbol("GBP/JPY"), InstrumentType::Forex);
+        assert_eq!(classify_symbol("EURUSD"), InstrumentType::Forex);
+        assert_eq!(classify_symbol("I:SPX"), InstrumentType::Index);
+        assert_eq!(classify_symbol("spy"), InstrumentType::Etf);
+        assert_eq!(classify_symbol("btc-usd"), InstrumentType::Crypto);
+    }
+
+    #[test]
+    fn test_classify_non_ascii_symbol_does_not_panic() {
+        assert_eq!(classify_symbol("ééééééééé"), InstrumentType::Equity);
+        assert_eq!(classify_symbol("AAPLé40119C00190000"), InstrumentType::Equity);
+    }
+}
@@ -0,0 +1,103 @@
+//! Provider-agnostic flat view of market data
+
+use crate::{price, MarketData, Price};
+use serde::{Deserialize, Serialize};
+
+/// Feed that produces `MarketData` today, used by `From<MarketData>`
+pub const DEFAULT_SOURCE: &str = "polygon";
+
+/// Kind of event a tick was derived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TickKind {
+    Trade,
+    Quote,
+    Aggregate,
+}
+
+/// One price observation in a shape shared by every provider.
+///
+/// Trades map directly; quotes use the bid/ask midpoint and the combined
+/// displayed size; aggregates use the close and the bar volume.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedTick {
+    /// Provider the event came from
+    pub source: String,
+    pub kind: TickKind,
+    /// Canonical symbol
+    pub symbol: String,
+    pub price: Price,
+    pub size: u64,
+    /// Event time in nanoseconds since the Unix epoch
+    pub timestamp_ns: i64,
+}
+
+impl NormalizedTick {
+    /// Flatten an event produced by `source`
+    pub fn from_market_data(data: MarketData, source: &str) -> Self {
+        let timestamp_ns = data.timestamp().timestamp_nanos_opt().unwrap_or_default();
+        let (kind, symbol, price, size) = match data {
+            MarketData::Trade(trade) => (TickKind::Trade, trade.symbol, trade.price, trade.size),
+            MarketData::Quote(quote) => (
+                TickKind::Quote,
+                quote.symbol,
+                (quote.bid_price + quote.ask_price) / price::from_units(2),
+                quote.bid_size + quote.ask_size,
+            ),
+            MarketData::Aggregate(agg) => (TickKind::Aggregate, agg.symbol, agg.close, agg.volume),
+        };
+
+        Self {
+            source: source.to_string(),
+            kind,
+            symbol,
+            price,
+            size,
+            timestamp_ns,
+        }
+    }
+}
+
+impl From<MarketData> for NormalizedTick {
+    fn from(data: MarketData) -> Self {
+        Self::from_market_data(data, DEFAULT_SOURCE)
+    }
+}
+
+impl From<&MarketData> for NormalizedTick {
+    fn from(data: &MarketData) -> Self {
+        Self::from(data.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QuoteData;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_quote_flattens_to_midpoint() {
+        let timestamp = chrono::Utc.timestamp_opt(1_700_000_000, 5).unwrap();
+        let tick = NormalizedTick::from(MarketData::Quote(QuoteData {
+            symbol: "AAPL".to_string(),
+            original_symbol: Some("AAPL.O".to_string()),
+            bid_price: price::from_f64(189.5),
+            ask_price: price::from_f64(189.7),
+            bid_size: 300,
+            ask_size: 100,
+            timestamp,
+            secondary_timestamp: None,
+            exchange: "NASDAQ|NYSE".to_string(),
+            bid_exchange: "NASDAQ".to_string(),
+            ask_exchange: "NYSE".to_string(),
+        }));
+
+        assert_eq!(tick.source, "polygon");
+        assert_eq!(tick.kind, TickKind::Quote);
+        assert_eq!(tick.symbol, "AAPL");
+        assert!((price::to_f64(tick.price) - 189.6).abs() < 1e-9);
+        assert_eq!(tick.size, 400);
+        assert_eq!(tick.timestamp_ns, 1_700_000_000_000_000_005);
+    }
+}
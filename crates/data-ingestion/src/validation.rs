@@ -1,99 +1,280 @@
 //! Market data validation
 
+use crate::calendar::{MarketCalendar, SessionState};
+use crate::config::ValidationConfig;
+use crate::price::to_f64;
+use crate::symbols::classify_symbol;
 use crate::MarketData;
-use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use tracing::warn;
 
+/// Individual validation checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationRule {
+    EmptySymbol,
+    InvalidPrice,
+    ZeroSize,
+    StaleTimestamp,
+    CrossedMarket,
+    ZeroQuoteSize,
+    InvalidOhlc,
+    ZeroVolume,
+    /// Trade price not a multiple of the symbol's tick size
+    TickSize,
+    /// Trade size not a multiple of the symbol's lot size
+    OddLot,
+    /// Price outside the bounds for the symbol's instrument type
+    PriceBounds,
+    /// Failures reported by custom validators
+    Custom,
+}
+
+impl ValidationRule {
+    /// Behavior when not configured explicitly
+    pub fn default_strictness(&self) -> Strictness {
+        match self {
+            ValidationRule::StaleTimestamp
+            | ValidationRule::ZeroQuoteSize
+            | ValidationRule::ZeroVolume
+            | ValidationRule::OddLot => Strictness::Warn,
+            _ => Strictness::Reject,
+        }
+    }
+}
+
+/// What to do when a rule fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Strictness {
+    /// Drop the message
+    Reject,
+    /// Log and keep the message
+    Warn,
+    /// Skip the check
+    Ignore,
+}
+
+/// A failed validation rule
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{rule:?}: {message}")]
+pub struct ValidationError {
+    pub rule: ValidationRule,
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Create an error for a rule
+    pub fn new(rule: ValidationRule, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            message: message.into(),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ValidationError>;
+
+/// Firm-specific check registered on the ingestion manager
+pub type ValidatorFn = Box<dyn Fn(&MarketData) -> Result<()> + Send + Sync>;
+
+/// Custom validator with the strictness applied to its failures
+pub struct CustomValidator {
+    pub name: String,
+    pub strictness: Strictness,
+    pub check: ValidatorFn,
+}
+
+/// Run custom validators in registration order; a rejecting failure
+/// short-circuits the rest
+pub fn run_custom_validators(data: &MarketData, validators: &[CustomValidator]) -> Result<()> {
+    for validator in validators {
+        let Err(e) = (validator.check)(data) else {
+            continue;
+        };
+        
+        match validator.strictness {
+            Strictness::Reject => return Err(e),
+            Strictness::Warn => warn!("Validator {}: {}", validator.name, e),
+            Strictness::Ignore => {}
+        }
+    }
+    
+    Ok(())
+}
+
+/// Applies each rule's configured strictness
+struct Checker<'a> {
+    config: &'a ValidationConfig,
+}
+
+impl Checker<'_> {
+    /// Reject, warn or ignore when `failed` holds, per the rule's strictness
+    fn check(&self, rule: ValidationRule, failed: bool, message: impl FnOnce() -> String) -> Result<()> {
+        if !failed {
+            return Ok(());
+        }
+        
+        match self.config.strictness(rule) {
+            Strictness::Reject => Err(ValidationError::new(rule, message())),
+            Strictness::Warn => {
+                warn!("{:?}: {}", rule, message());
+                Ok(())
+            }
+            Strictness::Ignore => Ok(()),
+        }
+    }
+
+    /// Check prices against the bounds for the symbol's instrument type,
+    /// classifying the provider symbol since normalization may strip the
+    /// prefix that identifies it
+    fn check_price_bounds(&self, symbol: &str, original: Option<&str>, prices: &[f64]) -> Result<()> {
+        let instrument = classify_symbol(original.unwrap_or(symbol));
+        let Some(bounds) = self.config.price_bounds.get(&instrument) else {
+            return Ok(());
+        };
+        
+        let outside = prices.iter().find(|&&price| !bounds.contains(price));
+        self.check(ValidationRule::PriceBounds, outside.is_some(), || {
+            format!(
+                "Price {} for {} outside {:?} bounds [{}, {}]",
+                outside.copied().unwrap_or_default(),
+                symbol,
+                instrument,
+                bounds.min,
+                bounds.max
+            )
+        })
+    }
+}
+
 /// Validate market data
-pub fn validate_market_data(data: &MarketData) -> Result<()> {
+pub fn validate_market_data(data: &MarketData, config: &ValidationConfig) -> Result<()> {
+    let checker = Checker { config };
     match data {
-        MarketData::Trade(trade) => validate_trade_data(trade),
-        MarketData::Quote(quote) => validate_quote_data(quote),
-        MarketData::Aggregate(agg) => validate_aggregate_data(agg),
+        MarketData::Trade(trade) => validate_trade_data(trade, &checker),
+        MarketData::Quote(quote) => validate_quote_data(quote, &checker),
+        MarketData::Aggregate(agg) => validate_aggregate_data(agg, &checker),
     }
 }
 
 /// Validate trade data
-fn validate_trade_data(trade: &crate::TradeData) -> Result<()> {
+fn validate_trade_data(trade: &crate::TradeData, checker: &Checker) -> Result<()> {
     // Validate symbol
-    if trade.symbol.is_empty() {
-        return Err(anyhow!("Empty symbol"));
-    }
+    checker.check(ValidationRule::EmptySymbol, trade.symbol.is_empty(), || {
+        "Empty symbol".to_string()
+    })?;
     
     // Validate price
-    if trade.price <= 0.0 || trade.price.is_nan() || trade.price.is_infinite() {
-        return Err(anyhow!("Invalid price: {}", trade.price));
-    }
+    let price = to_f64(trade.price);
+    checker.check(
+        ValidationRule::InvalidPrice,
+        price <= 0.0 || price.is_nan() || price.is_infinite(),
+        || format!("Invalid price: {}", trade.price),
+    )?;
+    checker.check_price_bounds(&trade.symbol, trade.original_symbol.as_deref(), &[price])?;
     
     // Validate size
-    if trade.size == 0 {
-        return Err(anyhow!("Zero trade size"));
+    checker.check(ValidationRule::ZeroSize, trade.size == 0, || {
+        "Zero trade size".to_string()
+    })?;
+    
+    // Validate tick and lot size against the symbol's spec
+    if let Some(spec) = checker.config.symbol_specs.get(&trade.symbol) {
+        checker.check(ValidationRule::TickSize, !spec.is_on_tick(price), || {
+            format!("Price {} off tick size {} for {}", price, spec.tick_size, trade.symbol)
+        })?;
+        checker.check(ValidationRule::OddLot, spec.is_odd_lot(trade.size), || {
+            format!("Odd lot of {} for {} (lot size {})", trade.size, trade.symbol, spec.lot_size)
+        })?;
     }
     
-    // Validate timestamp (not too old)
+    // Validate timestamp (not too old); stale prints are expected while closed
     let now = chrono::Utc::now();
     let age = now.signed_duration_since(trade.timestamp);
-    if age.num_seconds() > 60 {
-        warn!("Old trade data: {} seconds old", age.num_seconds());
-    }
+    let session = MarketCalendar::us_equity().session_state(now, &trade.exchange);
+    checker.check(
+        ValidationRule::StaleTimestamp,
+        age.num_seconds() > 60 && session != SessionState::Closed,
+        || format!("Old trade data: {} seconds old", age.num_seconds()),
+    )?;
     
     Ok(())
 }
 
 /// Validate quote data
-fn validate_quote_data(quote: &crate::QuoteData) -> Result<()> {
+fn validate_quote_data(quote: &crate::QuoteData, checker: &Checker) -> Result<()> {
     // Validate symbol
-    if quote.symbol.is_empty() {
-        return Err(anyhow!("Empty symbol"));
-    }
+    checker.check(ValidationRule::EmptySymbol, quote.symbol.is_empty(), || {
+        "Empty symbol".to_string()
+    })?;
     
     // Validate prices
-    if quote.bid_price <= 0.0 || quote.ask_price <= 0.0 {
-        return Err(anyhow!("Invalid bid/ask prices"));
-    }
+    checker.check(
+        ValidationRule::InvalidPrice,
+        to_f64(quote.bid_price) <= 0.0 || to_f64(quote.ask_price) <= 0.0,
+        || "Invalid bid/ask prices".to_string(),
+    )?;
+    checker.check_price_bounds(
+        &quote.symbol,
+        quote.original_symbol.as_deref(),
+        &[to_f64(quote.bid_price), to_f64(quote.ask_price)],
+    )?;
     
     // Validate spread
-    if quote.ask_price <= quote.bid_price {
-        return Err(anyhow!("Invalid spread: ask <= bid"));
-    }
+    checker.check(ValidationRule::CrossedMarket, quote.ask_price <= quote.bid_price, || {
+        "Invalid spread: ask <= bid".to_string()
+    })?;
     
     // Validate sizes
-    if quote.bid_size == 0 || quote.ask_size == 0 {
-        warn!("Zero bid/ask size for {}", quote.symbol);
-    }
+    checker.check(
+        ValidationRule::ZeroQuoteSize,
+        quote.bid_size == 0 || quote.ask_size == 0,
+        || format!("Zero bid/ask size for {}", quote.symbol),
+    )?;
     
     Ok(())
 }
 
 /// Validate aggregate data
-fn validate_aggregate_data(agg: &crate::AggregateData) -> Result<()> {
+fn validate_aggregate_data(agg: &crate::AggregateData, checker: &Checker) -> Result<()> {
     // Validate symbol
-    if agg.symbol.is_empty() {
-        return Err(anyhow!("Empty symbol"));
-    }
+    checker.check(ValidationRule::EmptySymbol, agg.symbol.is_empty(), || {
+        "Empty symbol".to_string()
+    })?;
     
     // Validate OHLC
-    if agg.open <= 0.0 || agg.high <= 0.0 || agg.low <= 0.0 || agg.close <= 0.0 {
-        return Err(anyhow!("Invalid OHLC values"));
-    }
+    checker.check(
+        ValidationRule::InvalidPrice,
+        [agg.open, agg.high, agg.low, agg.close].iter().any(|&p| to_f64(p) <= 0.0),
+        || "Invalid OHLC values".to_string(),
+    )?;
+    checker.check_price_bounds(
+        &agg.symbol,
+        agg.original_symbol.as_deref(),
+        &[to_f64(agg.low), to_f64(agg.high)],
+    )?;
     
     // Validate OHLC relationships
-    if agg.high < agg.low {
-        return Err(anyhow!("High < Low"));
-    }
-    
-    if agg.high < agg.open || agg.high < agg.close {
-        return Err(anyhow!("High is not the highest"));
-    }
-    
-    if agg.low > agg.open || agg.low > agg.close {
-        return Err(anyhow!("Low is not the lowest"));
-    }
+    checker.check(ValidationRule::InvalidOhlc, agg.high < agg.low, || {
+        "High < Low".to_string()
+    })?;
+    checker.check(
+        ValidationRule::InvalidOhlc,
+        agg.high < agg.open || agg.high < agg.close,
+        || "High is not the highest".to_string(),
+    )?;
+    checker.check(
+        ValidationRule::InvalidOhlc,
+        agg.low > agg.open || agg.low > agg.close,
+        || "Low is not the lowest".to_string(),
+    )?;
     
     // Validate volume
-    if agg.volume == 0 {
-        warn!("Zero volume for {}", agg.symbol);
-    }
+    checker.check(ValidationRule::ZeroVolume, agg.volume == 0, || {
+        format!("Zero volume for {}", agg.symbol)
+    })?;
     
     Ok(())
 }
@@ -103,31 +284,136 @@ mod tests {
     use super::*;
     use chrono::Utc;
 
+    fn trade(symbol: &str, value: f64, size: u64) -> MarketData {
+        MarketData::Trade(crate::TradeData {
+            symbol: symbol.to_string(),
+            original_symbol: None,
+            price: crate::price::from_f64(value),
+            size,
+            timestamp: Utc::now(),
+            secondary_timestamp: None,
+            exchange: "NASDAQ".to_string(),
+            conditions: vec![],
+        })
+    }
+
     #[test]
     fn test_valid_trade_data() {
         let trade = crate::TradeData {
             symbol: "AAPL".to_string(),
-            price: 150.0,
+            original_symbol: None,
+            price: crate::price::from_f64(150.0),
             size: 100,
             timestamp: Utc::now(),
+            secondary_timestamp: None,
             exchange: "NASDAQ".to_string(),
             conditions: vec![],
         };
         
-        assert!(validate_trade_data(&trade).is_ok());
+        let config = ValidationConfig::default();
+        assert!(validate_market_data(&MarketData::Trade(trade), &config).is_ok());
     }
 
     #[test]
     fn test_invalid_trade_price() {
         let trade = crate::TradeData {
             symbol: "AAPL".to_string(),
-            price: -150.0,
+            original_symbol: None,
+            price: crate::price::from_f64(-150.0),
             size: 100,
             timestamp: Utc::now(),
+            secondary_timestamp: None,
             exchange: "NASDAQ".to_string(),
             conditions: vec![],
         };
         
-        assert!(validate_trade_data(&trade).is_err());
+        let config = ValidationConfig::default();
+        let err = validate_market_data(&MarketData::Trade(trade), &config).unwrap_err();
+        assert_eq!(err.rule, ValidationRule::InvalidPrice);
+    }
+
+    #[test]
+    fn test_rule_strictness_overrides() {
+        let quote = MarketData::Quote(crate::QuoteData {
+            symbol: "AAPL".to_string(),
+            original_symbol: None,
+            bid_price: crate::price::from_f64(150.05),
+            ask_price: crate::price::from_f64(150.0),
+            bid_size: 100,
+            ask_size: 100,
+            timestamp: Utc::now(),
+            secondary_timestamp: None,
+            exchange: "NASDAQ|NASDAQ".to_string(),
+            bid_exchange: "NASDAQ".to_string(),
+            ask_exchange: "NASDAQ".to_string(),
+        });
+        let mut config = ValidationConfig::default();
+        assert!(validate_market_data(&quote, &config).is_err());
+
+        config.rules.insert(ValidationRule::CrossedMarket, Strictness::Warn);
+        assert!(validate_market_data(&quote, &config).is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_custom_validators_warn_or_reject() {
+        let trade = MarketData::Trade(crate::TradeData {
+            symbol: "AAPL".to_string(),
+            original_symbol: None,
+            price: crate::price::from_f64(150.0),
+            size: 100,
+            timestamp: Utc::now(),
+            secondary_timestamp: None,
+            exchange: "IEX".to_string(),
+            conditions: vec![],
+        });
+        let no_iex = |strictness| CustomValidator {
+            name: "no_iex".to_string(),
+            strictness,
+            check: Box::new(|data: &MarketData| match data {
+                MarketData::Trade(t) if t.exchange == "IEX" => {
+                    Err(ValidationError::new(ValidationRule::Custom, "IEX trades not accepted"))
+                }
+                _ => Ok(()),
+            }),
+        };
+
+        assert!(run_custom_validators(&trade, &[no_iex(Strictness::Warn)]).is_ok());
+        assert!(run_custom_validators(&trade, &[no_iex(Strictness::Reject)]).is_err());
+    }
+
+    #[test]
+    fn test_tick_and_lot_size() {
+        let mut config = ValidationConfig::default();
+        config.symbol_specs.insert(
+            "AAPL".to_string(),
+            crate::config::SymbolSpec { tick_size: 0.01, lot_size: 100 },
+        );
+        assert!(validate_market_data(&trade("AAPL", 150.12, 100), &config).is_ok());
+        // Odd lots only warn by default
+        assert!(validate_market_data(&trade("AAPL", 150.12, 37), &config).is_ok());
+        let err = validate_market_data(&trade("AAPL", 150.12345, 100), &config).unwrap_err();
+        assert_eq!(err.rule, ValidationRule::TickSize);
+    }
+
+    #[test]
+    fn test_price_bounds_by_instrument_type() {
+        let mut config = ValidationConfig::default();
+
+        assert!(validate_market_data(&trade("O:AAPL240119C00190000", 0.01, 1), &config).is_ok());
+        assert!(validate_market_data(&trade("X:SHIBUSD", 0.00002, 1), &config).is_ok());
+        let err = validate_market_data(&trade("SPY", 0.005, 1), &config).unwrap_err();
+        assert_eq!(err.rule, ValidationRule::PriceBounds);
+        assert!(validate_market_data(&trade("AAPL", 15_000_000.0, 1), &config).is_err());
+
+        // Bounds apply to configs loaded from a file too
+        let loaded: ValidationConfig = serde_json::from_str(
+            r#"{"max_price_deviation":10.0,"max_timestamp_lag":5,"strict_validation":true}"#,
+        )
+        .unwrap();
+        assert!(validate_market_data(&trade("SPY", 0.005, 1), &loaded).is_err());
+
+        // Outside strict mode bounds only warn
+        config.strict_validation = false;
+        assert!(validate_market_data(&trade("SPY", 0.005, 1), &config).is_ok());
+    }
+}
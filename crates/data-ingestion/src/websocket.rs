@@ -1,120 +1,938 @@
 //! WebSocket client for real-time market data
 
-use crate::{config::DataIngestionConfig, MarketData};
+use crate::{
+    config::{CloseAction, ClosePolicyConfig, DataIngestionConfig, EndpointConfig, FeedMode, TimestampSource},
+    dead_letter::DeadLetterSink,
+    mock_server::MockServer,
+    polygon::{self, PolygonCluster, PolygonMessage, PolygonStatus},
+    rate_limit::TokenBucket,
+    symbols::SymbolNormalizer,
+    MarketData,
+};
 use anyhow::Result;
+use dashmap::DashMap;
 use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
 use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 use tracing::{debug, error, info, warn};
 
-/// WebSocket manager for market data streams
-pub struct WebSocketManager {
-    config: DataIngestionConfig,
+/// Frame that could not be parsed, carrying a truncated copy of its text
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to parse Polygon frame ({source}): {frame}")]
+pub struct FrameParseError {
+    /// Raw frame, truncated to the configured length
+    pub frame: String,
+    #[source]
+    pub source: serde_json::Error,
+}
+
+impl FrameParseError {
+    fn new(text: &str, max_len: usize, source: serde_json::Error) -> Self {
+        let frame = match text.char_indices().nth(max_len) {
+            Some((end, _)) => format!("{}... ({} bytes)", &text[..end], text.len()),
+            None => text.to_string(),
+        };
+        Self { frame, source }
+    }
+}
+
+/// Connection refused or ended by Polygon
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ServerError {
+    /// The API key was rejected; reconnecting will not help
+    #[error("Polygon authentication failed: {0}")]
+    AuthFailed(String),
+    /// Too many connections are open for this key and cluster
+    #[error("Polygon connection limit reached, close other clients using this API key: {0}")]
+    MaxConnections(String),
+    /// Closed with a code or reason the close policy gives up on
+    #[error("Polygon closed the connection with code {code}: {reason}")]
+    Closed { code: u16, reason: String },
+}
+
+impl ServerError {
+    /// Error carried by a status event, or `None` for benign statuses
+    pub fn from_status(status: &PolygonStatus) -> Option<Self> {
+        match status.status.as_str() {
+            "auth_failed" => Some(ServerError::AuthFailed(status.message.clone())),
+            "max_connections" => Some(ServerError::MaxConnections(status.message.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// Longest wait between reconnection attempts
+const MAX_BACKOFF_SECS: u64 = 60;
+
+/// Acknowledgement state of a subscription
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", content = "reason", rename_all = "snake_case")]
+pub enum SubscriptionState {
+    /// Sent, awaiting Polygon's status message
+    Pending,
+    /// Acknowledged by Polygon
+    Confirmed,
+    /// Rejected, e.g. unknown ticker or missing entitlement
+    Failed(String),
+}
+
+/// Market data and status events from one frame
+#[derive(Debug, Default)]
+struct ParsedFrame {
+    data: Vec<MarketData>,
+    statuses: Vec<PolygonStatus>,
+    /// Raw JSON of events that failed to parse, with the error
+    rejected: Vec<(String, serde_json::Error)>,
+}
+
+/// Live state of one endpoint connection, shared with its task
+#[derive(Debug, Default)]
+struct ConnectionState {
+    connected: AtomicBool,
+    connected_at_ms: AtomicI64,
+    last_message_ms: AtomicI64,
+    /// Subscription channel (e.g. `T.AAPL`) to acknowledgement state
+    subscriptions: DashMap<String, SubscriptionState>,
+}
+
+impl ConnectionState {
+    fn last_activity_ms(&self) -> i64 {
+        self.last_message_ms
+            .load(Ordering::Relaxed)
+            .max(self.connected_at_ms.load(Ordering::Relaxed))
+    }
+}
+
+/// Settings and outputs shared by every connection task
+struct ConnectionContext {
+    api_key: String,
+    base_url: String,
+    connect_timeout: std::time::Duration,
+    max_reconnect_attempts: u32,
+    max_frame_len: usize,
+    subscribe_batch_size: usize,
+    subscribe_batch_delay: std::time::Duration,
+    /// Paces control messages across every connection, so reconnects do
+    /// not start with a fresh burst
+    control_limiter: tokio::sync::Mutex<TokenBucket>,
+    close_policy: ClosePolicyConfig,
     data_tx: broadcast::Sender<MarketData>,
+    fatal_tx: broadcast::Sender<ServerError>,
+    dead_letter: Option<DeadLetterSink>,
+    normalizer: SymbolNormalizer,
+    timestamp_source: TimestampSource,
+}
+
+/// A supervised connection to one endpoint
+struct Connection {
+    endpoint: EndpointConfig,
+    state: Arc<ConnectionState>,
+    task: JoinHandle<()>,
+}
+
+/// WebSocket manager for market data streams.
+///
+/// Each configured endpoint runs in its own task that reconnects with
+/// backoff on failure; all of them feed the same broadcast channel.
+pub struct WebSocketManager {
+    endpoints: Vec<EndpointConfig>,
+    context: Arc<ConnectionContext>,
+    connections: HashMap<String, Connection>,
+    /// Kept alive for the manager's lifetime when the feed is `Mock`
+    _mock_server: Option<MockServer>,
 }
 
 impl WebSocketManager {
     /// Create new WebSocket manager
     pub async fn new(config: &DataIngestionConfig) -> Result<Self> {
+        let control_rate = config.websocket.control_messages_per_second;
+        if !(control_rate > 0.0 && control_rate.is_finite()) {
+            anyhow::bail!("control_messages_per_second must be positive, got {}", control_rate);
+        }
         let (data_tx, _) = broadcast::channel(config.websocket.buffer_size);
+        let dead_letter = match &config.dead_letter.target {
+            Some(target) => Some(DeadLetterSink::new(target, &config.redis_url)?),
+            None => None,
+        };
+        
+        let mock_server = match &config.websocket.feed {
+            FeedMode::Mock { frames_path, interval_ms } => {
+                let interval = std::time::Duration::from_millis(*interval_ms);
+                let server = MockServer::from_file(frames_path, interval).await?;
+                info!("Replaying {} from mock server at {}", frames_path, server.base_url());
+                Some(server)
+            }
+            _ => None,
+        };
+        let base_url = match &mock_server {
+            Some(server) => server.base_url(),
+            None => config.websocket.base_url().to_string(),
+        };
         
         Ok(Self {
-            config: config.clone(),
-            data_tx,
+            endpoints: config.endpoints(),
+            context: Arc::new(ConnectionContext {
+                api_key: config.polygon_api_key.clone(),
+                base_url,
+                connect_timeout: std::time::Duration::from_secs(config.websocket.connect_timeout),
+                max_reconnect_attempts: config.websocket.max_reconnect_attempts,
+                max_frame_len: config.dead_letter.max_frame_len,
+                subscribe_batch_size: config.websocket.subscribe_batch_size.max(1),
+                subscribe_batch_delay: std::time::Duration::from_millis(
+                    config.websocket.subscribe_batch_delay_ms,
+                ),
+                control_limiter: tokio::sync::Mutex::new(TokenBucket::new(
+                    config.websocket.control_burst,
+                    control_rate,
+                )),
+                close_policy: config.websocket.close_policy.clone(),
+                data_tx,
+                fatal_tx: broadcast::channel(16).0,
+                dead_letter,
+                normalizer: SymbolNormalizer::new(&config.symbol_normalization),
+                timestamp_source: config.timestamp_source,
+            }),
+            connections: HashMap::new(),
+            _mock_server: mock_server,
         })
     }
 
     /// Start WebSocket connections
     pub async fn start(&mut self) -> Result<()> {
-        info!("Starting WebSocket connections");
+        info!("Starting {} WebSocket connection(s)", self.endpoints.len());
         
-        // Connect to Polygon.io WebSocket
-        self.connect_polygon().await?;
+        for endpoint in self.endpoints.clone() {
+            if self.connections.contains_key(&endpoint.name) {
+                continue;
+            }
+            self.spawn_connection(endpoint);
+        }
         
         Ok(())
     }
 
-    /// Connect to Polygon.io WebSocket
-    async fn connect_polygon(&self) -> Result<()> {
-        let url = format!(
-            "wss://socket.polygon.io/stocks?apikey={}",
-            self.config.polygon_api_key
+    /// Drop every connection and connect again
+    pub async fn reconnect(&mut self) -> Result<()> {
+        info!("Reconnecting all WebSocket connections");
+        
+        for (_, connection) in self.connections.drain() {
+            connection.task.abort();
+        }
+        self.start().await
+    }
+
+    /// Drop and reconnect a single endpoint by name
+    pub fn reconnect_endpoint(&mut self, name: &str) -> Result<()> {
+        let connection = self
+            .connections
+            .remove(name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown WebSocket endpoint: {}", name))?;
+        
+        info!(endpoint = name, "Reconnecting WebSocket");
+        connection.task.abort();
+        self.spawn_connection(connection.endpoint);
+        
+        Ok(())
+    }
+
+    fn spawn_connection(&mut self, endpoint: EndpointConfig) {
+        let state = Arc::new(ConnectionState::default());
+        let task = tokio::spawn(Self::supervise(
+            self.context.clone(),
+            endpoint.clone(),
+            state.clone(),
+        ));
+        
+        self.connections.insert(
+            endpoint.name.clone(),
+            Connection { endpoint, state, task },
         );
+    }
+
+    /// Keep one endpoint connected, backing off between failed attempts.
+    ///
+    /// The attempt counter resets whenever a connection was established, so
+    /// `max_reconnect_attempts` only bounds consecutive failures. A rejected
+    /// API key or a close the policy gives up on stops the task, and a
+    /// connection limit waits the longest backoff before trying again.
+    async fn supervise(
+        context: Arc<ConnectionContext>,
+        endpoint: EndpointConfig,
+        state: Arc<ConnectionState>,
+    ) {
+        let mut attempts: u32 = 0;
         
-        let (ws_stream, _) = connect_async(&url).await?;
+        loop {
+            let mut max_connections = false;
+            match Self::run_connection(&context, &endpoint, &state).await {
+                Ok(()) => warn!(endpoint = %endpoint.name, "WebSocket connection closed"),
+                Err(e) => match e.downcast::<ServerError>() {
+                    Ok(e @ (ServerError::AuthFailed(_) | ServerError::Closed { .. })) => {
+                        error!(endpoint = %endpoint.name, "{}; not reconnecting", e);
+                        state.connected.store(false, Ordering::Relaxed);
+                        // No receivers is fine
+                        let _ = context.fatal_tx.send(e);
+                        return;
+                    }
+                    Ok(e @ ServerError::MaxConnections(_)) => {
+                        error!(endpoint = %endpoint.name, "{}", e);
+                        max_connections = true;
+                    }
+                    Err(e) => error!(endpoint = %endpoint.name, "WebSocket error: {:?}", e),
+                },
+            }
+            
+            if state.connected.swap(false, Ordering::Relaxed) {
+                attempts = 0;
+            }
+            attempts += 1;
+            if attempts > context.max_reconnect_attempts {
+                error!(
+                    endpoint = %endpoint.name,
+                    "Giving up after {} reconnect attempts", context.max_reconnect_attempts
+                );
+                return;
+            }
+            
+            let backoff = match max_connections {
+                true => MAX_BACKOFF_SECS,
+                false => (1u64 << attempts.min(6)).min(MAX_BACKOFF_SECS),
+            };
+            info!(endpoint = %endpoint.name, "Reconnecting in {}s (attempt {})", backoff, attempts);
+            tokio::time::sleep(std::time::Duration::from_secs(backoff)).await;
+        }
+    }
+
+    /// Connect, subscribe and forward frames until the connection ends
+    async fn run_connection(
+        context: &ConnectionContext,
+        endpoint: &EndpointConfig,
+        state: &ConnectionState,
+    ) -> Result<()> {
+        let url = endpoint.resolved_url(&context.base_url);
+        let (ws_stream, _) = tokio::time::timeout(context.connect_timeout, connect_async(&url))
+            .await
+            .map_err(|_| anyhow::anyhow!("Timed out connecting to {}", url))??;
         let (mut write, mut read) = ws_stream.split();
+        let limiter = &context.control_limiter;
         
-        // Subscribe to symbols
-        let subscribe_msg = serde_json::json!({
-            "action": "subscribe",
-            "params": format!("T.{}", self.config.symbols.join(",T."))
-        });
-        
-        write.send(Message::Text(subscribe_msg.to_string())).await?;
+        // Authenticate before subscribing; the key never appears in the URL
+        Self::send_control(&mut write, limiter, "auth", &context.api_key).await?;
+        tokio::time::timeout(context.connect_timeout, Self::await_auth(&mut read))
+            .await
+            .map_err(|_| anyhow::anyhow!("Timed out waiting for auth on {}", endpoint.name))??;
+        debug!(endpoint = %endpoint.name, "Authenticated");
         
-        let data_tx = self.data_tx.clone();
+        let channels = endpoint.subscription_channels();
+        Self::subscribe_channels(&mut write, limiter, context, state, &channels).await?;
+        state.connected_at_ms.store(chrono::Utc::now().timestamp_millis(), Ordering::Relaxed);
+        state.connected.store(true, Ordering::Relaxed);
+        info!(endpoint = %endpoint.name, "Subscribed to {} channels", channels.len());
         
         // Handle incoming messages
-        tokio::spawn(async move {
-            while let Some(msg) = read.next().await {
-                match msg {
-                    Ok(Message::Text(text)) => {
-                        if let Ok(market_data) = Self::parse_polygon_message(&text) {
-                            if let Err(_) = data_tx.send(market_data) {
-                                warn!("No subscribers for market data");
+        while let Some(msg) = read.next().await {
+            match msg? {
+                Message::Text(text) => {
+                    let received_at = chrono::Utc::now();
+                    state.last_message_ms.store(received_at.timestamp_millis(), Ordering::Relaxed);
+                    match Self::parse_polygon_message(&text, context.max_frame_len, &context.normalizer) {
+                        Ok(frame) => {
+                            for status in &frame.statuses {
+                                if let Some(e) = ServerError::from_status(status) {
+                                    return Err(e.into());
+                                }
+                                Self::apply_status(&state.subscriptions, &endpoint.name, status);
+                            }
+                            for (event, e) in &frame.rejected {
+                                warn!(endpoint = %endpoint.name, "Skipping unparseable Polygon event: {}", e);
+                                Self::dead_letter(context, event, e).await;
+                            }
+                            for mut market_data in frame.data {
+                                market_data.stamp_ingest_time(received_at, context.timestamp_source);
+                                if context.data_tx.send(market_data).is_err() {
+                                    warn!("No subscribers for market data");
+                                }
                             }
                         }
+                        Err(e) => {
+                            warn!(endpoint = %endpoint.name, "{}", e);
+                            Self::dead_letter(context, &text, &e).await;
+                        }
                     }
-                    Ok(Message::Close(_)) => {
-                        warn!("WebSocket connection closed");
-                        break;
-                    }
-                    Err(e) => {
-                        error!("WebSocket error: {:?}", e);
-                        break;
+                }
+                Message::Close(frame) => {
+                    let Some(frame) = frame else { break };
+                    let code = u16::from(frame.code);
+                    match context.close_policy.action(code, &frame.reason) {
+                        CloseAction::Reconnect => {
+                            info!(
+                                endpoint = %endpoint.name,
+                                "Server closed connection ({}): {}", code, frame.reason
+                            );
+                            break;
+                        }
+                        CloseAction::GiveUp => {
+                            return Err(ServerError::Closed {
+                                code,
+                                reason: frame.reason.to_string(),
+                            }
+                            .into())
+                        }
                     }
-                    _ => {}
                 }
+                _ => {}
             }
-        });
+        }
         
         Ok(())
     }
 
-    /// Parse Polygon.io message
-    fn parse_polygon_message(text: &str) -> Result<MarketData> {
-        // Simplified parser - implement full Polygon.io protocol
-        let value: serde_json::Value = serde_json::from_str(text)?;
-        
-        // This is a simplified implementation
-        // In production, implement full Polygon.io message parsing
-        if let Some(trades) = value.as_array() {
-            for trade in trades {
-                if let Some(event_type) = trade.get("ev").and_then(|v| v.as_str()) {
-                    match event_type {
-                        "T" => {
-                            // Trade message
-                            let trade_data = crate::TradeData {
-                                symbol: trade.get("sym").unwrap().as_str().unwrap().to_string(),
-                                price: trade.get("p").unwrap().as_f64().unwrap(),
-                                size: trade.get("s").unwrap().as_u64().unwrap(),
-                                timestamp: chrono::Utc::now(),
-                                exchange: trade.get("x").unwrap_or(&serde_json::Value::String("UNKNOWN".to_string())).as_str().unwrap().to_string(),
-                                conditions: vec![],
-                            };
-                            return Ok(MarketData::Trade(trade_data));
-                        }
-                        _ => continue,
+    /// Subscribe in batches of `subscribe_batch_size`, pausing between
+    /// batches so large symbol sets don't trip Polygon's limits
+    async fn subscribe_channels<S>(
+        write: &mut S,
+        limiter: &tokio::sync::Mutex<TokenBucket>,
+        context: &ConnectionContext,
+        state: &ConnectionState,
+        channels: &[String],
+    ) -> Result<()>
+    where
+        S: futures_util::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+    {
+        for (i, batch) in channels.chunks(context.subscribe_batch_size).enumerate() {
+            if i > 0 {
+                tokio::time::sleep(context.subscribe_batch_delay).await;
+            }
+            for channel in batch {
+                state.subscriptions.insert(channel.clone(), SubscriptionState::Pending);
+            }
+            let params = batch.join(",");
+            Self::send_control(write, limiter, "subscribe", &params).await?;
+        }
+        
+        Ok(())
+    }
+
+    /// Read status frames until Polygon accepts or rejects the auth message
+    async fn await_auth<S>(read: &mut S) -> Result<()>
+    where
+        S: futures_util::Stream<Item = std::result::Result<Message, tokio_tungstenite::tungstenite::Error>>
+            + Unpin,
+    {
+        while let Some(msg) = read.next().await {
+            let Message::Text(text) = msg? else {
+                continue;
+            };
+            for message in polygon::parse_frame(&text)? {
+                let PolygonMessage::Status(status) = message else {
+                    continue;
+                };
+                if let Some(e) = ServerError::from_status(&status) {
+                    return Err(e.into());
+                }
+                match status.status.as_str() {
+                    "auth_success" => return Ok(()),
+                    _ => debug!("Polygon status {}: {}", status.status, status.message),
+                }
+            }
+        }
+        
+        anyhow::bail!("Connection closed before authentication completed")
+    }
+
+    /// Store a frame or event that failed to parse, if dead-lettering is on
+    async fn dead_letter(context: &ConnectionContext, text: &str, error: &dyn std::fmt::Display) {
+        if let Some(dead_letter) = &context.dead_letter {
+            if let Err(write_err) = dead_letter.record(text, error).await {
+                error!("Failed to store dead-letter frame: {:?}", write_err);
+            }
+        }
+    }
+
+    /// Send a control action once the shared rate limiter allows it
+    async fn send_control<S>(
+        write: &mut S,
+        limiter: &tokio::sync::Mutex<TokenBucket>,
+        action: &str,
+        params: &str,
+    ) -> Result<()>
+    where
+        S: futures_util::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+    {
+        limiter.lock().await.acquire().await;
+        let message = serde_json::json!({ "action": action, "params": params });
+        write.send(Message::Text(message.to_string())).await?;
+        Ok(())
+    }
+
+    /// Parse a Polygon.io frame into market data stamped with exchange time.
+    ///
+    /// Frames are JSON arrays of events. The typed fast path handles
+    /// well-formed frames; otherwise events are parsed one by one and
+    /// malformed ones are returned for dead-lettering. Status events are returned separately,
+    /// and data symbols are mapped to canonical form.
+    fn parse_polygon_message(
+        text: &str,
+        max_frame_len: usize,
+        normalizer: &SymbolNormalizer,
+    ) -> std::result::Result<ParsedFrame, FrameParseError> {
+        #[cfg(feature = "simd")]
+        let parsed = polygon::parse_frame_simd(&mut text.as_bytes().to_vec());
+        #[cfg(not(feature = "simd"))]
+        let parsed = polygon::parse_frame(text);
+        
+        let mut frame = ParsedFrame::default();
+        let messages = match parsed {
+            Ok(messages) => messages,
+            Err(_) => {
+                let events = polygon::parse_events::<serde_json::Map<String, serde_json::Value>>(text)
+                    .map_err(|source| FrameParseError::new(text, max_frame_len, source))?;
+                
+                let mut messages = Vec::new();
+                for event in events {
+                    let event = serde_json::Value::Object(event);
+                    match PolygonMessage::deserialize(&event) {
+                        Ok(message) => messages.push(message),
+                        Err(e) => frame.rejected.push((event.to_string(), e)),
                     }
                 }
+                messages
+            }
+        };
+        
+        for message in messages {
+            match message {
+                PolygonMessage::Status(status) => frame.statuses.push(status),
+                message => frame.data.extend(message.into_market_data().map(|mut data| {
+                    normalizer.apply(&mut data);
+                    data
+                })),
             }
         }
         
-        Err(anyhow::anyhow!("Unable to parse message"))
+        Ok(frame)
+    }
+
+    /// Update subscription state from a status event.
+    ///
+    /// Polygon reports each subscription as e.g. `subscribed to: T.AAPL`;
+    /// any other status naming a tracked channel marks it failed.
+    fn apply_status(
+        subscriptions: &DashMap<String, SubscriptionState>,
+        endpoint: &str,
+        status: &PolygonStatus,
+    ) {
+        let Some((_, channel)) = status.message.rsplit_once(": ") else {
+            debug!("Polygon status {}: {}", status.status, status.message);
+            return;
+        };
+        let channel = channel.trim();
+        let Some(mut state) = subscriptions.get_mut(channel) else {
+            debug!("Polygon status {}: {}", status.status, status.message);
+            return;
+        };
+        
+        if status.status == "success" {
+            if status.message.starts_with("unsubscribed") {
+                drop(state);
+                subscriptions.remove(channel);
+            } else {
+                *state = SubscriptionState::Confirmed;
+            }
+        } else {
+            error!(endpoint, "Subscription to {} rejected: {}", channel, status.message);
+            *state = SubscriptionState::Failed(status.message.clone());
+        }
     }
 
     /// Subscribe to market data stream
     pub fn subscribe(&self) -> broadcast::Receiver<MarketData> {
-        self.data_tx.subscribe()
+        self.context.data_tx.subscribe()
+    }
+
+    /// Errors that stopped a connection for good, such as a rejected API key
+    pub fn subscribe_fatal(&self) -> broadcast::Receiver<ServerError> {
+        self.context.fatal_tx.subscribe()
+    }
+
+    /// Whether every endpoint connection is currently open
+    pub fn is_connected(&self) -> bool {
+        !self.connections.is_empty()
+            && self
+                .connections
+                .values()
+                .all(|connection| connection.state.connected.load(Ordering::Relaxed))
+    }
+
+    /// Open/closed state of each endpoint connection by name
+    pub fn connection_status(&self) -> HashMap<String, bool> {
+        self.connections
+            .iter()
+            .map(|(name, connection)| (name.clone(), connection.state.connected.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    /// Acknowledgement state of every subscription, by channel (e.g. `T.AAPL`)
+    pub fn subscription_status(&self) -> HashMap<String, SubscriptionState> {
+        self.connections
+            .values()
+            .flat_map(|connection| {
+                connection
+                    .state
+                    .subscriptions
+                    .iter()
+                    .map(|entry| (entry.key().clone(), entry.value().clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Channels whose subscription Polygon rejected, with the reason
+    pub fn failed_subscriptions(&self) -> Vec<(String, String)> {
+        self.subscription_status()
+            .into_iter()
+            .filter_map(|(channel, state)| match state {
+                SubscriptionState::Failed(reason) => Some((channel, reason)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Time the last message was received on any connection, if any
+    pub fn last_message_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        match self
+            .connections
+            .values()
+            .map(|connection| connection.state.last_message_ms.load(Ordering::Relaxed))
+            .max()
+            .unwrap_or(0)
+        {
+            0 => None,
+            ms => chrono::DateTime::from_timestamp_millis(ms),
+        }
+    }
+
+    /// Time since each connection's last message, or since it connected if
+    /// none has arrived, with the endpoint's name and cluster. Connections
+    /// still connecting are left out.
+    pub fn idle_endpoints(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<(String, PolygonCluster, chrono::Duration)> {
+        self.connections
+            .iter()
+            .filter_map(|(name, connection)| {
+                let last_activity_ms = connection.state.last_activity_ms();
+                if last_activity_ms == 0 {
+                    return None;
+                }
+                let last_activity = chrono::DateTime::from_timestamp_millis(last_activity_ms)?;
+                Some((name.clone(), connection.endpoint.cluster, now.signed_duration_since(last_activity)))
+            })
+            .collect()
+    }
+}
+
+impl Drop for WebSocketManager {
+    fn drop(&mut self) {
+        for connection in self.connections.values() {
+            connection.task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServerConfig;
+
+    #[test]
+    fn test_parse_error_carries_truncated_frame() {
+        let err = WebSocketManager::parse_polygon_message(
+            r#"[{"ev":"T","sym":"AAPL""#,
+            8,
+            &SymbolNormalizer::default(),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.frame, r#"[{"ev":"... (23 bytes)"#);
+    }
+
+    #[test]
+    fn test_single_object_frames_are_parsed() {
+        let frame = WebSocketManager::parse_polygon_message(
+            r#"{"ev":"status","status":"auth_success","message":"authenticated"}"#,
+            512,
+            &SymbolNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(frame.statuses.len(), 1);
+        assert_eq!(frame.statuses[0].status, "auth_success");
+
+        // A malformed lone event is rejected like one inside an array
+        let frame = WebSocketManager::parse_polygon_message(
+            r#"{"ev":"T","sym":"AAPL"}"#,
+            512,
+            &SymbolNormalizer::default(),
+        )
+        .unwrap();
+        assert!(frame.data.is_empty() && frame.statuses.is_empty());
+        assert_eq!(frame.rejected.len(), 1);
+
+        assert!(WebSocketManager::parse_polygon_message("42", 512, &SymbolNormalizer::default()).is_err());
+    }
+
+    #[test]
+    fn test_malformed_events_are_kept_for_dead_letter() {
+        let frame = WebSocketManager::parse_polygon_message(
+            r#"[{"ev":"T","sym":"AAPL","x":4,"p":190.5,"s":100,"t":1700000000000},{"ev":"T","sym":"MSFT"}]"#,
+            512,
+            &SymbolNormalizer::default(),
+        )
+        .unwrap();
+
+        assert_eq!(frame.data.len(), 1);
+        assert_eq!(frame.rejected.len(), 1);
+        assert!(frame.rejected[0].0.contains(r#""sym":"MSFT""#));
+    }
+
+    #[test]
+    fn test_status_events_update_subscriptions() {
+        let subscriptions = DashMap::new();
+        subscriptions.insert("T.AAPL".to_string(), SubscriptionState::Pending);
+        subscriptions.insert("T.ZZZZ".to_string(), SubscriptionState::Pending);
+
+        let frame = WebSocketManager::parse_polygon_message(
+            r#"[{"ev":"status","status":"success","message":"subscribed to: T.AAPL"},
+                {"ev":"status","status":"error","message":"not authorized to subscribe to: T.ZZZZ"}]"#,
+            512,
+            &SymbolNormalizer::default(),
+        )
+        .unwrap();
+        for status in &frame.statuses {
+            WebSocketManager::apply_status(&subscriptions, "stocks", status);
+        }
+
+        assert_eq!(*subscriptions.get("T.AAPL").unwrap(), SubscriptionState::Confirmed);
+        assert!(matches!(*subscriptions.get("T.ZZZZ").unwrap(), SubscriptionState::Failed(_)));
+    }
+
+    #[test]
+    fn test_parsed_symbols_are_normalized() {
+        let normalizer = SymbolNormalizer::new(&crate::config::SymbolNormalizationConfig {
+            aliases: HashMap::from([("BRK-B".to_string(), "BRK.B".to_string())]),
+            strip_prefixes: vec![],
+        });
+
+        let frame = WebSocketManager::parse_polygon_message(
+            r#"[{"ev":"T","sym":"BRK-B","x":4,"p":412.5,"s":10,"c":[],"t":1700000000000}]"#,
+            512,
+            &normalizer,
+        )
+        .unwrap();
+
+        let MarketData::Trade(trade) = &frame.data[0] else { panic!("expected a trade") };
+        assert_eq!(trade.symbol, "BRK.B");
+        assert_eq!(trade.original_symbol.as_deref(), Some("BRK-B"));
     }
-}
\ No newline at end of file
+
+    fn frames(texts: &[&str]) -> impl futures_util::Stream<
+        Item = std::result::Result<Message, tokio_tungstenite::tungstenite::Error>,
+    > + Unpin {
+        futures_util::stream::iter(
+            texts
+                .iter()
+                .map(|text| Ok(Message::Text(text.to_string())))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_auth_waits_for_success_status() {
+        let mut read = frames(&[
+            r#"[{"ev":"status","status":"connected","message":"Connected Successfully"}]"#,
+            r#"[{"ev":"status","status":"auth_success","message":"authenticated"}]"#,
+        ]);
+
+        assert!(WebSocketManager::await_auth(&mut read).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_auth_accepts_single_object_status() {
+        let mut read = frames(&[
+            r#"{"ev":"status","status":"connected","message":"Connected Successfully"}"#,
+            r#"{"ev":"status","status":"auth_success","message":"authenticated"}"#,
+        ]);
+
+        assert!(WebSocketManager::await_auth(&mut read).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_auth_failure_is_an_error() {
+        let mut read = frames(&[
+            r#"[{"ev":"status","status":"auth_failed","message":"authentication failed"}]"#,
+        ]);
+
+        let err = WebSocketManager::await_auth(&mut read).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ServerError>(),
+            Some(&ServerError::AuthFailed("authentication failed".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_close_policy_defaults() {
+        let policy = ClosePolicyConfig::default();
+
+        assert_eq!(policy.action(1012, "service restart"), CloseAction::Reconnect);
+        assert_eq!(policy.action(1006, ""), CloseAction::Reconnect);
+        assert_eq!(policy.action(1008, "policy violation"), CloseAction::GiveUp);
+        // Reasons take precedence over codes
+        assert_eq!(policy.action(1000, "Authentication Failed"), CloseAction::GiveUp);
+    }
+
+    #[tokio::test]
+    async fn test_max_connections_is_reported_during_auth() {
+        let mut read = frames(&[
+            r#"[{"ev":"status","status":"connected","message":"Connected Successfully"}]"#,
+            r#"[{"ev":"status","status":"max_connections","message":"Maximum number of websocket connections exceeded."}]"#,
+        ]);
+
+        let err = WebSocketManager::await_auth(&mut read).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ServerError>(), Some(ServerError::MaxConnections(_))));
+    }
+
+    fn mock_config(server: &MockServer) -> DataIngestionConfig {
+        let mut config = DataIngestionConfig::default();
+        config.symbols = vec!["AAPL".to_string()];
+        config.websocket.base_url = Some(server.base_url());
+        config.websocket.connect_timeout = 5;
+        config
+    }
+
+    fn session_frames() -> Vec<String> {
+        let now = chrono::Utc::now().timestamp_millis();
+        vec![
+            format!(r#"[{{"ev":"T","sym":"AAPL","x":4,"p":190.5,"s":100,"c":[],"t":{now}}}]"#),
+            format!(
+                r#"[{{"ev":"Q","sym":"AAPL","bx":4,"bp":190.4,"bs":200,"ax":7,"ap":190.6,"as":300,"t":{now}}}]"#
+            ),
+            format!(
+                r#"[{{"ev":"A","sym":"AAPL","o":190.1,"h":190.9,"l":190.0,"c":190.5,"v":1200,"s":{},"e":{now}}}]"#,
+                now - 1000
+            ),
+        ]
+    }
+
+    async fn recv(rx: &mut broadcast::Receiver<MarketData>) -> MarketData {
+        tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv())
+            .await
+            .expect("timed out waiting for market data")
+            .expect("market data channel closed")
+    }
+
+    #[tokio::test]
+    async fn test_mock_feed_delivers_validated_data() {
+        let server = MockServer::start(MockServerConfig {
+            frames: session_frames(),
+            interval: std::time::Duration::ZERO,
+            close_after_replay: false,
+        })
+        .await
+        .unwrap();
+        let mut config = mock_config(&server);
+        config.websocket.endpoints = vec![EndpointConfig {
+            name: "stocks".to_string(),
+            cluster: polygon::PolygonCluster::Stocks,
+            url: None,
+            channels: vec!["T".to_string(), "Q".to_string(), "A".to_string()],
+            symbols: vec!["AAPL".to_string()],
+        }];
+        let mut manager = WebSocketManager::new(&config).await.unwrap();
+        let mut rx = manager.subscribe();
+        manager.start().await.unwrap();
+
+        let trade = recv(&mut rx).await;
+        let quote = recv(&mut rx).await;
+        let aggregate = recv(&mut rx).await;
+
+        assert!(matches!(&trade, MarketData::Trade(t) if t.symbol == "AAPL" && t.size == 100));
+        let MarketData::Quote(q) = &quote else { panic!("expected a quote") };
+        assert_eq!(q.symbol, "AAPL");
+        assert_eq!((q.bid_price, q.ask_price), (crate::price::from_f64(190.4), crate::price::from_f64(190.6)));
+        assert_eq!((q.bid_size, q.ask_size), (200, 300));
+        assert_eq!((q.bid_exchange.as_str(), q.ask_exchange.as_str()), ("NASDAQ_OMX_BX", "CBOE_EDGA"));
+        assert!(matches!(&aggregate, MarketData::Aggregate(a) if a.volume == 1200));
+        for data in [&trade, &quote, &aggregate] {
+            crate::validation::validate_market_data(data, &config.validation).unwrap();
+        }
+        assert!(manager.is_connected());
+        let status = manager.subscription_status();
+        for channel in ["T.AAPL", "Q.AAPL", "A.AAPL"] {
+            assert_eq!(status.get(channel), Some(&SubscriptionState::Confirmed), "{}", channel);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_server_drops_connection() {
+        let server = MockServer::start(MockServerConfig {
+            frames: session_frames()[..1].to_vec(),
+            interval: std::time::Duration::ZERO,
+            close_after_replay: true,
+        })
+        .await
+        .unwrap();
+        let mut manager = WebSocketManager::new(&mock_config(&server)).await.unwrap();
+        let mut rx = manager.subscribe();
+        manager.start().await.unwrap();
+
+        assert!(matches!(recv(&mut rx).await, MarketData::Trade(_)));
+        // Replayed again on the new connection after backoff
+        assert!(matches!(recv(&mut rx).await, MarketData::Trade(_)));
+        assert!(server.connection_count() >= 2);
+    }
+
+    #[tokio::test]
+    async fn test_non_positive_control_rate_is_rejected() {
+        for rate in [0.0, -1.0, f64::NAN] {
+            let mut config = DataIngestionConfig::default();
+            config.websocket.control_messages_per_second = rate;
+
+            let Err(err) = WebSocketManager::new(&config).await else {
+                panic!("rate {} was accepted", rate);
+            };
+            assert!(err.to_string().contains("control_messages_per_second must be positive"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_control_limiter_is_shared_across_reconnects() {
+        let server = MockServer::start(MockServerConfig {
+            frames: session_frames()[..1].to_vec(),
+            interval: std::time::Duration::ZERO,
+            close_after_replay: true,
+        })
+        .await
+        .unwrap();
+        let mut config = mock_config(&server);
+        config.websocket.control_burst = 10;
+        config.websocket.control_messages_per_second = 0.001;
+        let mut manager = WebSocketManager::new(&config).await.unwrap();
+        let mut rx = manager.subscribe();
+        manager.start().await.unwrap();
+
+        recv(&mut rx).await;
+        recv(&mut rx).await;
+
+        // Auth and subscribe on both connections drew from the same burst
+        let mut limiter = manager.context.control_limiter.lock().await;
+        let now = std::time::Instant::now();
+        let remaining = (0..10).take_while(|_| limiter.try_acquire_at(now).is_ok()).count();
+        assert!(remaining <= 6, "{} tokens left", remaining);
+    }
+}
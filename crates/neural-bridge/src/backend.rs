@@ -0,0 +1,155 @@
+//! Inference backends
+//!
+//! `NeuralForecastClient` delegates model loading and inference to an
+//! `InferenceBackend`. The Python backend drives NeuralForecast through
+//! PyO3; the ONNX backend (feature `onnx`) runs exported models through
+//! ONNX Runtime without a Python interpreter or the GIL. `MockBackend`
+//! serves deterministic predictions for tests.
+
+use crate::{
+    config::ModelConfig, error::Result, models::ModelStats, PredictionInput, PredictionResult,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub mod mock;
+#[cfg(feature = "onnx")]
+pub mod onnx;
+pub mod python;
+
+pub use mock::MockBackend;
+#[cfg(feature = "onnx")]
+pub use onnx::OnnxBackend;
+pub use python::PythonBackend;
+
+/// Backend selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BackendKind {
+    #[default]
+    Python,
+    Onnx,
+    /// Pure-Rust baselines from `crate::baselines`
+    Baseline,
+}
+
+/// Model loading and inference backend
+pub trait InferenceBackend: Send + Sync {
+    /// Backend name for logs and metadata
+    fn name(&self) -> &'static str;
+
+    /// Device inference runs on, for metadata
+    fn device(&self) -> &'static str {
+        "cpu"
+    }
+
+    /// Prepare the runtime (import modules, create environments)
+    fn initialize(&self) -> Result<()>;
+
+    /// Load a model so it can serve predictions
+    fn load(&self, model_name: &str, config: &ModelConfig) -> Result<()>;
+
+    /// Generate a prediction with a loaded model
+    fn predict(&self, model_name: &str, input: &PredictionInput) -> Result<PredictionResult>;
+
+    /// Generate a prediction, passing each newly produced block of steps to
+    /// `emit` as it becomes available. Backends without autoregressive
+    /// stepping emit the whole forecast once.
+    fn predict_streaming(
+        &self,
+        model_name: &str,
+        input: &PredictionInput,
+        emit: &mut dyn FnMut(&[f64]),
+    ) -> Result<PredictionResult> {
+        let result = self.predict(model_name, input)?;
+        emit(&result.prediction);
+        Ok(result)
+    }
+
+    /// Performance statistics for a model
+    fn stats(&self, model_name: &str) -> Result<ModelStats>;
+
+    /// Whether the runtime is ready to serve predictions
+    fn health(&self) -> Result<bool>;
+}
+
+/// A shared backend, e.g. one kept by the caller to inspect afterwards
+impl<B: InferenceBackend + ?Sized> InferenceBackend for Arc<B> {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn device(&self) -> &'static str {
+        (**self).device()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        (**self).initialize()
+    }
+
+    fn load(&self, model_name: &str, config: &ModelConfig) -> Result<()> {
+        (**self).load(model_name, config)
+    }
+
+    fn predict(&self, model_name: &str, input: &PredictionInput) -> Result<PredictionResult> {
+        (**self).predict(model_name, input)
+    }
+
+    fn predict_streaming(
+        &self,
+        model_name: &str,
+        input: &PredictionInput,
+        emit: &mut dyn FnMut(&[f64]),
+    ) -> Result<PredictionResult> {
+        (**self).predict_streaming(model_name, input, emit)
+    }
+
+    fn stats(&self, model_name: &str) -> Result<ModelStats> {
+        (**self).stats(model_name)
+    }
+
+    fn health(&self) -> Result<bool> {
+        (**self).health()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NeuralBridgeConfig;
+    use crate::neuralforecast::NeuralForecastClient;
+    use crate::test_support::aapl_input;
+
+    #[test]
+    fn test_client_uses_configured_backend() {
+        let mut config = NeuralBridgeConfig::default();
+        config.neuralforecast.backend = BackendKind::Baseline;
+        assert_eq!(NeuralForecastClient::new(&config).unwrap().backend_name(), "baseline");
+
+        #[cfg(not(feature = "onnx"))]
+        {
+            config.neuralforecast.backend = BackendKind::Onnx;
+            assert!(matches!(
+                NeuralForecastClient::new(&config),
+                Err(crate::NeuralBridgeError::Backend(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_default_streaming_emits_whole_forecast_once_through_arc() {
+        let backend = Arc::new(crate::baselines::BaselineBackend::new());
+        let mut model = NeuralBridgeConfig::default().neuralforecast.models["NBEATS"].clone();
+        model.model_type = "Naive".to_string();
+        backend.load("NAIVE", &model).unwrap();
+
+        let mut blocks = Vec::new();
+        let result = backend
+            .predict_streaming("NAIVE", &aapl_input(30, 4), &mut |block| blocks.push(block.to_vec()))
+            .unwrap();
+
+        assert_eq!(backend.name(), "baseline");
+        assert_eq!(backend.device(), "cpu");
+        assert_eq!(blocks, vec![result.prediction]);
+        assert_eq!(blocks[0].len(), 4);
+    }
+}
@@ -0,0 +1,197 @@
+//! Deterministic backend for tests
+
+use super::InferenceBackend;
+use crate::{
+    config::ModelConfig,
+    error::{NeuralBridgeError, Result},
+    models::ModelStats,
+    PredictionInput, PredictionResult,
+};
+use parking_lot::RwLock;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Backend returning the last observed value for every step, streamed one
+/// step at a time.
+///
+/// Needs no Python runtime, so cache, selection and batching logic can be
+/// exercised in isolation.
+pub struct MockBackend {
+    loaded: RwLock<HashSet<String>>,
+    confidence: f64,
+    available: AtomicBool,
+    predictions: AtomicU64,
+    latency: Duration,
+    in_flight: AtomicUsize,
+    peak_in_flight: AtomicUsize,
+}
+
+impl MockBackend {
+    /// Create a mock backend reporting the given confidence
+    pub fn new(confidence: f64) -> Self {
+        Self {
+            loaded: RwLock::new(HashSet::new()),
+            confidence,
+            available: AtomicBool::new(true),
+            predictions: AtomicU64::new(0),
+            latency: Duration::ZERO,
+            in_flight: AtomicUsize::new(0),
+            peak_in_flight: AtomicUsize::new(0),
+        }
+    }
+
+    /// Block each prediction for `latency`, to observe concurrency
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Create a mock backend whose runtime fails to initialize
+    pub fn unavailable() -> Self {
+        Self {
+            available: AtomicBool::new(false),
+            ..Self::default()
+        }
+    }
+
+    /// Make the runtime come up or go down, e.g. to simulate recovery
+    pub fn set_available(&self, available: bool) {
+        self.available.store(available, Ordering::Relaxed);
+    }
+
+    /// Number of predictions served so far
+    pub fn prediction_count(&self) -> u64 {
+        self.predictions.load(Ordering::Relaxed)
+    }
+
+    /// Most predictions that have run at the same time
+    pub fn peak_concurrency(&self) -> usize {
+        self.peak_in_flight.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new(0.9)
+    }
+}
+
+impl InferenceBackend for MockBackend {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn initialize(&self) -> Result<()> {
+        if !self.available.load(Ordering::Relaxed) {
+            return Err(NeuralBridgeError::PythonInitFailed("mock backend unavailable".to_string()));
+        }
+        Ok(())
+    }
+
+    fn load(&self, model_name: &str, _config: &ModelConfig) -> Result<()> {
+        self.loaded.write().insert(model_name.to_string());
+        Ok(())
+    }
+
+    fn predict(&self, model_name: &str, input: &PredictionInput) -> Result<PredictionResult> {
+        if !self.loaded.read().contains(model_name) {
+            return Err(NeuralBridgeError::ModelNotLoaded(model_name.to_string()));
+        }
+        self.predictions.fetch_add(1, Ordering::Relaxed);
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        std::thread::sleep(self.latency);
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        let last = input.historical_data.last().copied().unwrap_or_default();
+        Ok(PredictionResult {
+            model_name: model_name.to_string(),
+            symbol: input.symbol.clone(),
+            prediction: vec![last; input.horizon],
+            confidence: self.confidence,
+            timestamp: chrono::Utc::now(),
+            horizon: input.horizon,
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn predict_streaming(
+        &self,
+        model_name: &str,
+        input: &PredictionInput,
+        emit: &mut dyn FnMut(&[f64]),
+    ) -> Result<PredictionResult> {
+        let result = self.predict(model_name, input)?;
+        for step in result.prediction.chunks(1) {
+            emit(step);
+        }
+        Ok(result)
+    }
+
+    fn stats(&self, model_name: &str) -> Result<ModelStats> {
+        Ok(ModelStats {
+            model_name: model_name.to_string(),
+            accuracy: self.confidence,
+            average_inference_time_ms: 0.0,
+            total_predictions: self.prediction_count(),
+            successful_predictions: self.prediction_count(),
+            failed_predictions: 0,
+            last_used: chrono::Utc::now(),
+            memory_usage_mb: 0.0,
+            outcomes_recorded: 0,
+            directional_accuracy: 0.0,
+        })
+    }
+
+    fn health(&self) -> Result<bool> {
+        Ok(self.available.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NeuralBridgeConfig;
+    use crate::neuralforecast::NeuralForecastClient;
+
+    fn input(horizon: usize) -> PredictionInput {
+        PredictionInput {
+            symbol: "AAPL".to_string(),
+            historical_data: vec![100.0, 101.0, 102.0],
+            timestamps: vec![],
+            features: HashMap::new(),
+            horizon,
+            seed: None,
+            resampling: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_delegates_to_backend() {
+        let config = NeuralBridgeConfig::default();
+        let mut client = NeuralForecastClient::with_backend(&config, Box::new(MockBackend::default()));
+
+        assert!(matches!(
+            client.predict(&input(3), "NBEATS").await,
+            Err(NeuralBridgeError::ModelNotLoaded(_))
+        ));
+
+        client.load_model("NBEATS").await.unwrap();
+        let result = client.predict(&input(3), "NBEATS").await.unwrap();
+
+        assert_eq!(result.prediction, vec![102.0; 3]);
+        assert_eq!(client.backend_name(), "mock");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_model_not_found() {
+        let config = NeuralBridgeConfig::default();
+        let mut client = NeuralForecastClient::with_backend(&config, Box::new(MockBackend::default()));
+
+        assert!(matches!(
+            client.load_model("ARIMA").await,
+            Err(NeuralBridgeError::ModelNotFound(_))
+        ));
+    }
+}
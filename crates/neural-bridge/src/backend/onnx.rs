@@ -0,0 +1,178 @@
+//! ONNX Runtime backend for exported models
+
+use super::InferenceBackend;
+use crate::{
+    config::ModelConfig,
+    error::{NeuralBridgeError, Result},
+    models::ModelStats,
+    PredictionInput, PredictionResult,
+};
+use ndarray::{Array2, CowArray};
+use ort::{Environment, GraphOptimizationLevel, Session, SessionBuilder, Value};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
+
+/// Loaded ONNX session and the input window it expects
+struct OnnxModel {
+    session: Session,
+    input_size: Option<usize>,
+    accuracy: f64,
+}
+
+/// ONNX backend running models exported from NeuralForecast.
+///
+/// Models take a `[1, input_size]` f32 tensor of recent values and return
+/// at least `horizon` forecast values in their first output.
+pub struct OnnxBackend {
+    environment: RwLock<Option<Arc<Environment>>>,
+    models: RwLock<HashMap<String, OnnxModel>>,
+    intra_threads: i16,
+}
+
+impl OnnxBackend {
+    /// Create an ONNX backend using `intra_threads` threads per session
+    pub fn new(intra_threads: usize) -> Self {
+        Self {
+            environment: RwLock::new(None),
+            models: RwLock::new(HashMap::new()),
+            intra_threads: intra_threads.clamp(1, i16::MAX as usize) as i16,
+        }
+    }
+}
+
+fn onnx_error(e: impl std::fmt::Display) -> NeuralBridgeError {
+    NeuralBridgeError::Backend(e.to_string())
+}
+
+impl InferenceBackend for OnnxBackend {
+    fn name(&self) -> &'static str {
+        "onnx"
+    }
+
+    fn initialize(&self) -> Result<()> {
+        info!("Initializing ONNX Runtime environment");
+        
+        let environment = Environment::builder()
+            .with_name("neural-bridge")
+            .build()
+            .map_err(onnx_error)?
+            .into_arc();
+        *self.environment.write() = Some(environment);
+        
+        Ok(())
+    }
+
+    fn load(&self, model_name: &str, model_config: &ModelConfig) -> Result<()> {
+        let environment = self.environment.read().clone().ok_or_else(|| {
+            NeuralBridgeError::Backend("ONNX Runtime not initialized".to_string())
+        })?;
+        
+        let session = SessionBuilder::new(&environment)
+            .and_then(|builder| builder.with_optimization_level(GraphOptimizationLevel::Level3))
+            .and_then(|builder| builder.with_intra_threads(self.intra_threads))
+            .and_then(|builder| builder.with_model_from_file(&model_config.model_path))
+            .map_err(onnx_error)?;
+        
+        let input_size = model_config
+            .parameters
+            .get("input_size")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize);
+        
+        self.models.write().insert(
+            model_name.to_string(),
+            OnnxModel {
+                session,
+                input_size,
+                accuracy: model_config.accuracy,
+            },
+        );
+        
+        info!("Loaded ONNX model {} from {}", model_name, model_config.model_path);
+        Ok(())
+    }
+
+    fn predict(&self, model_name: &str, input: &PredictionInput) -> Result<PredictionResult> {
+        let models = self.models.read();
+        let model = models
+            .get(model_name)
+            .ok_or_else(|| NeuralBridgeError::ModelNotLoaded(model_name.to_string()))?;
+        
+        // Feed the most recent `input_size` points (or the whole series)
+        let window_len = model
+            .input_size
+            .unwrap_or(input.historical_data.len())
+            .min(input.historical_data.len());
+        let window: Vec<f32> = input.historical_data[input.historical_data.len() - window_len..]
+            .iter()
+            .map(|&v| v as f32)
+            .collect();
+        
+        let array = CowArray::from(
+            Array2::from_shape_vec((1, window_len), window)
+                .map_err(onnx_error)?
+                .into_dyn(),
+        );
+        let inputs = vec![Value::from_array(model.session.allocator(), &array).map_err(onnx_error)?];
+        let outputs = model.session.run(inputs).map_err(onnx_error)?;
+        
+        let output = outputs
+            .first()
+            .ok_or_else(|| NeuralBridgeError::Backend("ONNX model produced no outputs".to_string()))?
+            .try_extract::<f32>()
+            .map_err(onnx_error)?;
+        let prediction: Vec<f64> = output
+            .view()
+            .iter()
+            .take(input.horizon)
+            .map(|&v| v as f64)
+            .collect();
+        
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "input_length".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(window_len)),
+        );
+        metadata.insert(
+            "model_type".to_string(),
+            serde_json::Value::String(model_name.to_string()),
+        );
+        
+        Ok(PredictionResult {
+            model_name: model_name.to_string(),
+            symbol: input.symbol.clone(),
+            prediction,
+            confidence: model.accuracy,
+            timestamp: chrono::Utc::now(),
+            horizon: input.horizon,
+            metadata,
+        })
+    }
+
+    fn stats(&self, model_name: &str) -> Result<ModelStats> {
+        let models = self.models.read();
+        let model = models
+            .get(model_name)
+            .ok_or_else(|| NeuralBridgeError::ModelNotLoaded(model_name.to_string()))?;
+        
+        // Sessions keep no history; live stats come from the performance tracker
+        Ok(ModelStats {
+            model_name: model_name.to_string(),
+            accuracy: model.accuracy,
+            average_inference_time_ms: 0.0,
+            total_predictions: 0,
+            successful_predictions: 0,
+            failed_predictions: 0,
+            last_used: chrono::Utc::now(),
+            memory_usage_mb: 0.0,
+            outcomes_recorded: 0,
+            directional_accuracy: 0.0,
+        })
+    }
+
+    fn health(&self) -> Result<bool> {
+        Ok(self.environment.read().is_some())
+    }
+}
@@ -0,0 +1,391 @@
+//! NeuralForecast backend via PyO3
+
+use super::InferenceBackend;
+use crate::{
+    config::ModelConfig,
+    error::{NeuralBridgeError, Result},
+    models::ModelStats,
+    prediction::PredictionValidator,
+    transform::{PaddingStrategy, Scaler, ScalerConfig},
+    PredictionInput, PredictionResult,
+};
+use parking_lot::RwLock;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::info;
+
+/// Python backend holding the NeuralForecast module and loaded models
+pub struct PythonBackend {
+    python_module: RwLock<Option<PyObject>>,
+    models: RwLock<HashMap<String, PyObject>>,
+    scalers: RwLock<HashMap<String, ScalerConfig>>,
+    /// Padding strategy and target length per model
+    paddings: RwLock<HashMap<String, (PaddingStrategy, usize)>>,
+    /// Whether Torch found a CUDA device at initialization
+    cuda: AtomicBool,
+}
+
+impl PythonBackend {
+    /// Create an uninitialized Python backend
+    pub fn new() -> Self {
+        pyo3::prepare_freethreaded_python();
+        
+        Self {
+            python_module: RwLock::new(None),
+            models: RwLock::new(HashMap::new()),
+            scalers: RwLock::new(HashMap::new()),
+            paddings: RwLock::new(HashMap::new()),
+            cuda: AtomicBool::new(false),
+        }
+    }
+
+    /// Convert Rust input to a NeuralForecast long-format DataFrame with
+    /// `unique_id`, `ds` and `y` columns plus one column per exogenous feature.
+    ///
+    /// `y` is scaled when a scaler is given. Inputs shorter than the padding
+    /// target are left-padded when a strategy is given.
+    fn convert_input_to_python(
+        &self,
+        py: Python,
+        input: &PredictionInput,
+        scaler: Option<&Scaler>,
+        padding: Option<(PaddingStrategy, usize)>,
+    ) -> Result<PyObject> {
+        PredictionValidator::validate_alignment(input)?;
+        let padded;
+        let input = match padding {
+            Some((strategy, target)) => {
+                padded = strategy.pad_input(input, target);
+                &padded
+            }
+            None => input,
+        };
+        
+        let pandas = py.import("pandas")?;
+        let columns = PyDict::new(py);
+        columns.set_item("unique_id", vec![input.symbol.as_str(); input.historical_data.len()])?;
+        
+        // NeuralForecast expects naive datetimes, so normalize to UTC first
+        let timestamps: Vec<String> = input.timestamps.iter().map(|ts| ts.to_rfc3339()).collect();
+        let ds_kwargs = PyDict::new(py);
+        ds_kwargs.set_item("utc", true)?;
+        let ds = pandas
+            .call_method("to_datetime", (timestamps,), Some(ds_kwargs))?
+            .call_method1("tz_localize", (py.None(),))?;
+        columns.set_item("ds", ds)?;
+        let y = match scaler {
+            Some(scaler) => scaler.transform(&input.historical_data),
+            None => input.historical_data.clone(),
+        };
+        columns.set_item("y", y)?;
+        
+        let mut feature_names: Vec<&String> = input.features.keys().collect();
+        feature_names.sort();
+        for name in feature_names {
+            columns.set_item(name.as_str(), input.features[name.as_str()].clone())?;
+        }
+        
+        let frame = pandas.call_method1("DataFrame", (columns,))?;
+        Ok(frame.into())
+    }
+
+    /// Convert a NeuralForecast prediction DataFrame to Rust format
+    fn convert_prediction_from_python(
+        &self,
+        py: Python,
+        prediction: PyObject,
+        input: &PredictionInput,
+        model_name: &str,
+        scaler: Option<&Scaler>,
+    ) -> Result<PredictionResult> {
+        // Older NeuralForecast releases return `unique_id` as the index
+        let frame = prediction.as_ref(py).call_method0("reset_index")?;
+        let columns: Vec<String> = frame
+            .getattr("columns")?
+            .call_method0("tolist")?
+            .extract()?;
+        
+        let value_column = columns
+            .iter()
+            .find(|column| column.as_str() == model_name)
+            .or_else(|| columns.iter().find(|column| is_point_forecast_column(column)))
+            .ok_or_else(|| {
+                NeuralBridgeError::Backend(format!(
+                    "No forecast column in NeuralForecast output: {:?}",
+                    columns
+                ))
+            })?;
+        
+        let mut values: Vec<f64> = frame
+            .get_item(value_column.as_str())?
+            .call_method0("tolist")?
+            .extract()?;
+        values.truncate(input.horizon);
+        if let Some(scaler) = scaler {
+            values = scaler.inverse(&values);
+        }
+        
+        let mut forecast_timestamps: Vec<String> = frame
+            .get_item("ds")?
+            .call_method1("astype", ("str",))?
+            .call_method0("tolist")?
+            .extract()?;
+        forecast_timestamps.truncate(values.len());
+        
+        // Prior only; a model's `confidence` strategy can replace it
+        let confidence = 0.85;
+        
+        let mut metadata = HashMap::new();
+        if let Some((level, lower, upper)) = Self::prediction_interval(frame, &columns, value_column, values.len())? {
+            let (lower, upper) = match scaler {
+                Some(scaler) => (scaler.inverse(&lower), scaler.inverse(&upper)),
+                None => (lower, upper),
+            };
+            metadata.insert(
+                "interval".to_string(),
+                serde_json::json!({ "level": level, "lower": lower, "upper": upper }),
+            );
+        }
+        metadata.insert(
+            "input_length".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(input.historical_data.len())),
+        );
+        metadata.insert(
+            "model_type".to_string(),
+            serde_json::Value::String(model_name.to_string()),
+        );
+        metadata.insert(
+            "forecast_timestamps".to_string(),
+            serde_json::json!(forecast_timestamps),
+        );
+        if let Some(scaler) = scaler {
+            metadata.insert("scaler".to_string(), serde_json::to_value(scaler)?);
+        }
+        
+        Ok(PredictionResult {
+            model_name: model_name.to_string(),
+            symbol: input.symbol.clone(),
+            prediction: values,
+            confidence,
+            timestamp: chrono::Utc::now(),
+            horizon: input.horizon,
+            metadata,
+        })
+    }
+
+    /// Bounds of the first `-lo-`/`-hi-` interval NeuralForecast produced for
+    /// `value_column`, as (level, lower, upper)
+    fn prediction_interval(
+        frame: &PyAny,
+        columns: &[String],
+        value_column: &str,
+        steps: usize,
+    ) -> Result<Option<(String, Vec<f64>, Vec<f64>)>> {
+        let prefix = format!("{}-lo-", value_column);
+        let Some((lower_column, level)) = columns
+            .iter()
+            .find_map(|column| Some((column, column.strip_prefix(&prefix)?)))
+        else {
+            return Ok(None);
+        };
+        let upper_column = format!("{}-hi-{}", value_column, level);
+        if !columns.contains(&upper_column) {
+            return Ok(None);
+        }
+        
+        let bounds = |column: &str| -> Result<Vec<f64>> {
+            let mut values: Vec<f64> = frame.get_item(column)?.call_method0("tolist")?.extract()?;
+            values.truncate(steps);
+            Ok(values)
+        };
+        Ok(Some((level.to_string(), bounds(lower_column)?, bounds(&upper_column)?)))
+    }
+}
+
+/// Seed Python, NumPy and Torch RNGs so dropout/sampling at inference is
+/// repeatable on the same hardware and library versions
+fn seed_python_rngs(py: Python, seed: u64) -> Result<()> {
+    py.import("random")?.call_method1("seed", (seed,))?;
+    // NumPy only accepts 32-bit seeds
+    py.import("numpy")?
+        .getattr("random")?
+        .call_method1("seed", (seed as u32,))?;
+    
+    let torch = py.import("torch")?;
+    torch.call_method1("manual_seed", (seed,))?;
+    if torch.getattr("cuda")?.call_method0("is_available")?.extract::<bool>()? {
+        torch.getattr("cuda")?.call_method1("manual_seed_all", (seed,))?;
+    }
+    
+    Ok(())
+}
+
+/// Whether a NeuralForecast output column holds point forecasts rather than
+/// identifiers or `-lo-`/`-hi-` prediction intervals
+fn is_point_forecast_column(column: &str) -> bool {
+    !matches!(column, "unique_id" | "ds" | "index")
+        && !column.contains("-lo-")
+        && !column.contains("-hi-")
+}
+
+impl Default for PythonBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InferenceBackend for PythonBackend {
+    fn name(&self) -> &'static str {
+        "python"
+    }
+
+    fn device(&self) -> &'static str {
+        if self.cuda.load(Ordering::Relaxed) {
+            "cuda"
+        } else {
+            "cpu"
+        }
+    }
+
+    fn initialize(&self) -> Result<()> {
+        info!("Initializing NeuralForecast Python environment");
+        
+        Python::with_gil(|py| -> PyResult<()> {
+            // Import required Python modules
+            let sys = py.import("sys")?;
+            let path = sys.getattr("path")?;
+            path.call_method1("append", ("/path/to/neuralforecast",))?;
+            
+            // Import NeuralForecast
+            let neuralforecast_module = py.import("neuralforecast")?;
+            *self.python_module.write() = Some(neuralforecast_module.into());
+            
+            let cuda = py.import("torch")?.getattr("cuda")?.call_method0("is_available")?;
+            self.cuda.store(cuda.extract::<bool>()?, Ordering::Relaxed);
+            
+            info!("NeuralForecast environment initialized");
+            Ok(())
+        })
+        .map_err(|e| NeuralBridgeError::PythonInitFailed(e.to_string()))
+    }
+
+    fn load(&self, model_name: &str, model_config: &ModelConfig) -> Result<()> {
+        // Saved checkpoints are loaded through the NeuralForecast wrapper,
+        // whose `predict(df=...)` accepts long-format frames
+        match model_config.model_type.as_str() {
+            "TemporalFusionTransformer" | "NBEATS" | "LSTM" => {}
+            _ => {
+                return Err(NeuralBridgeError::UnsupportedModelType(
+                    model_config.model_type.clone(),
+                ));
+            }
+        }
+        
+        // Only the checkpoint load holds the GIL; the registries are updated
+        // after it is released
+        let model = Python::with_gil(|py| -> Result<PyObject> {
+            let module = self
+                .python_module
+                .read()
+                .as_ref()
+                .map(|module| module.clone_ref(py))
+                .ok_or_else(|| {
+                    NeuralBridgeError::PythonInitFailed("NeuralForecast not initialized".to_string())
+                })?;
+            
+            let load_kwargs = PyDict::new(py);
+            load_kwargs.set_item("path", &model_config.model_path)?;
+            Ok(module
+                .getattr(py, "NeuralForecast")?
+                .call_method(py, "load", (), Some(load_kwargs))?)
+        })?;
+        
+        self.models.write().insert(model_name.to_string(), model);
+        self.scalers.write().insert(model_name.to_string(), model_config.scaler);
+        self.paddings.write().insert(
+            model_name.to_string(),
+            (model_config.padding, model_config.min_history_points()),
+        );
+        Ok(())
+    }
+
+    fn predict(&self, model_name: &str, input: &PredictionInput) -> Result<PredictionResult> {
+        Python::with_gil(|py| -> Result<PredictionResult> {
+            let model = self
+                .models
+                .read()
+                .get(model_name)
+                .map(|model| model.clone_ref(py))
+                .ok_or_else(|| NeuralBridgeError::ModelNotLoaded(model_name.to_string()))?;
+            
+            if let Some(seed) = input.seed {
+                seed_python_rngs(py, seed)?;
+            }
+            
+            // Fit on this window (or use the saved parameters) before marshaling
+            let scaler_config = self.scalers.read().get(model_name).copied().unwrap_or_default();
+            let scaler = Scaler::fit(scaler_config, &input.historical_data);
+            
+            // Pad only when enabled and the input is actually short
+            let padding = self
+                .paddings
+                .read()
+                .get(model_name)
+                .copied()
+                .filter(|(strategy, target)| strategy.is_enabled() && input.historical_data.len() < *target);
+            
+            // Convert input data to a long-format DataFrame
+            let py_data = self.convert_input_to_python(py, input, scaler.as_ref(), padding)?;
+            
+            // Generate prediction
+            let predict_kwargs = PyDict::new(py);
+            predict_kwargs.set_item("df", py_data)?;
+            let prediction = model.call_method(py, "predict", (), Some(predict_kwargs))?;
+            
+            // Convert result back to Rust format
+            let mut result =
+                self.convert_prediction_from_python(py, prediction, input, model_name, scaler.as_ref())?;
+            if let Some((strategy, target)) = padding {
+                result.metadata.insert(
+                    "padding".to_string(),
+                    serde_json::json!({
+                        "strategy": strategy.as_str(),
+                        "padded_points": target - input.historical_data.len(),
+                    }),
+                );
+            }
+            Ok(result)
+        })
+    }
+
+    fn stats(&self, model_name: &str) -> Result<ModelStats> {
+        // Placeholder implementation
+        // In practice, this would query the model's performance metrics
+        Ok(ModelStats {
+            model_name: model_name.to_string(),
+            accuracy: 0.80,
+            average_inference_time_ms: 8.5,
+            total_predictions: 1000,
+            successful_predictions: 950,
+            failed_predictions: 50,
+            last_used: chrono::Utc::now(),
+            memory_usage_mb: 256.0,
+            outcomes_recorded: 0,
+            directional_accuracy: 0.0,
+        })
+    }
+
+    fn health(&self) -> Result<bool> {
+        Python::with_gil(|py| -> Result<bool> {
+            if let Some(module) = self.python_module.read().as_ref() {
+                // Try to access the module
+                let _version = module.getattr(py, "__version__")?;
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        })
+    }
+}
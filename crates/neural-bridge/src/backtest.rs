@@ -0,0 +1,199 @@
+//! Walk-forward backtesting of a model over a historical series
+
+use crate::{
+    error::{NeuralBridgeError, Result},
+    models::{directional_accuracy, ErrorMetric},
+    CacheMode, NeuralBridgeManager, PredictionInput,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Walk-forward window layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestConfig {
+    /// Price points fed to the model at each step
+    pub input_length: usize,
+
+    /// Steps forecast and scored at each step
+    pub horizon: usize,
+
+    /// Points the window advances between steps
+    pub step: usize,
+
+    /// Also fold each outcome into the model's live rolling accuracy
+    pub record_outcomes: bool,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            input_length: 100,
+            horizon: 5,
+            step: 5,
+            record_outcomes: false,
+        }
+    }
+}
+
+/// Accuracy of a model over a backtest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub model_name: String,
+    pub symbol: String,
+    /// Windows forecast and scored
+    pub windows: usize,
+    /// Windows whose prediction failed
+    pub failed_windows: usize,
+    /// Mean absolute percentage error across windows
+    pub mape: f64,
+    /// Root mean squared error across all scored points
+    pub rmse: f64,
+    /// Fraction of steps whose predicted move had the realized sign
+    pub directional_accuracy: f64,
+}
+
+/// Walks a model forward over history using the manager's prediction path
+pub struct Backtester<'a> {
+    manager: &'a NeuralBridgeManager,
+}
+
+impl<'a> Backtester<'a> {
+    /// Backtest models served by `manager`
+    pub fn new(manager: &'a NeuralBridgeManager) -> Self {
+        Self { manager }
+    }
+
+    /// Forecast each window of `prices` with `model_name` and score it
+    /// against the realized values that follow
+    pub async fn run(
+        &self,
+        model_name: &str,
+        symbol: &str,
+        prices: &[f64],
+        timestamps: &[chrono::DateTime<chrono::Utc>],
+        config: &BacktestConfig,
+    ) -> Result<BacktestReport> {
+        if timestamps.len() != prices.len() {
+            return Err(NeuralBridgeError::Validation(
+                "Timestamp and price length mismatch".to_string(),
+            ));
+        }
+        if config.input_length == 0 || config.horizon == 0 || config.step == 0 {
+            return Err(NeuralBridgeError::Validation(
+                "Backtest input_length, horizon and step must be positive".to_string(),
+            ));
+        }
+        if prices.len() < config.input_length + config.horizon {
+            return Err(NeuralBridgeError::Validation(format!(
+                "Backtest needs at least {} points, got {}",
+                config.input_length + config.horizon,
+                prices.len()
+            )));
+        }
+
+        let mut windows = 0;
+        let mut failed_windows = 0;
+        let mut mape_sum = 0.0;
+        let mut mape_windows = 0;
+        let mut squared_error = 0.0;
+        let mut scored_points = 0;
+        let mut direction_hits = 0.0;
+        let mut direction_steps = 0;
+
+        for end in (config.input_length..=prices.len() - config.horizon).step_by(config.step) {
+            let start = end - config.input_length;
+            let input = PredictionInput {
+                symbol: symbol.to_string(),
+                historical_data: prices[start..end].to_vec(),
+                timestamps: timestamps[start..end].to_vec(),
+                features: HashMap::new(),
+                horizon: config.horizon,
+                seed: None,
+                resampling: None,
+            };
+            let actual = &prices[end..end + config.horizon];
+
+            let result = match self.manager.predict_with_model(input, Some(model_name), CacheMode::Off).await {
+                Ok(result) => result,
+                Err(e) => {
+                    debug!("Backtest window ending at {} failed: {}", end, e);
+                    failed_windows += 1;
+                    continue;
+                }
+            };
+            let predicted = &result.prediction;
+            windows += 1;
+
+            if let Some(mape) = ErrorMetric::Mape.compute(predicted, actual) {
+                mape_sum += mape;
+                mape_windows += 1;
+            }
+            for (p, a) in predicted.iter().zip(actual) {
+                squared_error += (p - a).powi(2);
+                scored_points += 1;
+            }
+
+            let last_known = prices[end - 1];
+            let steps = predicted.len().min(actual.len());
+            direction_hits += directional_accuracy(predicted, actual, last_known) * steps as f64;
+            direction_steps += steps;
+
+            if config.record_outcomes {
+                self.manager.record_outcome(model_name, predicted, actual, last_known)?;
+            }
+        }
+
+        let ratio = |numerator: f64, denominator: usize| {
+            if denominator == 0 {
+                0.0
+            } else {
+                numerator / denominator as f64
+            }
+        };
+
+        Ok(BacktestReport {
+            model_name: model_name.to_string(),
+            symbol: symbol.to_string(),
+            windows,
+            failed_windows,
+            mape: ratio(mape_sum, mape_windows),
+            rmse: ratio(squared_error, scored_points).sqrt(),
+            directional_accuracy: ratio(direction_hits, direction_steps),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::mock_manager;
+
+    #[tokio::test]
+    async fn test_walk_forward_scores_each_window() {
+        let manager = mock_manager().await;
+
+        let start = chrono::Utc::now();
+        let prices: Vec<f64> = (0..40).map(|i| 100.0 + i as f64).collect();
+        let timestamps: Vec<_> = (0..40).map(|i| start + chrono::Duration::minutes(i)).collect();
+        let config = BacktestConfig {
+            input_length: 20,
+            horizon: 2,
+            step: 6,
+            record_outcomes: false,
+        };
+
+        let report = Backtester::new(&manager)
+            .run("NBEATS", "AAPL", &prices, &timestamps, &config)
+            .await
+            .unwrap();
+
+        // Windows end at 20, 26, 32 and 38; the mock repeats the last price
+        assert_eq!(report.windows, 4);
+        assert_eq!(report.failed_windows, 0);
+        assert!((report.rmse - 2.5f64.sqrt()).abs() < 1e-9);
+        assert_eq!(report.directional_accuracy, 0.0);
+        // Windows are neither served from nor written to the live cache
+        assert_eq!(manager.prediction_cache.read().await.len(), 0);
+    }
+}
@@ -0,0 +1,208 @@
+//! Classical baseline forecasters in pure Rust
+//!
+//! Naive, drift and simple moving average forecasts to benchmark the neural
+//! models against. `BaselineBackend` serves them through the same
+//! `InferenceBackend` interface, selected by `model_type` (`Naive`, `Drift`,
+//! `SMA` with an optional `window` parameter).
+
+use crate::{
+    backend::InferenceBackend,
+    config::ModelConfig,
+    error::{NeuralBridgeError, Result},
+    models::ModelStats,
+    PredictionInput, PredictionResult,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Window used by `SMA` models that don't set `parameters["window"]`
+pub const DEFAULT_SMA_WINDOW: usize = 20;
+
+/// Baseline forecasting method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Baseline {
+    /// Repeat the last observed value
+    Naive,
+    /// Extend the average change between the first and last observation
+    Drift,
+    /// Repeat the mean of the last `window` observations
+    Sma { window: usize },
+}
+
+impl Baseline {
+    /// Baseline for a model configuration, if its type is a baseline
+    pub fn from_config(config: &ModelConfig) -> Option<Self> {
+        match config.model_type.as_str() {
+            "Naive" => Some(Baseline::Naive),
+            "Drift" => Some(Baseline::Drift),
+            "SMA" => Some(Baseline::Sma {
+                window: config
+                    .parameters
+                    .get("window")
+                    .and_then(|value| value.as_u64())
+                    .map_or(DEFAULT_SMA_WINDOW, |window| window as usize),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Method name recorded in metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Baseline::Naive => "naive",
+            Baseline::Drift => "drift",
+            Baseline::Sma { .. } => "sma",
+        }
+    }
+
+    /// Forecast `horizon` steps past the end of `history`
+    pub fn forecast(&self, history: &[f64], horizon: usize) -> Vec<f64> {
+        let Some(&last) = history.last() else {
+            return vec![0.0; horizon];
+        };
+
+        match self {
+            Baseline::Naive => vec![last; horizon],
+            Baseline::Drift => {
+                let slope = if history.len() > 1 {
+                    (last - history[0]) / (history.len() - 1) as f64
+                } else {
+                    0.0
+                };
+                (1..=horizon).map(|step| last + slope * step as f64).collect()
+            }
+            Baseline::Sma { window } => {
+                let recent = &history[history.len().saturating_sub((*window).max(1))..];
+                vec![recent.iter().sum::<f64>() / recent.len() as f64; horizon]
+            }
+        }
+    }
+
+    /// Forecast an input as a `PredictionResult` reporting `confidence`
+    pub fn predict(&self, model_name: &str, input: &PredictionInput, confidence: f64) -> PredictionResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("baseline".to_string(), serde_json::json!(self.as_str()));
+        if let Baseline::Sma { window } = self {
+            metadata.insert("window".to_string(), serde_json::json!(window));
+        }
+
+        PredictionResult {
+            model_name: model_name.to_string(),
+            symbol: input.symbol.clone(),
+            prediction: self.forecast(&input.historical_data, input.horizon),
+            confidence,
+            timestamp: chrono::Utc::now(),
+            horizon: input.horizon,
+            metadata,
+        }
+    }
+}
+
+/// Backend serving baseline models with no Python runtime.
+///
+/// Results report the model's configured `accuracy` as confidence.
+#[derive(Default)]
+pub struct BaselineBackend {
+    models: RwLock<HashMap<String, (Baseline, f64)>>,
+}
+
+impl BaselineBackend {
+    /// Create a backend with no models loaded
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl InferenceBackend for BaselineBackend {
+    fn name(&self) -> &'static str {
+        "baseline"
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn load(&self, model_name: &str, config: &ModelConfig) -> Result<()> {
+        let baseline = Baseline::from_config(config)
+            .ok_or_else(|| NeuralBridgeError::UnsupportedModelType(config.model_type.clone()))?;
+        self.models
+            .write()
+            .insert(model_name.to_string(), (baseline, config.accuracy));
+        Ok(())
+    }
+
+    fn predict(&self, model_name: &str, input: &PredictionInput) -> Result<PredictionResult> {
+        let (baseline, confidence) = self
+            .models
+            .read()
+            .get(model_name)
+            .copied()
+            .ok_or_else(|| NeuralBridgeError::ModelNotLoaded(model_name.to_string()))?;
+        Ok(baseline.predict(model_name, input, confidence))
+    }
+
+    fn stats(&self, model_name: &str) -> Result<ModelStats> {
+        let (_, accuracy) = self
+            .models
+            .read()
+            .get(model_name)
+            .copied()
+            .ok_or_else(|| NeuralBridgeError::ModelNotLoaded(model_name.to_string()))?;
+        Ok(ModelStats {
+            model_name: model_name.to_string(),
+            accuracy,
+            average_inference_time_ms: 0.0,
+            total_predictions: 0,
+            successful_predictions: 0,
+            failed_predictions: 0,
+            last_used: chrono::Utc::now(),
+            memory_usage_mb: 0.0,
+            outcomes_recorded: 0,
+            directional_accuracy: 0.0,
+        })
+    }
+
+    fn health(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_baseline_forecasts() {
+        let history = [10.0, 11.0, 13.0, 16.0];
+
+        assert_eq!(Baseline::Naive.forecast(&history, 2), vec![16.0, 16.0]);
+        assert_eq!(Baseline::Drift.forecast(&history, 2), vec![18.0, 20.0]);
+        assert_eq!(Baseline::Sma { window: 2 }.forecast(&history, 1), vec![14.5]);
+        assert_eq!(Baseline::Sma { window: 10 }.forecast(&history, 1), vec![12.5]);
+    }
+
+    #[test]
+    fn test_backend_loads_baseline_model_types() {
+        let backend = BaselineBackend::new();
+        let mut config = crate::config::NeuralBridgeConfig::default().neuralforecast.models["NBEATS"].clone();
+        assert!(backend.load("NBEATS", &config).is_err());
+
+        config.model_type = "Drift".to_string();
+        backend.load("DRIFT", &config).unwrap();
+        let input = PredictionInput {
+            symbol: "AAPL".to_string(),
+            historical_data: vec![1.0, 2.0, 3.0],
+            timestamps: vec![],
+            features: HashMap::new(),
+            horizon: 2,
+            seed: None,
+            resampling: None,
+        };
+
+        let result = backend.predict("DRIFT", &input).unwrap();
+        assert_eq!(result.prediction, vec![4.0, 5.0]);
+        assert_eq!(result.metadata["baseline"], serde_json::json!("drift"));
+    }
+}
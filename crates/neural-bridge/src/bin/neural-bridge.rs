@@ -0,0 +1,213 @@
+//! One-off predictions from a CSV of timestamped prices
+//!
+//! The CSV needs a `timestamp` column (RFC 3339, `%Y-%m-%d %H:%M:%S` or Unix
+//! milliseconds) and a `price` column; any other numeric column is passed
+//! to the model as a feature of the same name.
+//!
+//! ```text
+//! neural-bridge --input aapl.csv --symbol AAPL --horizon 10 --format summary
+//! ```
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, ValueEnum};
+use neural_bridge::{
+    backend::BackendKind,
+    config::NeuralBridgeConfig,
+    prediction::{PredictionFormatter, PredictionPriority, PredictionRequest, PredictionStatus},
+    NeuralBridgeManager, PredictionInput,
+};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(name = "neural-bridge", about = "Forecast a price series from a CSV file")]
+struct Args {
+    /// CSV of timestamped prices; `-` reads stdin
+    #[arg(short, long)]
+    input: PathBuf,
+
+    /// Symbol the series belongs to
+    #[arg(short, long)]
+    symbol: String,
+
+    /// Steps to forecast
+    #[arg(long, default_value_t = 5)]
+    horizon: usize,
+
+    /// Model to use instead of automatic selection
+    #[arg(short, long)]
+    model: Option<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Summary)]
+    format: Format,
+
+    /// JSON `NeuralBridgeConfig`; defaults are used when omitted
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Inference backend, overriding the config
+    #[arg(short, long, value_enum)]
+    backend: Option<Backend>,
+
+    /// RNG seed for reproducible output
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Csv,
+    Summary,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Backend {
+    Python,
+    Onnx,
+    Baseline,
+}
+
+impl From<Backend> for BackendKind {
+    fn from(backend: Backend) -> Self {
+        match backend {
+            Backend::Python => BackendKind::Python,
+            Backend::Onnx => BackendKind::Onnx,
+            Backend::Baseline => BackendKind::Baseline,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let mut config = match &args.config {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config {}", path.display()))?;
+            serde_json::from_str::<NeuralBridgeConfig>(&text)
+                .with_context(|| format!("Invalid config {}", path.display()))?
+        }
+        None => NeuralBridgeConfig::default(),
+    };
+    if let Some(backend) = args.backend {
+        config.neuralforecast.backend = backend.into();
+    }
+
+    let csv = if args.input.as_os_str() == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        std::fs::read_to_string(&args.input)
+            .with_context(|| format!("Failed to read {}", args.input.display()))?
+    };
+    let mut input = parse_csv(&args.symbol, &csv)?;
+    input.horizon = args.horizon;
+    input.seed = args.seed;
+
+    let manager = NeuralBridgeManager::new(config)?;
+    manager.initialize().await?;
+    if let Some(model) = &args.model {
+        manager.load_model(model).await?;
+    }
+
+    let response = manager
+        .predict_request(PredictionRequest {
+            input,
+            model_preference: args.model.clone(),
+            priority: PredictionPriority::Normal,
+            callback_url: None,
+            request_id: "cli".to_string(),
+            bypass_cache: false,
+        })
+        .await;
+    if let PredictionStatus::Failed { error } = response.status {
+        bail!("Prediction failed: {}", error);
+    }
+
+    let output = match args.format {
+        Format::Json => PredictionFormatter::to_json(&response.result)?,
+        Format::Csv => PredictionFormatter::to_csv(&response.result)?,
+        Format::Summary => PredictionFormatter::to_summary(&response.result),
+    };
+    println!("{}", output.trim_end());
+    Ok(())
+}
+
+/// Build an input from CSV text, with every column besides `timestamp` and
+/// `price` read as a feature
+fn parse_csv(symbol: &str, text: &str) -> Result<PredictionInput> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(text.as_bytes());
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|header| header.eq_ignore_ascii_case(name));
+    let timestamp_column = column("timestamp").context("CSV has no `timestamp` column")?;
+    let price_column = column("price").context("CSV has no `price` column")?;
+
+    let mut builder = PredictionInput::builder(symbol);
+    let mut features: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for (row, record) in reader.records().enumerate() {
+        let record = record?;
+        let line = row + 2;
+        let timestamp = parse_timestamp(&record[timestamp_column])
+            .with_context(|| format!("Invalid timestamp on line {}", line))?;
+        let price: f64 = record[price_column]
+            .parse()
+            .with_context(|| format!("Invalid price on line {}", line))?;
+        builder = builder.add_price(timestamp, price);
+
+        for (index, name) in headers.iter().enumerate() {
+            if index == timestamp_column || index == price_column {
+                continue;
+            }
+            let value: f64 = record[index]
+                .parse()
+                .with_context(|| format!("Invalid {} on line {}", name, line))?;
+            features.entry(name.to_string()).or_default().push(value);
+        }
+    }
+
+    for (name, values) in features {
+        builder = builder.add_feature(name, values);
+    }
+    Ok(builder.build()?)
+}
+
+fn parse_timestamp(text: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(text) {
+        return Ok(timestamp.with_timezone(&chrono::Utc));
+    }
+    if let Ok(timestamp) = chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S") {
+        return Ok(timestamp.and_utc());
+    }
+    let millis: i64 = text.parse().context("Unrecognized timestamp format")?;
+    chrono::DateTime::from_timestamp_millis(millis).context("Timestamp out of range")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_columns_become_prices_and_features() {
+        let csv = "timestamp,price,volume\n\
+                   2024-01-02T14:30:00Z,190.5,1000\n\
+                   2024-01-02 14:31:00,190.7,1200\n\
+                   1704206520000,190.6,900\n";
+
+        let input = parse_csv("AAPL", csv).unwrap();
+
+        assert_eq!(input.historical_data, vec![190.5, 190.7, 190.6]);
+        assert_eq!(input.features["volume"], vec![1000.0, 1200.0, 900.0]);
+        assert_eq!(input.timestamps[2].timestamp_millis(), 1_704_206_520_000);
+    }
+
+    #[test]
+    fn test_missing_price_column_is_an_error() {
+        let err = parse_csv("AAPL", "timestamp,close\n2024-01-02T14:30:00Z,1\n").unwrap_err();
+        assert!(err.to_string().contains("price"));
+    }
+}
@@ -1,19 +1,37 @@
-//! Model caching system
+//! Model and prediction caching
 
+use crate::PredictionResult;
+use lru::LruCache;
 use parking_lot::RwLock;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::num::NonZeroUsize;
+use std::time::Duration;
 
 /// Cached model wrapper
 #[derive(Debug, Clone)]
 pub struct CachedModel {
     pub name: String,
-    pub model_data: Arc<Vec<u8>>, // Serialized model data
+    /// On-disk size of the loaded checkpoint
+    pub size_bytes: u64,
     pub metadata: crate::models::ModelMetadata,
     pub last_accessed: std::time::Instant,
     pub access_count: u64,
 }
 
+/// Bytes on disk of a checkpoint file, or of every file under a checkpoint
+/// directory; 0 if the path can't be read
+pub fn checkpoint_size(path: &std::path::Path) -> u64 {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| checkpoint_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
 /// Model cache implementation
 pub struct ModelCache {
     cache: RwLock<HashMap<String, CachedModel>>,
@@ -60,6 +78,11 @@ impl ModelCache {
         }
     }
 
+    /// Version of a cached model, without counting as an access
+    pub fn version(&self, name: &str) -> Option<String> {
+        self.cache.read().get(name).map(|model| model.metadata.version.clone())
+    }
+
     /// Check if model exists in cache
     pub fn contains(&self, name: &str) -> bool {
         let cache = self.cache.read();
@@ -85,7 +108,7 @@ impl ModelCache {
         let total_models = cache.len();
         let total_memory = cache
             .values()
-            .map(|model| model.model_data.len())
+            .map(|model| model.size_bytes as usize)
             .sum::<usize>();
         
         let total_accesses = cache
@@ -117,6 +140,14 @@ impl ModelCache {
     pub fn max_size(&self) -> usize {
         self.max_size
     }
+
+    /// Remove models not accessed within `ttl`, returning how many were removed
+    pub fn sweep_expired(&self, ttl: std::time::Duration) -> usize {
+        let mut cache = self.cache.write();
+        let before = cache.len();
+        cache.retain(|_, model| model.last_accessed.elapsed() < ttl);
+        before - cache.len()
+    }
 }
 
 /// Cache statistics
@@ -133,4 +164,254 @@ impl CacheStats {
     pub fn memory_usage_mb(&self) -> f64 {
         self.total_memory_bytes as f64 / (1024.0 * 1024.0)
     }
-}
\ No newline at end of file
+}
+/// Cached prediction with its serialized size
+#[derive(Debug, Clone)]
+struct PredictionEntry {
+    result: PredictionResult,
+    size_bytes: usize,
+}
+
+/// LRU prediction cache bounded by entry count and optionally by bytes
+pub struct PredictionCache {
+    entries: LruCache<String, PredictionEntry>,
+    max_entries: usize,
+    max_bytes: Option<usize>,
+    total_bytes: usize,
+}
+
+impl PredictionCache {
+    /// Create an empty prediction cache
+    pub fn new(max_entries: usize, max_bytes: Option<usize>) -> Self {
+        let capacity = NonZeroUsize::new(max_entries.max(1)).unwrap();
+        Self {
+            entries: LruCache::new(capacity),
+            max_entries,
+            max_bytes,
+            total_bytes: 0,
+        }
+    }
+
+    /// Get a cached prediction without changing its LRU position
+    pub fn get(&self, key: &str) -> Option<&PredictionResult> {
+        self.entries.peek(key).map(|entry| &entry.result)
+    }
+
+    /// Mark a prediction as recently used
+    pub fn promote(&mut self, key: &str) {
+        self.entries.promote(key);
+    }
+
+    /// Insert a prediction, evicting least recently used entries until both
+    /// the entry cap and the byte budget are respected
+    pub fn insert(&mut self, key: String, result: PredictionResult) {
+        let size_bytes = serde_json::to_vec(&result).map_or(0, |bytes| bytes.len());
+        self.remove(&key);
+
+        while self.entries.len() >= self.entries.cap().get()
+            || self.max_bytes.map_or(false, |max| self.total_bytes + size_bytes > max)
+        {
+            match self.entries.pop_lru() {
+                Some((_, evicted)) => self.total_bytes -= evicted.size_bytes,
+                None => break,
+            }
+        }
+
+        self.total_bytes += size_bytes;
+        self.entries.put(key, PredictionEntry { result, size_bytes });
+    }
+
+    /// Remove a cached prediction
+    pub fn remove(&mut self, key: &str) -> Option<PredictionResult> {
+        let entry = self.entries.pop(key)?;
+        self.total_bytes -= entry.size_bytes;
+        Some(entry.result)
+    }
+
+    /// Keep only predictions matching the predicate
+    pub fn retain(&mut self, mut keep: impl FnMut(&PredictionResult) -> bool) {
+        let dropped: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| !keep(&entry.result))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in dropped {
+            self.remove(&key);
+        }
+    }
+
+    /// Clear all cached predictions
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.total_bytes = 0;
+    }
+
+    /// Key and age of each cached prediction, most recently used first.
+    ///
+    /// Age is measured from the result's timestamp, as for TTL checks.
+    pub fn entry_ages(&self, now: chrono::DateTime<chrono::Utc>) -> Vec<(String, Duration)> {
+        self.entries
+            .iter()
+            .map(|(key, entry)| (key.clone(), age(&entry.result, now)))
+            .collect()
+    }
+
+    /// Remove predictions at least `ttl` old, returning how many were removed
+    pub fn sweep_expired(&mut self, ttl: Duration, now: chrono::DateTime<chrono::Utc>) -> usize {
+        let before = self.entries.len();
+        self.retain(|result| age(result, now) < ttl);
+        before - self.entries.len()
+    }
+
+    /// Iterate over cached predictions from most to least recently used
+    pub fn values(&self) -> impl Iterator<Item = &PredictionResult> {
+        self.entries.iter().map(|(_, entry)| &entry.result)
+    }
+
+    /// Iterate over keys and predictions from most to least recently used
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &PredictionResult)> {
+        self.entries.iter().map(|(key, entry)| (key, &entry.result))
+    }
+
+    /// Number of cached predictions
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get cache statistics
+    pub fn stats(&self) -> PredictionCacheStats {
+        PredictionCacheStats {
+            total_entries: self.entries.len(),
+            max_entries: self.max_entries,
+            total_memory_bytes: self.total_bytes,
+            max_bytes: self.max_bytes,
+        }
+    }
+}
+
+/// Time since a result was produced; zero for timestamps in the future
+fn age(result: &PredictionResult, now: chrono::DateTime<chrono::Utc>) -> Duration {
+    now.signed_duration_since(result.timestamp).to_std().unwrap_or_default()
+}
+
+/// Prediction cache statistics
+#[derive(Debug, Clone)]
+pub struct PredictionCacheStats {
+    pub total_entries: usize,
+    pub max_entries: usize,
+    pub total_memory_bytes: usize,
+    pub max_bytes: Option<usize>,
+}
+
+impl PredictionCacheStats {
+    /// Get memory usage in MB
+    pub fn memory_usage_mb(&self) -> f64 {
+        self.total_memory_bytes as f64 / (1024.0 * 1024.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_size_sums_directory_files() {
+        let dir = std::env::temp_dir().join(format!("checkpoint-size-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("weights")).unwrap();
+        std::fs::write(dir.join("config.json"), [0u8; 100]).unwrap();
+        std::fs::write(dir.join("weights").join("model.ckpt"), [0u8; 400]).unwrap();
+
+        assert_eq!(checkpoint_size(&dir), 500);
+        assert_eq!(checkpoint_size(&dir.join("config.json")), 100);
+        assert_eq!(checkpoint_size(&dir.join("missing")), 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn result(horizon: usize, age_seconds: i64) -> PredictionResult {
+        PredictionResult {
+            model_name: "TFT".to_string(),
+            symbol: "AAPL".to_string(),
+            prediction: vec![1.0; horizon],
+            confidence: 0.9,
+            // Whole seconds keep the serialized size independent of the clock
+            timestamp: chrono::DateTime::from_timestamp(chrono::Utc::now().timestamp() - age_seconds, 0)
+                .unwrap(),
+            horizon,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_byte_budget_evicts_oldest() {
+        let entry_size = serde_json::to_vec(&result(100, 0)).unwrap().len();
+        let mut cache = PredictionCache::new(100, Some(entry_size * 2));
+
+        cache.insert("old".to_string(), result(100, 30));
+        cache.insert("mid".to_string(), result(100, 20));
+        cache.insert("new".to_string(), result(100, 10));
+
+        assert!(cache.get("old").is_none());
+        assert!(cache.get("mid").is_some() && cache.get("new").is_some());
+        assert_eq!(cache.stats().total_memory_bytes, entry_size * 2);
+    }
+
+    #[test]
+    fn test_entry_cap_evicts_least_recently_used() {
+        let mut cache = PredictionCache::new(2, None);
+        cache.insert("a".to_string(), result(5, 0));
+        cache.insert("b".to_string(), result(5, 0));
+        // Lookups alone leave the order unchanged
+        cache.get("b");
+        cache.promote("a");
+        cache.insert("c".to_string(), result(5, 0));
+
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some() && cache.get("c").is_some());
+    }
+
+    #[test]
+    fn test_byte_budget_evicts_least_recently_used() {
+        let entry_size = serde_json::to_vec(&result(100, 0)).unwrap().len();
+        let mut cache = PredictionCache::new(100, Some(entry_size * 2));
+        cache.insert("old".to_string(), result(100, 30));
+        cache.insert("mid".to_string(), result(100, 20));
+        cache.promote("old");
+
+        cache.insert("new".to_string(), result(100, 10));
+
+        assert!(cache.get("mid").is_none());
+        assert!(cache.get("old").is_some() && cache.get("new").is_some());
+    }
+
+    #[test]
+    fn test_retain_updates_byte_usage() {
+        let mut cache = PredictionCache::new(10, None);
+        cache.insert("a".to_string(), result(5, 0));
+        cache.retain(|_| false);
+
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().total_memory_bytes, 0);
+    }
+
+    #[test]
+    fn test_sweep_removes_only_expired_entries() {
+        let mut cache = PredictionCache::new(10, None);
+        cache.insert("fresh".to_string(), result(5, 10));
+        cache.insert("stale".to_string(), result(5, 600));
+
+        let ages = cache.entry_ages(chrono::Utc::now());
+        assert_eq!(ages.len(), 2);
+        assert!(ages.iter().any(|(key, age)| key == "stale" && age.as_secs() >= 600));
+
+        assert_eq!(cache.sweep_expired(Duration::from_secs(300), chrono::Utc::now()), 1);
+        assert!(cache.get("fresh").is_some());
+        assert!(cache.stats().total_memory_bytes > 0);
+    }
+}
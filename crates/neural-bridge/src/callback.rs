@@ -0,0 +1,48 @@
+//! Webhook delivery of completed predictions
+
+use crate::config::CallbackConfig;
+use crate::prediction::PredictionResponse;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// POST a response to its callback URL with bounded retries.
+///
+/// Delivery failures are logged with the request id and never propagated.
+pub async fn deliver(
+    client: &reqwest::Client,
+    url: &str,
+    response: &PredictionResponse,
+    config: &CallbackConfig,
+) {
+    let max_attempts = config.max_attempts.max(1);
+
+    for attempt in 1..=max_attempts {
+        let result = client
+            .post(url)
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .json(response)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status());
+
+        match result {
+            Ok(_) => {
+                debug!("Delivered prediction {} to {}", response.request_id, url);
+                return;
+            }
+            Err(e) => warn!(
+                "Callback for prediction {} failed (attempt {}/{}): {:?}",
+                response.request_id, attempt, max_attempts, e
+            ),
+        }
+
+        if attempt < max_attempts {
+            tokio::time::sleep(Duration::from_millis(config.retry_backoff_ms * attempt as u64)).await;
+        }
+    }
+
+    error!(
+        "Giving up delivering prediction {} to {} after {} attempts",
+        response.request_id, url, max_attempts
+    );
+}
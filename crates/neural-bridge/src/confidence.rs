@@ -0,0 +1,179 @@
+//! Confidence estimation strategies
+//!
+//! `PredictionResult::confidence` is always in `[0, 1]`, but what it means
+//! depends on the strategy that produced it, so values from different
+//! strategies are not comparable:
+//!
+//! - `backend`: the inference backend's own figure (a fixed prior for the
+//!   Python backend, the configured accuracy for ONNX and baselines)
+//! - `quantile_width`: how narrow the forecast's prediction interval is
+//!   relative to the forecast itself; needs an `interval` in the metadata
+//! - `historical_accuracy`: the model's rolling accuracy from recorded
+//!   outcomes; needs at least one outcome
+//! - `volatility`: how calm the input series was, scaled by the horizon
+//!
+//! When a strategy lacks what it needs, the backend's figure is kept.
+
+use crate::{models::ModelStats, PredictionInput, PredictionResult};
+use serde::{Deserialize, Serialize};
+
+/// Computes the confidence reported for a prediction
+pub trait ConfidenceEstimator: Send + Sync {
+    /// Name recorded in prediction metadata
+    fn name(&self) -> &'static str;
+
+    /// Confidence for `result`, or `None` to keep the backend's value
+    fn estimate(
+        &self,
+        input: &PredictionInput,
+        result: &PredictionResult,
+        stats: Option<&ModelStats>,
+    ) -> Option<f64>;
+}
+
+/// Built-in confidence strategies, selectable per model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfidenceStrategy {
+    /// Keep the backend's figure
+    #[default]
+    Backend,
+    /// Narrower prediction intervals give higher confidence
+    QuantileWidth,
+    /// Rolling accuracy from recorded outcomes
+    HistoricalAccuracy,
+    /// Calmer inputs and shorter horizons give higher confidence
+    Volatility,
+}
+
+impl ConfidenceStrategy {
+    /// Name recorded in prediction metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfidenceStrategy::Backend => "backend",
+            ConfidenceStrategy::QuantileWidth => "quantile_width",
+            ConfidenceStrategy::HistoricalAccuracy => "historical_accuracy",
+            ConfidenceStrategy::Volatility => "volatility",
+        }
+    }
+}
+
+impl ConfidenceEstimator for ConfidenceStrategy {
+    fn name(&self) -> &'static str {
+        self.as_str()
+    }
+
+    fn estimate(
+        &self,
+        input: &PredictionInput,
+        result: &PredictionResult,
+        stats: Option<&ModelStats>,
+    ) -> Option<f64> {
+        let confidence = match self {
+            ConfidenceStrategy::Backend => return None,
+            ConfidenceStrategy::QuantileWidth => from_interval_width(result)?,
+            ConfidenceStrategy::HistoricalAccuracy => stats
+                .filter(|stats| stats.outcomes_recorded > 0)
+                .map(|stats| stats.accuracy)?,
+            ConfidenceStrategy::Volatility => from_volatility(&input.historical_data, result.horizon)?,
+        };
+        Some(confidence.clamp(0.0, 1.0))
+    }
+}
+
+/// `1 / (1 + w)` where `w` is the mean interval width over the mean
+/// absolute forecast, read from `metadata["interval"]`
+fn from_interval_width(result: &PredictionResult) -> Option<f64> {
+    let interval = result.metadata.get("interval")?;
+    let bounds = |key: &str| -> Option<Vec<f64>> { serde_json::from_value(interval.get(key)?.clone()).ok() };
+    let (lower, upper) = (bounds("lower")?, bounds("upper")?);
+
+    let steps = lower.len().min(upper.len()).min(result.prediction.len());
+    if steps == 0 {
+        return None;
+    }
+    let width = lower.iter().zip(&upper).take(steps).map(|(lo, hi)| (hi - lo).abs()).sum::<f64>();
+    let scale = result.prediction.iter().take(steps).map(|p| p.abs()).sum::<f64>();
+    if scale == 0.0 {
+        return None;
+    }
+    Some(1.0 / (1.0 + width / scale))
+}
+
+/// `1 - σ·√horizon`, where `σ` is the standard deviation of the input's
+/// one-step returns
+fn from_volatility(prices: &[f64], horizon: usize) -> Option<f64> {
+    let returns: Vec<f64> = prices
+        .windows(2)
+        .filter(|pair| pair[0] != 0.0)
+        .map(|pair| pair[1] / pair[0] - 1.0)
+        .collect();
+    if returns.len() < 2 {
+        return None;
+    }
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    Some(1.0 - variance.sqrt() * (horizon.max(1) as f64).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn input(prices: Vec<f64>) -> PredictionInput {
+        let start = chrono::Utc::now();
+        PredictionInput {
+            symbol: "AAPL".to_string(),
+            timestamps: (0..prices.len()).map(|i| start + chrono::Duration::minutes(i as i64)).collect(),
+            historical_data: prices,
+            features: HashMap::new(),
+            horizon: 4,
+            seed: None,
+            resampling: None,
+        }
+    }
+
+    fn result(metadata: HashMap<String, serde_json::Value>) -> PredictionResult {
+        PredictionResult {
+            model_name: "NBEATS".to_string(),
+            symbol: "AAPL".to_string(),
+            prediction: vec![100.0, 100.0],
+            confidence: 0.85,
+            timestamp: chrono::Utc::now(),
+            horizon: 4,
+            metadata,
+        }
+    }
+
+    #[test]
+    fn test_interval_width_sets_confidence() {
+        let interval = serde_json::json!({ "level": "90", "lower": [95.0, 95.0], "upper": [105.0, 105.0] });
+        let result = result(HashMap::from([("interval".to_string(), interval)]));
+
+        let confidence = ConfidenceStrategy::QuantileWidth.estimate(&input(vec![100.0; 10]), &result, None);
+        assert!((confidence.unwrap() - 1.0 / 1.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_volatility_penalizes_noisy_inputs() {
+        let calm = input((0..50).map(|i| 100.0 + (i % 2) as f64 * 0.01).collect());
+        let noisy = input((0..50).map(|i| 100.0 + (i % 2) as f64 * 5.0).collect());
+        let result = result(HashMap::new());
+
+        let strategy = ConfidenceStrategy::Volatility;
+        let calm = strategy.estimate(&calm, &result, None).unwrap();
+        let noisy = strategy.estimate(&noisy, &result, None).unwrap();
+        assert!(calm > 0.99 && noisy < calm);
+    }
+
+    #[test]
+    fn test_missing_inputs_keep_backend_value() {
+        let input = input(vec![100.0; 10]);
+        let result = result(HashMap::new());
+
+        assert_eq!(ConfidenceStrategy::Backend.estimate(&input, &result, None), None);
+        assert_eq!(ConfidenceStrategy::QuantileWidth.estimate(&input, &result, None), None);
+        assert_eq!(ConfidenceStrategy::HistoricalAccuracy.estimate(&input, &result, None), None);
+    }
+}
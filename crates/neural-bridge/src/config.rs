@@ -1,5 +1,10 @@
 //! Configuration for neural bridge
 
+use crate::backend::BackendKind;
+use crate::confidence::ConfidenceStrategy;
+use crate::fallback::FallbackConfig;
+use crate::models::ErrorMetric;
+use crate::transform::{InterpolationConfig, PaddingStrategy, PreTransform, ScalerConfig};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -21,11 +26,48 @@ pub struct NeuralBridgeConfig {
     /// Maximum cache entries
     pub max_cache_entries: usize,
     
+    /// Optional prediction cache budget in serialized bytes
+    #[serde(default)]
+    pub max_cache_bytes: Option<usize>,
+    
+    /// Salt folded into prediction cache keys; bump it to invalidate all
+    /// cached predictions after changing weights or preprocessing
+    #[serde(default)]
+    pub cache_version: String,
+    
     /// Models to preload on startup
     pub preload_models: Vec<String>,
     
     /// Performance settings
     pub performance: PerformanceConfig,
+    
+    /// Model selection policy
+    #[serde(default)]
+    pub model_selection: ModelSelectionPolicy,
+    
+    /// Prediction callback delivery settings
+    #[serde(default)]
+    pub callback: CallbackConfig,
+    
+    /// File used to persist model performance stats across restarts
+    #[serde(default)]
+    pub performance_stats_path: Option<String>,
+    
+    /// Interval between performance stats snapshots in seconds
+    #[serde(default = "default_performance_stats_persist_interval_seconds")]
+    pub performance_stats_persist_interval_seconds: u64,
+    
+    /// Error metric used to score realized outcomes
+    #[serde(default)]
+    pub accuracy_metric: ErrorMetric,
+    
+    /// Smoothing factor for the rolling accuracy (weight of the newest outcome)
+    #[serde(default = "default_accuracy_smoothing")]
+    pub accuracy_smoothing: f64,
+    
+    /// Pure-Rust forecasts served while the inference backend is down
+    #[serde(default)]
+    pub fallback: FallbackConfig,
 }
 
 /// NeuralForecast specific configuration
@@ -42,6 +84,44 @@ pub struct NeuralForecastConfig {
     
     /// Maximum batch size
     pub max_batch_size: usize,
+    
+    /// Inference backend serving the models
+    #[serde(default)]
+    pub backend: BackendKind,
+    
+    /// Symbol to model overrides, consulted before the selection policy.
+    /// Keys may use a leading or trailing `*` wildcard (e.g. `*USD`, `X:*`).
+    #[serde(default)]
+    pub symbol_model_overrides: HashMap<String, String>,
+    
+    /// Model used for every prediction, bypassing overrides and the
+    /// selection policy. For tests and canaries.
+    #[serde(default)]
+    pub force_model: Option<String>,
+}
+
+impl NeuralForecastConfig {
+    /// Model routed to a symbol by an override; exact keys win, then the
+    /// longest matching wildcard pattern
+    pub fn model_override(&self, symbol: &str) -> Option<&str> {
+        if let Some(model) = self.symbol_model_overrides.get(symbol) {
+            return Some(model);
+        }
+        
+        self.symbol_model_overrides
+            .iter()
+            .filter(|(pattern, _)| {
+                if let Some(suffix) = pattern.strip_prefix('*') {
+                    symbol.ends_with(suffix)
+                } else if let Some(prefix) = pattern.strip_suffix('*') {
+                    symbol.starts_with(prefix)
+                } else {
+                    false
+                }
+            })
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, model)| model.as_str())
+    }
 }
 
 /// Individual model configuration
@@ -64,6 +144,173 @@ pub struct ModelConfig {
     
     /// Model-specific parameters
     pub parameters: HashMap<String, serde_json::Value>,
+    
+    /// Scaling applied to the price input; the output is inverted back
+    #[serde(default)]
+    pub scaler: ScalerConfig,
+    
+    /// Transform applied to prices before inference (after which any
+    /// scaler is fitted); forecasts are converted back to prices
+    #[serde(default)]
+    pub pre_transform: PreTransform,
+    
+    /// Fewest price points the model accepts; defaults to `input_size`
+    #[serde(default)]
+    pub min_history: Option<usize>,
+    
+    /// Padding applied to inputs shorter than the minimum history instead
+    /// of rejecting them
+    #[serde(default)]
+    pub padding: PaddingStrategy,
+    
+    /// Filling of missing prices and intervals before inference
+    #[serde(default)]
+    pub interpolation: InterpolationConfig,
+    
+    /// Version of the model weights; part of the prediction cache key, so
+    /// bumping it invalidates only this model's cached predictions
+    #[serde(default = "default_model_version")]
+    pub version: String,
+    
+    /// How `confidence` is computed for this model's predictions; see
+    /// `confidence` for what each strategy's value means
+    #[serde(default)]
+    pub confidence: ConfidenceStrategy,
+}
+
+fn default_model_version() -> String {
+    "1.0.0".to_string()
+}
+
+impl ModelConfig {
+    /// Lookback window declared in `parameters["input_size"]`
+    pub fn input_size(&self) -> Option<usize> {
+        self.parameters
+            .get("input_size")
+            .and_then(|value| value.as_u64())
+            .map(|size| size as usize)
+    }
+
+    /// Fewest price points a prediction with this model accepts
+    pub fn min_history_points(&self) -> usize {
+        self.min_history
+            .or_else(|| self.input_size())
+            .unwrap_or(crate::prediction::MIN_HISTORY_POINTS)
+    }
+}
+
+/// Data-driven model selection policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelSelectionPolicy {
+    /// Rules evaluated in order; candidates of all matching rules are considered
+    pub rules: Vec<SelectionRule>,
+    
+    /// Symbol to class mapping (e.g. "SPY" -> "index")
+    pub symbol_classes: HashMap<String, String>,
+    
+    /// Model used when no rule matches
+    pub default_model: String,
+}
+
+/// Single selection rule mapping a context to candidate models
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionRule {
+    /// Minimum horizon (inclusive)
+    pub min_horizon: usize,
+    
+    /// Maximum horizon (inclusive), unbounded if not set
+    pub max_horizon: Option<usize>,
+    
+    /// Symbol classes this rule applies to; empty matches any symbol
+    pub symbol_classes: Vec<String>,
+    
+    /// Features that must be present in the input
+    pub required_features: Vec<String>,
+    
+    /// Candidate models in order of preference
+    pub candidates: Vec<String>,
+}
+
+impl SelectionRule {
+    /// Whether the rule applies to the given context
+    pub fn matches(&self, horizon: usize, symbol_class: Option<&str>, features: &[String]) -> bool {
+        let in_range = horizon >= self.min_horizon
+            && self.max_horizon.map_or(true, |max| horizon <= max);
+        let class_matches = self.symbol_classes.is_empty()
+            || symbol_class.map_or(false, |class| self.symbol_classes.iter().any(|c| c == class));
+        let features_present = self
+            .required_features
+            .iter()
+            .all(|required| features.contains(required));
+        
+        in_range && class_matches && features_present
+    }
+}
+
+impl ModelSelectionPolicy {
+    /// Candidate models for the given context, in rule and preference order
+    pub fn candidates(&self, horizon: usize, symbol: &str, features: &[String]) -> Vec<String> {
+        let symbol_class = self.symbol_classes.get(symbol).map(String::as_str);
+        let mut candidates: Vec<String> = Vec::new();
+        
+        for rule in self.rules.iter().filter(|r| r.matches(horizon, symbol_class, features)) {
+            for model in &rule.candidates {
+                if !candidates.contains(model) {
+                    candidates.push(model.clone());
+                }
+            }
+        }
+        
+        candidates
+    }
+}
+
+impl Default for ModelSelectionPolicy {
+    fn default() -> Self {
+        let rule = |min_horizon, max_horizon, candidates: &[&str]| SelectionRule {
+            min_horizon,
+            max_horizon,
+            symbol_classes: vec![],
+            required_features: vec![],
+            candidates: candidates.iter().map(|c| c.to_string()).collect(),
+        };
+        
+        Self {
+            rules: vec![
+                // Short-term: prefer N-BEATS
+                rule(1, Some(5), &["NBEATS", "TFT"]),
+                // Medium-term: prefer TFT
+                rule(6, Some(20), &["TFT", "LSTM"]),
+                // Long-term: prefer LSTM
+                rule(21, None, &["LSTM", "TFT"]),
+            ],
+            symbol_classes: HashMap::new(),
+            default_model: "TFT".to_string(),
+        }
+    }
+}
+
+/// Webhook callback delivery configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallbackConfig {
+    /// Per-attempt request timeout in milliseconds
+    pub timeout_ms: u64,
+    
+    /// Maximum delivery attempts
+    pub max_attempts: u32,
+    
+    /// Backoff between attempts in milliseconds, multiplied by attempt number
+    pub retry_backoff_ms: u64,
+}
+
+impl Default for CallbackConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 5000,
+            max_attempts: 3,
+            retry_backoff_ms: 500,
+        }
+    }
 }
 
 /// Performance configuration
@@ -75,11 +322,31 @@ pub struct PerformanceConfig {
     /// Enable GPU acceleration
     pub enable_gpu: bool,
     
-    /// Number of worker threads
+    /// Prediction queue workers, and the most inferences run at once
     pub worker_threads: usize,
     
+    /// Maximum number of queued prediction requests
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+    
     /// Enable model compilation
     pub enable_compilation: bool,
+    
+    /// Reject predictions whose effective confidence is below this (0 disables)
+    #[serde(default)]
+    pub min_confidence: f64,
+}
+
+fn default_queue_capacity() -> usize {
+    1024
+}
+
+fn default_performance_stats_persist_interval_seconds() -> u64 {
+    300
+}
+
+fn default_accuracy_smoothing() -> f64 {
+    crate::models::DEFAULT_ACCURACY_SMOOTHING
 }
 
 impl Default for NeuralBridgeConfig {
@@ -97,6 +364,13 @@ impl Default for NeuralBridgeConfig {
                 "input_size".to_string(),
                 serde_json::Value::Number(serde_json::Number::from(168))
             )].iter().cloned().collect(),
+            scaler: ScalerConfig::None,
+            pre_transform: PreTransform::None,
+            min_history: None,
+            padding: PaddingStrategy::None,
+            interpolation: InterpolationConfig::default(),
+            version: default_model_version(),
+            confidence: ConfidenceStrategy::Backend,
         });
         
         // N-BEATS configuration
@@ -113,6 +387,13 @@ impl Default for NeuralBridgeConfig {
                     serde_json::Value::String("seasonality".to_string())
                 ])
             )].iter().cloned().collect(),
+            scaler: ScalerConfig::None,
+            pre_transform: PreTransform::None,
+            min_history: None,
+            padding: PaddingStrategy::None,
+            interpolation: InterpolationConfig::default(),
+            version: default_model_version(),
+            confidence: ConfidenceStrategy::Backend,
         });
         
         // LSTM configuration
@@ -126,6 +407,13 @@ impl Default for NeuralBridgeConfig {
                 "hidden_size".to_string(),
                 serde_json::Value::Number(serde_json::Number::from(128))
             )].iter().cloned().collect(),
+            scaler: ScalerConfig::None,
+            pre_transform: PreTransform::None,
+            min_history: None,
+            padding: PaddingStrategy::None,
+            interpolation: InterpolationConfig::default(),
+            version: default_model_version(),
+            confidence: ConfidenceStrategy::Backend,
         });
         
         Self {
@@ -135,10 +423,15 @@ impl Default for NeuralBridgeConfig {
                 models,
                 default_horizon: 10,
                 max_batch_size: 32,
+                backend: BackendKind::default(),
+                symbol_model_overrides: HashMap::new(),
+                force_model: None,
             },
             cache_size: 1000,
             cache_ttl_seconds: 300, // 5 minutes
             max_cache_entries: 10000,
+            max_cache_bytes: None,
+            cache_version: String::new(),
             preload_models: vec![
                 "TFT".to_string(),
                 "NBEATS".to_string(),
@@ -148,8 +441,17 @@ impl Default for NeuralBridgeConfig {
                 target_inference_ms: 10,
                 enable_gpu: true,
                 worker_threads: 4,
+                queue_capacity: default_queue_capacity(),
                 enable_compilation: true,
+                min_confidence: 0.0,
             },
+            model_selection: ModelSelectionPolicy::default(),
+            callback: CallbackConfig::default(),
+            performance_stats_path: None,
+            performance_stats_persist_interval_seconds: default_performance_stats_persist_interval_seconds(),
+            accuracy_metric: ErrorMetric::default(),
+            accuracy_smoothing: default_accuracy_smoothing(),
+            fallback: FallbackConfig::default(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,100 @@
+//! Error types for neural bridge operations
+
+use pyo3::PyErr;
+use thiserror::Error;
+
+/// Result alias for neural bridge operations
+pub type Result<T> = std::result::Result<T, NeuralBridgeError>;
+
+/// Errors returned by neural bridge operations
+#[derive(Debug, Error)]
+pub enum NeuralBridgeError {
+    /// Model is not present in the configuration
+    #[error("Model {0} not found in configuration")]
+    ModelNotFound(String),
+
+    /// Model is configured but has not been loaded
+    #[error("Model {0} not loaded")]
+    ModelNotLoaded(String),
+
+    /// Model type has no loader
+    #[error("Unsupported model type: {0}")]
+    UnsupportedModelType(String),
+
+    /// Python environment or NeuralForecast import failed
+    #[error("Python initialization failed: {0}")]
+    PythonInitFailed(String),
+
+    /// Python call failed during loading or inference
+    #[error("Python error: {0}")]
+    Python(String),
+
+    /// Non-Python inference backend failed
+    #[error("Backend error: {0}")]
+    Backend(String),
+
+    /// Inference did not complete within the allowed time
+    #[error("Inference timed out after {elapsed_ms}ms (limit: {limit_ms}ms)")]
+    InferenceTimeout { elapsed_ms: u64, limit_ms: u64 },
+
+    /// Prediction queue is at capacity
+    #[error("Prediction queue full (capacity: {0})")]
+    QueueFull(usize),
+
+    /// Worker stopped before producing a response
+    #[error("Prediction worker stopped before responding")]
+    WorkerStopped,
+
+    /// Prediction confidence is below the configured minimum
+    #[error("Low confidence from {model}: {confidence:.4} < {min_confidence:.4}")]
+    LowConfidence {
+        model: String,
+        confidence: f64,
+        min_confidence: f64,
+    },
+
+    /// Input or output failed validation
+    #[error("Validation failed: {0}")]
+    Validation(String),
+
+    /// Persisted state could not be read or written
+    #[error("Persistence error: {0}")]
+    Persistence(String),
+
+    /// Filesystem operation failed
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Result could not be serialized
+    #[error("Serialization failed: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl NeuralBridgeError {
+    /// Whether the operation may succeed if retried
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::Python(_) | Self::InferenceTimeout { .. } | Self::QueueFull(_)
+        )
+    }
+}
+
+impl From<PyErr> for NeuralBridgeError {
+    fn from(err: PyErr) -> Self {
+        Self::Python(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_errors() {
+        assert!(NeuralBridgeError::Python("boom".to_string()).is_transient());
+        assert!(NeuralBridgeError::InferenceTimeout { elapsed_ms: 20, limit_ms: 10 }.is_transient());
+        assert!(!NeuralBridgeError::ModelNotLoaded("TFT".to_string()).is_transient());
+        assert!(!NeuralBridgeError::Validation("bad horizon".to_string()).is_transient());
+    }
+}
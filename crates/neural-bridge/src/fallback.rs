@@ -0,0 +1,113 @@
+//! Pure-Rust forecasts served while the inference backend is unavailable
+
+use crate::{PredictionInput, PredictionResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Forecasting method used in degraded mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FallbackMethod {
+    /// Repeat the last observed value
+    LastValue,
+    /// Extend the least-squares trend of the input window
+    LinearExtrapolation,
+}
+
+impl FallbackMethod {
+    /// Method name recorded in metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FallbackMethod::LastValue => "last_value",
+            FallbackMethod::LinearExtrapolation => "linear_extrapolation",
+        }
+    }
+
+    /// Forecast `horizon` steps past the end of `history`
+    pub fn forecast(&self, history: &[f64], horizon: usize) -> Vec<f64> {
+        let last = history.last().copied().unwrap_or_default();
+        let slope = match self {
+            FallbackMethod::LastValue => 0.0,
+            FallbackMethod::LinearExtrapolation => least_squares_slope(history),
+        };
+        (1..=horizon).map(|step| last + slope * step as f64).collect()
+    }
+}
+
+/// Degraded-mode configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackConfig {
+    /// Serve fallback forecasts when the backend is down instead of failing
+    pub enabled: bool,
+
+    /// Forecasting method
+    pub method: FallbackMethod,
+
+    /// Confidence reported on fallback results
+    pub confidence: f64,
+
+    /// Seconds between attempts to bring an unavailable backend back up
+    #[serde(default = "default_recheck_interval_secs")]
+    pub recheck_interval_secs: u64,
+}
+
+fn default_recheck_interval_secs() -> u64 {
+    30
+}
+
+impl Default for FallbackConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            method: FallbackMethod::LastValue,
+            confidence: 0.1,
+            recheck_interval_secs: default_recheck_interval_secs(),
+        }
+    }
+}
+
+/// Build a fallback result flagged with `metadata["fallback"] = true`
+pub fn predict(config: &FallbackConfig, input: &PredictionInput) -> PredictionResult {
+    let mut metadata = HashMap::new();
+    metadata.insert("fallback".to_string(), serde_json::json!(true));
+    metadata.insert("fallback_method".to_string(), serde_json::json!(config.method.as_str()));
+
+    PredictionResult {
+        model_name: format!("fallback_{}", config.method.as_str()),
+        symbol: input.symbol.clone(),
+        prediction: config.method.forecast(&input.historical_data, input.horizon),
+        confidence: config.confidence,
+        timestamp: chrono::Utc::now(),
+        horizon: input.horizon,
+        metadata,
+    }
+}
+
+/// Slope per step of the least-squares line through the series
+fn least_squares_slope(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let (covariance, variance) = values.iter().enumerate().fold((0.0, 0.0), |(cov, var), (i, y)| {
+        let dx = i as f64 - mean_x;
+        (cov + dx * (y - mean_y), var + dx * dx)
+    });
+    covariance / variance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_forecasts() {
+        let history = [10.0, 12.0, 14.0, 16.0];
+
+        assert_eq!(FallbackMethod::LastValue.forecast(&history, 2), vec![16.0, 16.0]);
+        assert_eq!(FallbackMethod::LinearExtrapolation.forecast(&history, 2), vec![18.0, 20.0]);
+        assert_eq!(FallbackMethod::LinearExtrapolation.forecast(&[5.0], 1), vec![5.0]);
+    }
+}
@@ -0,0 +1,277 @@
+//! gRPC service exposing a shared `NeuralBridgeManager`
+//!
+//! Messages are defined in `proto/neural_bridge.proto`. Timestamps travel as
+//! Unix milliseconds and metadata values as JSON strings.
+
+use crate::{models::ModelStats, NeuralBridgeError, NeuralBridgeManager, PredictionInput, PredictionResult};
+use chrono::TimeZone;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::error;
+
+/// Generated protobuf messages and service traits
+pub mod proto {
+    tonic::include_proto!("neural_bridge");
+}
+
+pub use proto::neural_bridge_server::NeuralBridgeServer;
+
+/// gRPC handlers delegating to one manager shared across requests
+#[derive(Clone)]
+pub struct NeuralBridgeService {
+    manager: Arc<NeuralBridgeManager>,
+}
+
+impl NeuralBridgeService {
+    /// Serve predictions from `manager`
+    pub fn new(manager: Arc<NeuralBridgeManager>) -> Self {
+        Self { manager }
+    }
+
+    /// Wrap the service for `tonic::transport::Server::add_service`
+    pub fn into_server(self) -> NeuralBridgeServer<Self> {
+        NeuralBridgeServer::new(self)
+    }
+
+    async fn predict_one(&self, request: proto::PredictRequest) -> Result<PredictionResult, Status> {
+        let input = request
+            .input
+            .ok_or_else(|| Status::invalid_argument("Missing prediction input"))?;
+        let input = PredictionInput::try_from(input)?;
+        Ok(self
+            .manager
+            .predict_with_model(input, request.model_name.as_deref(), crate::CacheMode::ReadWrite)
+            .await?)
+    }
+}
+
+#[tonic::async_trait]
+impl proto::neural_bridge_server::NeuralBridge for NeuralBridgeService {
+    async fn predict(
+        &self,
+        request: Request<proto::PredictRequest>,
+    ) -> Result<Response<proto::PredictionResult>, Status> {
+        let result = self.predict_one(request.into_inner()).await?;
+        Ok(Response::new(result.into()))
+    }
+
+    async fn batch_predict(
+        &self,
+        request: Request<proto::BatchPredictRequest>,
+    ) -> Result<Response<proto::BatchPredictResponse>, Status> {
+        let mut items = Vec::new();
+        for request in request.into_inner().requests {
+            let outcome = match self.predict_one(request).await {
+                Ok(result) => proto::batch_item::Outcome::Result(result.into()),
+                Err(status) => {
+                    error!("Batch prediction failed: {}", status.message());
+                    proto::batch_item::Outcome::Error(status.message().to_string())
+                }
+            };
+            items.push(proto::BatchItem { outcome: Some(outcome) });
+        }
+        Ok(Response::new(proto::BatchPredictResponse { items }))
+    }
+
+    async fn get_model_stats(
+        &self,
+        request: Request<proto::GetModelStatsRequest>,
+    ) -> Result<Response<proto::ModelStats>, Status> {
+        let stats = self.manager.get_model_stats(&request.into_inner().model_name).await?;
+        Ok(Response::new(stats.into()))
+    }
+}
+
+impl From<NeuralBridgeError> for Status {
+    fn from(err: NeuralBridgeError) -> Self {
+        let message = err.to_string();
+        match err {
+            NeuralBridgeError::Validation(_) | NeuralBridgeError::UnsupportedModelType(_) => {
+                Status::invalid_argument(message)
+            }
+            NeuralBridgeError::ModelNotFound(_) => Status::not_found(message),
+            NeuralBridgeError::ModelNotLoaded(_) | NeuralBridgeError::LowConfidence { .. } => {
+                Status::failed_precondition(message)
+            }
+            NeuralBridgeError::PythonInitFailed(_)
+            | NeuralBridgeError::Python(_)
+            | NeuralBridgeError::WorkerStopped => Status::unavailable(message),
+            NeuralBridgeError::InferenceTimeout { .. } => Status::deadline_exceeded(message),
+            NeuralBridgeError::QueueFull(_) => Status::resource_exhausted(message),
+            NeuralBridgeError::Backend(_)
+            | NeuralBridgeError::Persistence(_)
+            | NeuralBridgeError::Io(_)
+            | NeuralBridgeError::Serialization(_) => Status::internal(message),
+        }
+    }
+}
+
+impl TryFrom<proto::PredictionInput> for PredictionInput {
+    type Error = Status;
+
+    fn try_from(input: proto::PredictionInput) -> Result<Self, Status> {
+        let timestamps = input
+            .timestamps_ms
+            .iter()
+            .map(|&ms| {
+                chrono::Utc
+                    .timestamp_millis_opt(ms)
+                    .single()
+                    .ok_or_else(|| Status::invalid_argument(format!("Invalid timestamp: {}", ms)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(PredictionInput {
+            symbol: input.symbol,
+            historical_data: input.historical_data,
+            timestamps,
+            features: input
+                .features
+                .into_iter()
+                .map(|(name, series)| (name, series.values))
+                .collect(),
+            horizon: input.horizon as usize,
+            seed: input.seed,
+            resampling: None,
+        })
+    }
+}
+
+impl From<PredictionInput> for proto::PredictionInput {
+    fn from(input: PredictionInput) -> Self {
+        Self {
+            symbol: input.symbol,
+            historical_data: input.historical_data,
+            timestamps_ms: input.timestamps.iter().map(|ts| ts.timestamp_millis()).collect(),
+            features: input
+                .features
+                .into_iter()
+                .map(|(name, values)| (name, proto::FeatureSeries { values }))
+                .collect(),
+            horizon: input.horizon as u32,
+            seed: input.seed,
+        }
+    }
+}
+
+impl From<PredictionResult> for proto::PredictionResult {
+    fn from(result: PredictionResult) -> Self {
+        Self {
+            model_name: result.model_name,
+            symbol: result.symbol,
+            prediction: result.prediction,
+            confidence: result.confidence,
+            timestamp_ms: result.timestamp.timestamp_millis(),
+            horizon: result.horizon as u32,
+            metadata: result
+                .metadata
+                .into_iter()
+                .map(|(key, value)| (key, value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::PredictionResult> for PredictionResult {
+    type Error = Status;
+
+    fn try_from(result: proto::PredictionResult) -> Result<Self, Status> {
+        let metadata = result
+            .metadata
+            .into_iter()
+            .map(|(key, value)| {
+                serde_json::from_str(&value)
+                    .map(|value| (key, value))
+                    .map_err(|e| Status::invalid_argument(format!("Invalid metadata JSON: {}", e)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(PredictionResult {
+            model_name: result.model_name,
+            symbol: result.symbol,
+            prediction: result.prediction,
+            confidence: result.confidence,
+            timestamp: chrono::Utc
+                .timestamp_millis_opt(result.timestamp_ms)
+                .single()
+                .ok_or_else(|| Status::invalid_argument("Invalid result timestamp"))?,
+            horizon: result.horizon as usize,
+            metadata,
+        })
+    }
+}
+
+impl From<ModelStats> for proto::ModelStats {
+    fn from(stats: ModelStats) -> Self {
+        Self {
+            model_name: stats.model_name,
+            accuracy: stats.accuracy,
+            average_inference_time_ms: stats.average_inference_time_ms,
+            total_predictions: stats.total_predictions,
+            successful_predictions: stats.successful_predictions,
+            failed_predictions: stats.failed_predictions,
+            last_used_ms: stats.last_used.timestamp_millis(),
+            memory_usage_mb: stats.memory_usage_mb,
+            outcomes_recorded: stats.outcomes_recorded,
+            directional_accuracy: stats.directional_accuracy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::proto::neural_bridge_server::NeuralBridge;
+    use super::*;
+    use crate::test_support::{aapl_input, mock_manager};
+
+    async fn service() -> NeuralBridgeService {
+        NeuralBridgeService::new(Arc::new(mock_manager().await))
+    }
+
+    fn input(horizon: usize) -> proto::PredictionInput {
+        PredictionInput {
+            seed: Some(7),
+            ..aapl_input(30, horizon)
+        }
+        .into()
+    }
+
+    #[tokio::test]
+    async fn test_predict_and_batch_report_per_item_errors() {
+        let service = service().await;
+
+        let result = service
+            .predict(Request::new(proto::PredictRequest {
+                input: Some(input(3)),
+                model_name: Some("NBEATS".to_string()),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(result.model_name, "NBEATS");
+        assert_eq!(PredictionResult::try_from(result).unwrap().prediction.len(), 3);
+
+        let batch = service
+            .batch_predict(Request::new(proto::BatchPredictRequest {
+                requests: vec![
+                    proto::PredictRequest { input: Some(input(5)), model_name: None },
+                    proto::PredictRequest { input: None, model_name: None },
+                ],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(batch.items[0].outcome, Some(proto::batch_item::Outcome::Result(_))));
+        assert!(matches!(batch.items[1].outcome, Some(proto::batch_item::Outcome::Error(_))));
+    }
+
+    #[test]
+    fn test_error_status_codes() {
+        let code = |err: NeuralBridgeError| Status::from(err).code();
+
+        assert_eq!(code(NeuralBridgeError::Validation("bad".to_string())), tonic::Code::InvalidArgument);
+        assert_eq!(code(NeuralBridgeError::ModelNotFound("X".to_string())), tonic::Code::NotFound);
+        assert_eq!(code(NeuralBridgeError::PythonInitFailed("no".to_string())), tonic::Code::Unavailable);
+        assert_eq!(code(NeuralBridgeError::QueueFull(8)), tonic::Code::ResourceExhausted);
+    }
+}
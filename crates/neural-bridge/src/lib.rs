@@ -14,18 +14,46 @@
 //! - LSTM: Long-term dependency modeling
 //! - 30+ models via NeuralForecast
 
-use anyhow::Result;
-use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, instrument, warn, Instrument};
 
+pub mod backend;
+pub mod backtest;
+pub mod baselines;
 pub mod cache;
+pub mod callback;
 pub mod config;
+pub mod confidence;
+pub mod error;
+pub mod fallback;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "live")]
+pub mod live;
+pub mod logging;
 pub mod models;
 pub mod neuralforecast;
+pub mod pool;
 pub mod prediction;
+pub mod queue;
+#[cfg(feature = "http")]
+pub mod rest;
+#[cfg(test)]
+mod test_support;
+pub mod transform;
+
+pub use error::{NeuralBridgeError, Result};
+
+use prediction::{PredictionRequest, PredictionResponse, PredictionStatus};
+
+/// Items buffered per streaming prediction before the producer waits
+const PREDICTION_STREAM_BUFFER: usize = 64;
+
+/// Metadata describing the call that produced a result rather than the forecast
+const RUNTIME_METADATA_KEYS: [&str; 3] = ["inference_time_ms", "backend", "device"];
 
 /// Prediction result from neural models
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,42 +75,162 @@ pub struct PredictionInput {
     pub timestamps: Vec<chrono::DateTime<chrono::Utc>>,
     pub features: HashMap<String, Vec<f64>>,
     pub horizon: usize,
+    /// RNG seed applied before inference. Results are only reproducible on
+    /// the same hardware and library versions.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Set by `prediction::resample`; reported in result metadata
+    #[serde(default)]
+    pub resampling: Option<prediction::Resampling>,
+}
+
+impl PredictionInput {
+    /// Start building an input whose series are validated on `build()`
+    pub fn builder(symbol: impl Into<String>) -> prediction::PredictionInputBuilder {
+        prediction::PredictionInputBuilder::new(symbol)
+    }
+
+    /// Names of the features available in this input, including price
+    pub fn available_features(&self) -> Vec<String> {
+        let mut features: Vec<String> = self.features.keys().cloned().collect();
+        if !features.iter().any(|f| f == "price") {
+            features.push("price".to_string());
+        }
+        features
+    }
+}
+
+/// How a prediction uses the prediction cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CacheMode {
+    /// Serve fresh cached results and store new ones
+    ReadWrite,
+    /// Skip the lookup but store the result
+    Refresh,
+    /// Neither read nor store, e.g. for backtest windows
+    Off,
+}
+
+impl CacheMode {
+    /// `Refresh` when a request asks to bypass the cache
+    fn bypassing(bypass_cache: bool) -> Self {
+        if bypass_cache {
+            CacheMode::Refresh
+        } else {
+            CacheMode::ReadWrite
+        }
+    }
+}
+
+/// Health status of the neural bridge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub python_ready: bool,
+    pub loaded_models: usize,
+    pub model_cache_capacity: usize,
+    pub prediction_cache_entries: usize,
+    pub prediction_cache_capacity: usize,
+    pub missing_preload_models: Vec<String>,
+    /// Fallback forecasts are being served in place of the backend
+    #[serde(default)]
+    pub fallback_active: bool,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// Neural bridge manager
 pub struct NeuralBridgeManager {
     config: config::NeuralBridgeConfig,
-    python_interpreter: Python,
     model_cache: cache::ModelCache,
+    model_selector: parking_lot::RwLock<models::ModelSelector>,
     neuralforecast: neuralforecast::NeuralForecastClient,
-    prediction_cache: RwLock<HashMap<String, PredictionResult>>,
+    prediction_cache: RwLock<cache::PredictionCache>,
+    queue: Arc<queue::PredictionQueue>,
+    http_client: reqwest::Client,
+    /// Whether the inference backend initialized successfully
+    backend_ready: std::sync::atomic::AtomicBool,
+    /// When the backend was last found unavailable, in Unix milliseconds
+    backend_checked_ms: std::sync::atomic::AtomicI64,
+    /// Cache key salt, seeded from `config.cache_version`
+    cache_version: parking_lot::RwLock<String>,
+    /// Per-model overrides of the configured confidence strategy
+    confidence_estimators: parking_lot::RwLock<HashMap<String, Arc<dyn confidence::ConfidenceEstimator>>>,
 }
 
 impl NeuralBridgeManager {
     /// Create new neural bridge manager
     pub fn new(config: config::NeuralBridgeConfig) -> Result<Self> {
-        pyo3::prepare_freethreaded_python();
-        
-        let python_interpreter = Python::acquire_gil();
-        let model_cache = cache::ModelCache::new(config.cache_size);
         let neuralforecast = neuralforecast::NeuralForecastClient::new(&config)?;
-        let prediction_cache = RwLock::new(HashMap::new());
+        Self::with_client(config, neuralforecast)
+    }
+
+    /// Create a manager around an explicit inference backend
+    pub fn with_backend(
+        config: config::NeuralBridgeConfig,
+        backend: Box<dyn backend::InferenceBackend>,
+    ) -> Result<Self> {
+        let neuralforecast = neuralforecast::NeuralForecastClient::with_backend(&config, backend);
+        Self::with_client(config, neuralforecast)
+    }
+
+    /// Assemble the manager around a client; the GIL is only ever acquired
+    /// locally by the Python backend
+    fn with_client(
+        config: config::NeuralBridgeConfig,
+        neuralforecast: neuralforecast::NeuralForecastClient,
+    ) -> Result<Self> {
+        let model_cache = cache::ModelCache::new(config.cache_size);
+        let model_selector = parking_lot::RwLock::new(models::ModelSelector::with_tracker(
+            models::ModelPerformanceTracker::new()
+                .with_error_metric(config.accuracy_metric, config.accuracy_smoothing),
+        ));
+        let prediction_cache = RwLock::new(cache::PredictionCache::new(
+            config.max_cache_entries,
+            config.max_cache_bytes,
+        ));
+        let queue = Arc::new(queue::PredictionQueue::new(config.performance.queue_capacity));
+        let cache_version = parking_lot::RwLock::new(config.cache_version.clone());
         
         Ok(Self {
             config,
-            python_interpreter,
             model_cache,
+            model_selector,
             neuralforecast,
             prediction_cache,
+            queue,
+            http_client: reqwest::Client::new(),
+            backend_ready: std::sync::atomic::AtomicBool::new(false),
+            backend_checked_ms: std::sync::atomic::AtomicI64::new(0),
+            cache_version,
+            confidence_estimators: parking_lot::RwLock::new(HashMap::new()),
         })
     }
 
-    /// Initialize Python environment and load models
-    pub async fn initialize(&mut self) -> Result<()> {
+    /// Initialize Python environment and load models.
+    ///
+    /// Takes `&self` so a manager already shared behind an `Arc` can be
+    /// initialized or have models reloaded from request handlers.
+    pub async fn initialize(&self) -> Result<()> {
         info!("Initializing neural bridge");
         
-        // Initialize NeuralForecast
-        self.neuralforecast.initialize().await?;
+        self.restore_performance_stats();
+        
+        // Initialize NeuralForecast; with a fallback configured the bridge
+        // stays up in degraded mode instead of failing
+        match self.neuralforecast.initialize().await {
+            Ok(()) => self.backend_ready.store(true, std::sync::atomic::Ordering::Relaxed),
+            Err(e) if self.config.fallback.enabled => {
+                self.backend_checked_ms
+                    .store(chrono::Utc::now().timestamp_millis(), std::sync::atomic::Ordering::Relaxed);
+                error!(
+                    "Inference backend unavailable, serving {} fallback forecasts: {}",
+                    self.config.fallback.method.as_str(),
+                    e
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        }
         
         // Preload configured models
         for model_name in &self.config.preload_models {
@@ -93,85 +241,581 @@ impl NeuralBridgeManager {
         Ok(())
     }
 
+    /// Persist performance stats before the manager is dropped
+    pub fn shutdown(&self) -> Result<()> {
+        info!("Shutting down neural bridge");
+        self.persist_performance_stats()
+    }
+
+    /// Spawn a task persisting performance stats on the configured interval
+    pub fn start_stats_persistence(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        let period = std::time::Duration::from_secs(
+            manager.config.performance_stats_persist_interval_seconds.max(1),
+        );
+        
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = manager.persist_performance_stats() {
+                    error!("Failed to persist performance stats: {:?}", e);
+                }
+            }
+        })
+    }
+
+    /// Write performance stats to the configured path, if any
+    fn persist_performance_stats(&self) -> Result<()> {
+        if let Some(path) = &self.config.performance_stats_path {
+            self.model_selector.read().get_performance_tracker().save(path)?;
+            debug!("Persisted performance stats to {}", path);
+        }
+        Ok(())
+    }
+
+    /// Reload performance stats from the configured path, if present
+    fn restore_performance_stats(&self) {
+        let Some(path) = &self.config.performance_stats_path else {
+            return;
+        };
+        if !std::path::Path::new(path).exists() {
+            return;
+        }
+        
+        match models::ModelPerformanceTracker::load(path) {
+            Ok(tracker) => {
+                info!("Restored performance stats from {}", path);
+                *self.model_selector.write().get_performance_tracker_mut() = tracker
+                    .with_error_metric(self.config.accuracy_metric, self.config.accuracy_smoothing);
+            }
+            Err(e) => warn!("Ignoring performance stats at {}: {:?}", path, e),
+        }
+    }
+
     /// Load a specific model
-    pub async fn load_model(&mut self, model_name: &str) -> Result<()> {
+    pub async fn load_model(&self, model_name: &str) -> Result<()> {
         info!("Loading model: {}", model_name);
         
         let model = self.neuralforecast.load_model(model_name).await?;
+        let reloaded = self.model_cache.contains(model_name);
         self.model_cache.insert(model_name.to_string(), model);
         
+        // Predictions from the replaced weights are stale
+        if reloaded {
+            let dropped = self.invalidate_predictions_for_model(model_name).await;
+            info!("Reloaded model {}, dropped {} cached predictions", model_name, dropped);
+        }
+        
+        if self.config.performance.enable_compilation {
+            match self.neuralforecast.warmup(model_name).await {
+                Ok(elapsed) => info!("Warmed up model {} in {}ms", model_name, elapsed.as_millis()),
+                Err(e) => warn!("Warmup prediction for model {} failed: {}", model_name, e),
+            }
+        }
+        
         Ok(())
     }
 
     /// Generate prediction for given input
     pub async fn predict(&self, input: PredictionInput) -> Result<PredictionResult> {
+        self.predict_with_model(input, None, CacheMode::ReadWrite).await
+    }
+
+    /// `predict` without serving from the cache, e.g. after a market event;
+    /// the fresh result replaces any cached one
+    pub async fn predict_fresh(&self, input: PredictionInput) -> Result<PredictionResult> {
+        self.predict_with_model(input, None, CacheMode::Refresh).await
+    }
+
+    /// Synchronous `predict` for callers outside an async context, such as
+    /// scripts and CLI tools. Runs on a temporary current-thread runtime;
+    /// calling it from inside a runtime is an error.
+    pub fn predict_blocking(&self, input: PredictionInput) -> Result<PredictionResult> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(NeuralBridgeError::Backend(
+                "predict_blocking called from an async context; use predict".to_string(),
+            ));
+        }
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(self.predict(input))
+    }
+
+    /// Stream a prediction as the model produces it.
+    ///
+    /// Autoregressive backends yield each step as a `Partial` item; others
+    /// yield the whole forecast in one. The last item is `Complete` with the
+    /// full result, which is also cached. Inference runs on the blocking
+    /// pool so the runtime is not stalled while streaming.
+    pub fn predict_stream(
+        self: &Arc<Self>,
+        input: PredictionInput,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<prediction::PredictionStreamItem>>> {
+        prediction::PredictionValidator::validate_input(&input)?;
+        let model_name = self.select_best_model(&input)?;
+        self.validate_history(&input, &model_name)?;
+        if !self.model_cache.contains(&model_name) {
+            return Err(NeuralBridgeError::ModelNotLoaded(model_name));
+        }
+        
+        let (tx, rx) = tokio::sync::mpsc::channel(PREDICTION_STREAM_BUFFER);
+        let cache_key = self.generate_cache_key(&input, &model_name);
+        let manager = Arc::clone(self);
+        
+        tokio::spawn(async move {
+            let start_time = std::time::Instant::now();
+            let step_tx = tx.clone();
+            let blocking_manager = Arc::clone(&manager);
+            let blocking_model = model_name.clone();
+            
+            let outcome = manager.neuralforecast.pool().run(move || {
+                let mut next_step = 0;
+                blocking_manager.neuralforecast.predict_streaming(&input, &blocking_model, &mut |values| {
+                    let item = prediction::PredictionStreamItem::Partial {
+                        start_step: next_step,
+                        values: values.to_vec(),
+                    };
+                    next_step += values.len();
+                    // A dropped receiver only stops delivery; inference completes
+                    let _ = step_tx.blocking_send(Ok(item));
+                })
+                .map(|mut result| {
+                    blocking_manager.apply_confidence(&input, &mut result);
+                    result
+                })
+            })
+            .await
+            .and_then(|outcome| outcome);
+            
+            manager.model_selector.write().get_performance_tracker_mut().record_prediction(
+                &model_name,
+                start_time.elapsed().as_secs_f64() * 1000.0,
+                outcome.is_ok(),
+            );
+            
+            let item = match outcome {
+                Ok(mut result) => {
+                    manager.cache_prediction(cache_key, result.clone()).await;
+                    manager.stamp_runtime(&mut result, false, start_time.elapsed());
+                    manager.check_confidence(result).map(prediction::PredictionStreamItem::Complete)
+                }
+                Err(e) => Err(e),
+            };
+            let _ = tx.send(item).await;
+        });
+        
+        Ok(rx)
+    }
+
+    /// Handle a prediction request, honoring its model preference
+    pub async fn predict_request(&self, request: PredictionRequest) -> PredictionResponse {
+        self.process_request(request, 0).await
+    }
+
+    /// Spawn `worker_threads` workers serving the prediction queue by priority
+    pub fn start_workers(self: &Arc<Self>) -> Vec<tokio::task::JoinHandle<()>> {
+        (0..self.config.performance.worker_threads.max(1))
+            .map(|worker_id| {
+                let manager = Arc::clone(self);
+                tokio::spawn(async move {
+                    debug!("Prediction worker {} started", worker_id);
+                    loop {
+                        let queued = manager.queue.pop().await;
+                        let queue_time_ms = queued.enqueued_at.elapsed().as_millis() as u64;
+                        let response = manager.process_request(queued.request, queue_time_ms).await;
+                        if queued.responder.send(response).is_err() {
+                            debug!("Prediction requester dropped before completion");
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Queue a request for the worker pool and wait for its response.
+    ///
+    /// Requires `start_workers` to have been called.
+    pub async fn submit(&self, request: PredictionRequest) -> Result<PredictionResponse> {
+        let receiver = self.queue.push(request)?;
+        receiver.await.map_err(|_| NeuralBridgeError::WorkerStopped)
+    }
+
+    /// Queue a request and return immediately with its request id.
+    ///
+    /// The response is POSTed as JSON to `callback_url` when the
+    /// prediction finishes. Requires `start_workers` to have been called.
+    pub fn submit_async(&self, mut request: PredictionRequest) -> Result<String> {
+        if request.request_id.is_empty() {
+            request.request_id = uuid::Uuid::new_v4().to_string();
+        }
+        let request_id = request.request_id.clone();
+        let callback_url = request.callback_url.clone();
+        
+        let receiver = self.queue.push(request)?;
+        let client = self.http_client.clone();
+        let callback_config = self.config.callback.clone();
+        let pending_id = request_id.clone();
+        
+        let span = tracing::info_span!("callback", request_id = %request_id);
+        tokio::spawn(
+            async move {
+                let Ok(response) = receiver.await else {
+                    error!("Prediction {} dropped before completion", pending_id);
+                    return;
+                };
+                
+                match callback_url {
+                    Some(url) => callback::deliver(&client, &url, &response, &callback_config).await,
+                    None => warn!("Prediction {} completed without a callback URL", pending_id),
+                }
+            }
+            .instrument(span),
+        );
+        
+        Ok(request_id)
+    }
+
+    /// Number of requests waiting for a worker
+    pub fn queue_len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Run a request and build its response
+    #[instrument(skip_all, fields(request_id = %request.request_id))]
+    async fn process_request(&self, request: PredictionRequest, queue_time_ms: u64) -> PredictionResponse {
+        let start_time = std::time::Instant::now();
+        let outcome = self
+            .predict_with_model(
+                request.input.clone(),
+                request.model_preference.as_deref(),
+                CacheMode::bypassing(request.bypass_cache),
+            )
+            .await;
+        let processing_time_ms = start_time.elapsed().as_millis() as u64;
+        
+        match outcome {
+            Ok(result) => PredictionResponse {
+                result,
+                request_id: request.request_id,
+                processing_time_ms,
+                queue_time_ms,
+                status: PredictionStatus::Success,
+            },
+            Err(e) => {
+                error!("Prediction request {} failed: {:?}", request.request_id, e);
+                PredictionResponse::failed(&request, &e, processing_time_ms, queue_time_ms)
+            }
+        }
+    }
+
+    /// Generate prediction, using the given model instead of automatic selection when set
+    #[instrument(skip_all, fields(symbol = %input.symbol, horizon = input.horizon, model))]
+    async fn predict_with_model(
+        &self,
+        input: PredictionInput,
+        model_preference: Option<&str>,
+        cache_mode: CacheMode,
+    ) -> Result<PredictionResult> {
         let start_time = std::time::Instant::now();
+        prediction::PredictionValidator::validate_input(&input)?;
+        
+        let model_name = match model_preference {
+            Some(model_name) => model_name.to_string(),
+            None => self.select_best_model(&input)?,
+        };
+        tracing::Span::current().record("model", model_name.as_str());
+        
+        // Fill gaps once so the fallback, confidence and cache key all see
+        // the same series as the model; every result reports how the input
+        // was preprocessed
+        let interpolation = self
+            .config
+            .neuralforecast
+            .models
+            .get(&model_name)
+            .map(|model| model.interpolation)
+            .unwrap_or_default();
+        let (input, interpolated) = interpolation.apply_owned(input)?;
+        let resampling = input.resampling.clone();
+        let stamp_preprocessing = |result: &mut PredictionResult| {
+            if interpolation.method != transform::InterpolationMethod::None {
+                result.metadata.insert(
+                    "interpolation".to_string(),
+                    serde_json::json!({
+                        "method": interpolation.method.as_str(),
+                        "filled_points": interpolated,
+                    }),
+                );
+            }
+            if let Some(resampling) = &resampling {
+                result.metadata.insert("resampling".to_string(), serde_json::json!(resampling));
+            }
+        };
+        
+        self.validate_history(&input, &model_name)?;
+        
+        if self.config.fallback.enabled && !self.recover_backend().await {
+            let mut result = self.fallback_prediction(&input);
+            stamp_preprocessing(&mut result);
+            self.stamp_runtime(&mut result, true, start_time.elapsed());
+            return Ok(result);
+        }
         
         // Check cache first
-        let cache_key = self.generate_cache_key(&input);
-        if let Some(cached_result) = self.get_cached_prediction(&cache_key).await {
+        let cache_key = self.generate_cache_key(&input, &model_name);
+        if cache_mode != CacheMode::ReadWrite {
+            debug!("Bypassing cache for {}", input.symbol);
+        } else if let Some(mut cached_result) = self.get_cached_prediction(&cache_key).await {
             debug!("Using cached prediction for {}", input.symbol);
-            return Ok(cached_result);
+            self.stamp_runtime(&mut cached_result, false, start_time.elapsed());
+            return self.check_confidence(cached_result);
         }
         
-        // Get model from cache or load it
-        let model_name = self.select_best_model(&input)?;
-        
         if !self.model_cache.contains(&model_name) {
             warn!("Model {} not loaded, loading now", model_name);
             // Note: In async context, we'd need to handle this differently
             // For now, return an error
-            return Err(anyhow::anyhow!("Model {} not loaded", model_name));
+            return Err(NeuralBridgeError::ModelNotLoaded(model_name));
         }
         
         // Generate prediction
-        let prediction_result = self.neuralforecast.predict(&input, &model_name).await?;
+        let outcome = self.neuralforecast.predict(&input, &model_name).await;
+        
+        let elapsed = start_time.elapsed();
+        self.model_selector
+            .write()
+            .get_performance_tracker_mut()
+            .record_prediction(&model_name, elapsed.as_secs_f64() * 1000.0, outcome.is_ok());
+        let mut prediction_result = match outcome {
+            Err(e @ (NeuralBridgeError::Python(_)
+            | NeuralBridgeError::PythonInitFailed(_)
+            | NeuralBridgeError::Backend(_)))
+                if self.config.fallback.enabled =>
+            {
+                warn!("{} failed, serving fallback forecast: {}", model_name, e);
+                let mut result = self.fallback_prediction(&input);
+                stamp_preprocessing(&mut result);
+                self.stamp_runtime(&mut result, true, start_time.elapsed());
+                return Ok(result);
+            }
+            outcome => outcome?,
+        };
+        stamp_preprocessing(&mut prediction_result);
+        self.apply_confidence(&input, &mut prediction_result);
         
         // Cache the result
-        self.cache_prediction(cache_key, prediction_result.clone()).await;
+        if cache_mode != CacheMode::Off {
+            self.cache_prediction(cache_key, prediction_result.clone()).await;
+        }
         
-        let elapsed = start_time.elapsed();
         if elapsed.as_millis() > 10 {
             warn!("Prediction took {}ms (target: <10ms)", elapsed.as_millis());
         }
         
         debug!("Prediction completed in {}μs", elapsed.as_micros());
-        Ok(prediction_result)
+        self.stamp_runtime(&mut prediction_result, false, elapsed);
+        self.check_confidence(prediction_result)
     }
 
-    /// Select the best model for given input
-    fn select_best_model(&self, input: &PredictionInput) -> Result<String> {
-        // Model selection logic based on:
-        // - Data characteristics
-        // - Prediction horizon
-        // - Symbol type
-        // - Available features
+    /// Report this call's latency and what served it, under
+    /// `RUNTIME_METADATA_KEYS`. Applied after caching so a cached entry
+    /// never carries the timing of the call that stored it.
+    fn stamp_runtime(&self, result: &mut PredictionResult, fallback: bool, elapsed: std::time::Duration) {
+        let (backend, device) = if fallback {
+            ("fallback", "cpu")
+        } else {
+            (self.neuralforecast.backend_name(), self.neuralforecast.device())
+        };
+        result.metadata.insert(
+            "inference_time_ms".to_string(),
+            serde_json::json!(elapsed.as_secs_f64() * 1000.0),
+        );
+        result.metadata.insert("backend".to_string(), serde_json::json!(backend));
+        result.metadata.insert("device".to_string(), serde_json::json!(device));
+    }
+
+    /// Whether the backend is ready, re-initializing an unavailable one at
+    /// most once per `fallback.recheck_interval_secs`
+    async fn recover_backend(&self) -> bool {
+        use std::sync::atomic::Ordering;
         
-        match input.horizon {
-            1..=5 => {
-                // Short-term predictions: use N-BEATS
-                Ok("NBEATS".to_string())
+        if self.backend_ready.load(Ordering::Relaxed) {
+            return true;
+        }
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let checked_ms = self.backend_checked_ms.load(Ordering::Relaxed);
+        let interval_ms = self.config.fallback.recheck_interval_secs.saturating_mul(1000) as i64;
+        if now_ms - checked_ms < interval_ms
+            || self
+                .backend_checked_ms
+                .compare_exchange(checked_ms, now_ms, Ordering::Relaxed, Ordering::Relaxed)
+                .is_err()
+        {
+            return false;
+        }
+        
+        let probe = async {
+            self.neuralforecast.initialize().await?;
+            if !self.neuralforecast.health_check().await? {
+                return Err(NeuralBridgeError::Backend("health check failed".to_string()));
+            }
+            for model_name in &self.config.preload_models {
+                self.load_model(model_name).await?;
             }
-            6..=20 => {
-                // Medium-term predictions: use TFT
-                Ok("TFT".to_string())
+            Ok::<_, NeuralBridgeError>(())
+        };
+        match probe.await {
+            Ok(()) => {
+                info!("Inference backend recovered, leaving fallback mode");
+                self.backend_ready.store(true, Ordering::Relaxed);
+                true
             }
-            21.. => {
-                // Long-term predictions: use LSTM
-                Ok("LSTM".to_string())
+            Err(e) => {
+                debug!("Inference backend still unavailable: {}", e);
+                false
             }
-            _ => Err(anyhow::anyhow!("Invalid prediction horizon: {}", input.horizon)),
         }
     }
 
+    /// Degraded-mode forecast; not cached and exempt from `min_confidence`
+    /// since it is flagged with `metadata["fallback"]`
+    fn fallback_prediction(&self, input: &PredictionInput) -> PredictionResult {
+        debug!("Serving fallback forecast for {}", input.symbol);
+        fallback::predict(&self.config.fallback, input)
+    }
+
+    /// Use a custom confidence estimator for a model in place of its
+    /// configured `confidence` strategy
+    pub fn set_confidence_estimator(
+        &self,
+        model_name: &str,
+        estimator: Arc<dyn confidence::ConfidenceEstimator>,
+    ) {
+        self.confidence_estimators.write().insert(model_name.to_string(), estimator);
+    }
+
+    /// Replace the backend's confidence with the model's estimator, if any
+    fn apply_confidence(&self, input: &PredictionInput, result: &mut PredictionResult) {
+        let custom = self.confidence_estimators.read().get(&result.model_name).cloned();
+        let strategy = self
+            .config
+            .neuralforecast
+            .models
+            .get(&result.model_name)
+            .map(|model| model.confidence)
+            .unwrap_or_default();
+        let estimator: &dyn confidence::ConfidenceEstimator = match &custom {
+            Some(estimator) => estimator.as_ref(),
+            None => &strategy,
+        };
+        
+        let stats = self
+            .model_selector
+            .read()
+            .get_performance_tracker()
+            .get_stats(&result.model_name)
+            .cloned();
+        if let Some(confidence) = estimator.estimate(input, result, stats.as_ref()) {
+            result.confidence = confidence.clamp(0.0, 1.0);
+        }
+        result.metadata.insert(
+            "confidence_strategy".to_string(),
+            serde_json::json!(estimator.name()),
+        );
+    }
+
+    /// Reject results below the configured minimum confidence
+    fn check_confidence(&self, result: PredictionResult) -> Result<PredictionResult> {
+        let min_confidence = self.config.performance.min_confidence;
+        let confidence = prediction::effective_confidence(&result);
+        
+        if confidence < min_confidence {
+            warn!(
+                "Rejecting {} prediction for {}: confidence {:.4} below {:.4}",
+                result.model_name, result.symbol, confidence, min_confidence
+            );
+            return Err(NeuralBridgeError::LowConfidence {
+                model: result.model_name,
+                confidence,
+                min_confidence,
+            });
+        }
+        
+        Ok(result)
+    }
+
+    /// Select the best model for given input
+    fn select_best_model(&self, input: &PredictionInput) -> Result<String> {
+        if input.horizon == 0 {
+            return Err(NeuralBridgeError::Validation(format!(
+                "Invalid prediction horizon: {}",
+                input.horizon
+            )));
+        }
+        
+        if let Some(model) = &self.config.neuralforecast.force_model {
+            return Ok(model.clone());
+        }
+        
+        if let Some(model) = self.config.neuralforecast.model_override(&input.symbol) {
+            return Ok(model.to_string());
+        }
+        
+        // Candidates come from the configured policy (horizon, symbol class,
+        // available features), are limited to models whose required features
+        // are present, and are ranked by live performance
+        self.model_selector.read().select_with_policy(
+            &self.config.model_selection,
+            input.horizon,
+            &input.symbol,
+            &input.available_features(),
+            &self.config.neuralforecast.models,
+        )
+    }
+
+    /// Reject inputs shorter than the model's configured minimum history,
+    /// unless the model pads short inputs
+    fn validate_history(&self, input: &PredictionInput, model_name: &str) -> Result<()> {
+        self.model_requirements(model_name)?.check_history(input)
+    }
+
+    /// Fewest price points a prediction for `symbol` at `horizon` needs,
+    /// from the model selected for an input carrying `features`
+    pub fn required_history(&self, symbol: &str, horizon: usize, features: &[&str]) -> Result<usize> {
+        let probe = PredictionInput {
+            symbol: symbol.to_string(),
+            historical_data: Vec::new(),
+            timestamps: Vec::new(),
+            features: features.iter().map(|name| (name.to_string(), Vec::new())).collect(),
+            horizon,
+            seed: None,
+            resampling: None,
+        };
+        let model_name = self.select_best_model(&probe)?;
+        let model = self
+            .config
+            .neuralforecast
+            .models
+            .get(&model_name)
+            .ok_or(NeuralBridgeError::ModelNotFound(model_name))?;
+        Ok(if model.padding.is_enabled() { 1 } else { model.min_history_points() })
+    }
+
     /// Generate cache key for prediction input
-    fn generate_cache_key(&self, input: &PredictionInput) -> String {
+    fn generate_cache_key(&self, input: &PredictionInput, model_name: &str) -> String {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
         
         let mut hasher = DefaultHasher::new();
+        self.cache_version.read().hash(&mut hasher);
         input.symbol.hash(&mut hasher);
+        model_name.hash(&mut hasher);
+        self.model_cache.version(model_name).hash(&mut hasher);
         input.horizon.hash(&mut hasher);
+        input.seed.hash(&mut hasher);
         
         // Hash the last few data points
         if input.historical_data.len() >= 10 {
@@ -186,36 +830,117 @@ impl NeuralBridgeManager {
 
     /// Get cached prediction
     async fn get_cached_prediction(&self, cache_key: &str) -> Option<PredictionResult> {
-        let cache = self.prediction_cache.read().await;
-        
-        if let Some(result) = cache.get(cache_key) {
+        let result = {
+            let cache = self.prediction_cache.read().await;
+            let result = cache.get(cache_key)?;
             // Check if cache entry is still valid (not older than configured TTL)
             let age = chrono::Utc::now().signed_duration_since(result.timestamp);
-            if age.num_seconds() < self.config.cache_ttl_seconds as i64 {
-                return Some(result.clone());
+            if age.num_seconds() >= self.config.cache_ttl_seconds as i64 {
+                return None;
             }
-        }
+            result.clone()
+        };
         
-        None
+        // Refresh the LRU position only when no one else holds the cache, so
+        // lookups never wait on each other
+        if let Ok(mut cache) = self.prediction_cache.try_write() {
+            cache.promote(cache_key);
+        }
+        Some(result)
     }
 
     /// Cache prediction result
     async fn cache_prediction(&self, cache_key: String, result: PredictionResult) {
+        self.prediction_cache.write().await.insert(cache_key, result);
+    }
+
+    /// Prediction cache entry and byte usage
+    pub async fn prediction_cache_stats(&self) -> cache::PredictionCacheStats {
+        self.prediction_cache.read().await.stats()
+    }
+
+    /// Drop cached predictions produced by a model, returning how many were removed
+    pub async fn invalidate_predictions_for_model(&self, model_name: &str) -> usize {
         let mut cache = self.prediction_cache.write().await;
-        
-        // Implement simple LRU by removing oldest entries if cache is full
-        if cache.len() >= self.config.max_cache_entries {
-            // Find and remove oldest entry
-            if let Some((oldest_key, _)) = cache
-                .iter()
-                .min_by_key(|(_, result)| result.timestamp)
-                .map(|(k, v)| (k.clone(), v.clone()))
-            {
-                cache.remove(&oldest_key);
+        let before = cache.len();
+        cache.retain(|result| result.model_name != model_name);
+        before - cache.len()
+    }
+
+    /// Key and age of each cached prediction, most recently used first
+    pub async fn prediction_cache_entries(&self) -> Vec<(String, std::time::Duration)> {
+        self.prediction_cache.read().await.entry_ages(chrono::Utc::now())
+    }
+
+    /// Evict cached predictions older than the TTL now instead of when next
+    /// looked up, returning how many were removed
+    pub async fn sweep_expired(&self) -> usize {
+        let ttl = std::time::Duration::from_secs(self.config.cache_ttl_seconds);
+        let removed = self
+            .prediction_cache
+            .write()
+            .await
+            .sweep_expired(ttl, chrono::Utc::now());
+        debug!("Swept {} expired predictions", removed);
+        removed
+    }
+
+    /// Write cached predictions to `path` as a JSON map of cache key to
+    /// result, returning how many were saved
+    pub async fn save_prediction_cache(&self, path: impl AsRef<std::path::Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let snapshot: HashMap<String, PredictionResult> = self
+            .prediction_cache
+            .read()
+            .await
+            .iter()
+            .map(|(key, result)| (key.clone(), result.clone()))
+            .collect();
+
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, serde_json::to_vec(&snapshot)?).await?;
+        tokio::fs::rename(&tmp_path, path).await?;
+        debug!("Saved {} cached predictions to {}", snapshot.len(), path.display());
+        Ok(snapshot.len())
+    }
+
+    /// Preseed the cache from a file written by `save_prediction_cache`.
+    ///
+    /// Predictions already past the TTL are dropped; returns how many were
+    /// loaded.
+    pub async fn load_prediction_cache(&self, path: impl AsRef<std::path::Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let bytes = tokio::fs::read(path).await?;
+        let snapshot: HashMap<String, PredictionResult> = serde_json::from_slice(&bytes)?;
+        let ttl = chrono::Duration::seconds(self.config.cache_ttl_seconds as i64);
+        let now = chrono::Utc::now();
+
+        let mut cache = self.prediction_cache.write().await;
+        let mut loaded = 0;
+        for (key, result) in snapshot {
+            if now.signed_duration_since(result.timestamp) < ttl {
+                cache.insert(key, result);
+                loaded += 1;
             }
         }
-        
-        cache.insert(cache_key, result);
+        info!("Preseeded {} cached predictions from {}", loaded, path.display());
+        Ok(loaded)
+    }
+
+    /// Drop all cached predictions
+    pub async fn clear_prediction_cache(&self) {
+        self.prediction_cache.write().await.clear();
+    }
+
+    /// Change the cache key salt; predictions cached under the previous
+    /// version can no longer be served, so they are dropped
+    pub async fn set_cache_version(&self, version: impl Into<String>) {
+        let version = version.into();
+        let previous = std::mem::replace(&mut *self.cache_version.write(), version.clone());
+        if previous != version {
+            info!("Cache version {:?} -> {:?}, clearing predictions", previous, version);
+            self.clear_prediction_cache().await;
+        }
     }
 
     /// Get available models
@@ -223,12 +948,191 @@ impl NeuralBridgeManager {
         self.model_cache.list_models()
     }
 
-    /// Get model performance statistics
+    /// Features, horizons and input length a configured model expects
+    pub fn model_requirements(&self, model_name: &str) -> Result<models::ModelRequirements> {
+        self.config
+            .neuralforecast
+            .models
+            .get(model_name)
+            .map(|config| models::ModelRequirements::from_config(model_name, config))
+            .ok_or_else(|| NeuralBridgeError::ModelNotFound(model_name.to_string()))
+    }
+
+    /// Report Python, cache and preload status for readiness probes
+    pub async fn health(&self) -> HealthReport {
+        let python_ready = match self.neuralforecast.health_check().await {
+            Ok(ready) => ready,
+            Err(e) => {
+                warn!("NeuralForecast health check failed: {:?}", e);
+                false
+            }
+        };
+
+        let missing_preload_models: Vec<String> = self
+            .config
+            .preload_models
+            .iter()
+            .filter(|name| !self.model_cache.contains(name))
+            .cloned()
+            .collect();
+
+        HealthReport {
+            healthy: python_ready && missing_preload_models.is_empty(),
+            python_ready,
+            loaded_models: self.model_cache.size(),
+            model_cache_capacity: self.model_cache.max_size(),
+            prediction_cache_entries: self.prediction_cache.read().await.len(),
+            prediction_cache_capacity: self.config.max_cache_entries,
+            missing_preload_models,
+            fallback_active: self.config.fallback.enabled && !python_ready,
+            checked_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Get model performance statistics, preferring live tracked stats
     pub async fn get_model_stats(&self, model_name: &str) -> Result<models::ModelStats> {
+        if let Some(stats) = self
+            .model_selector
+            .read()
+            .get_performance_tracker()
+            .get_stats(model_name)
+        {
+            return Ok(stats.clone());
+        }
+        if !self.config.neuralforecast.models.contains_key(model_name) {
+            return Err(NeuralBridgeError::ModelNotFound(model_name.to_string()));
+        }
         self.neuralforecast.get_model_stats(model_name).await
     }
 
+    /// Score a realized forecast against actual values and update the
+    /// model's rolling accuracy and directional accuracy. `last_known` is
+    /// the last price in the input the forecast was made from.
+    pub fn record_outcome(
+        &self,
+        model_name: &str,
+        predicted: &[f64],
+        actual: &[f64],
+        last_known: f64,
+    ) -> Result<f64> {
+        self.model_selector
+            .write()
+            .get_performance_tracker_mut()
+            .record_outcome(model_name, predicted, actual, last_known)
+    }
+
+    /// Forecast several horizons for one input in a single call.
+    ///
+    /// Horizons are grouped by the model selected for each; every model runs
+    /// once at its longest horizon and shorter horizons are served from the
+    /// prefix of that forecast. Each horizon is cached independently.
+    #[instrument(skip_all, fields(symbol = %input.symbol, horizons = ?horizons))]
+    pub async fn predict_multi_horizon(
+        &self,
+        input: PredictionInput,
+        horizons: &[usize],
+    ) -> Result<HashMap<usize, PredictionResult>> {
+        let mut horizons_by_model: HashMap<String, Vec<usize>> = HashMap::new();
+        for &horizon in horizons {
+            let model_name = self.select_best_model(&PredictionInput { horizon, ..input.clone() })?;
+            horizons_by_model.entry(model_name).or_default().push(horizon);
+        }
+        
+        let start_time = std::time::Instant::now();
+        let mut results = HashMap::with_capacity(horizons.len());
+        for (model_name, mut model_horizons) in horizons_by_model {
+            model_horizons.sort_unstable();
+            model_horizons.dedup();
+            
+            let mut missing = Vec::new();
+            for horizon in model_horizons {
+                let key = self.generate_cache_key(&PredictionInput { horizon, ..input.clone() }, &model_name);
+                match self.get_cached_prediction(&key).await {
+                    Some(mut cached) => {
+                        self.stamp_runtime(&mut cached, false, start_time.elapsed());
+                        results.insert(horizon, self.check_confidence(cached)?);
+                    }
+                    None => missing.push((horizon, key)),
+                }
+            }
+            
+            let Some((longest, _)) = missing.pop() else {
+                continue;
+            };
+            let full = self
+                .predict_with_model(
+                    PredictionInput { horizon: longest, ..input.clone() },
+                    Some(&model_name),
+                    CacheMode::ReadWrite,
+                )
+                .await?;
+            
+            // Fallback forecasts are never cached, and cached entries carry
+            // no runtime stamps
+            let fallback = full.metadata.contains_key("fallback");
+            for (horizon, key) in missing {
+                let result = truncate_prediction(&full, horizon);
+                if !fallback {
+                    let mut cached = result.clone();
+                    for runtime_key in RUNTIME_METADATA_KEYS {
+                        cached.metadata.remove(runtime_key);
+                    }
+                    self.cache_prediction(key, cached).await;
+                }
+                results.insert(horizon, result);
+            }
+            results.insert(longest, full);
+        }
+        
+        Ok(results)
+    }
+
+    /// Predictions from the `k` most confident models that can serve the
+    /// input, sorted by confidence descending.
+    ///
+    /// Every loaded model whose required features are present is run
+    /// individually so disagreement between models stays visible. Models
+    /// that fail or fall below the confidence floor are left out.
+    #[instrument(skip_all, fields(symbol = %input.symbol, k = k))]
+    pub async fn predict_ranked(&self, input: PredictionInput, k: usize) -> Result<Vec<PredictionResult>> {
+        let mut model_names: Vec<String> = self
+            .model_cache
+            .list_models()
+            .into_iter()
+            .filter(|name| {
+                self.model_requirements(name)
+                    .map_or(true, |requirements| requirements.missing_features(&input).is_empty())
+            })
+            .collect();
+        model_names.sort();
+        
+        let mut results = Vec::with_capacity(model_names.len());
+        let mut last_error = None;
+        for model_name in &model_names {
+            match self.predict_with_model(input.clone(), Some(model_name), CacheMode::ReadWrite).await {
+                Ok(result) => results.push(result),
+                Err(e) => {
+                    debug!("Leaving {} out of ranked predictions: {}", model_name, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        
+        if results.is_empty() {
+            return Err(last_error.unwrap_or_else(|| {
+                NeuralBridgeError::Validation(format!("No loaded model can serve {}", input.symbol))
+            }));
+        }
+        
+        results.sort_by(|a, b| {
+            prediction::effective_confidence(b).total_cmp(&prediction::effective_confidence(a))
+        });
+        results.truncate(k);
+        Ok(results)
+    }
+
     /// Batch prediction for multiple inputs
+    #[instrument(skip_all, fields(batch_size = inputs.len()))]
     pub async fn batch_predict(&self, inputs: Vec<PredictionInput>) -> Result<Vec<PredictionResult>> {
         let mut results = Vec::with_capacity(inputs.len());
         
@@ -247,4 +1151,611 @@ impl NeuralBridgeManager {
         
         Ok(results)
     }
-}
\ No newline at end of file
+}
+
+/// Prefix of a forecast as a result for a shorter horizon
+fn truncate_prediction(result: &PredictionResult, horizon: usize) -> PredictionResult {
+    let mut truncated = result.clone();
+    truncated.prediction.truncate(horizon);
+    truncated.horizon = horizon;
+    if let Some(serde_json::Value::Array(timestamps)) = truncated.metadata.get_mut("forecast_timestamps") {
+        timestamps.truncate(horizon);
+    }
+    if let Some(interval) = truncated.metadata.get_mut("interval") {
+        for key in ["lower", "upper"] {
+            if let Some(serde_json::Value::Array(bounds)) = interval.get_mut(key) {
+                bounds.truncate(horizon);
+            }
+        }
+    }
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MockBackend;
+    use crate::test_support::{aapl_input, mock_manager as manager};
+
+    fn input(horizon: usize) -> PredictionInput {
+        aapl_input(200, horizon)
+    }
+
+    fn price_only_input(horizon: usize) -> PredictionInput {
+        PredictionInput {
+            features: HashMap::new(),
+            ..input(horizon)
+        }
+    }
+
+    #[test]
+    fn test_manager_is_shareable_across_tasks() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<NeuralBridgeManager>();
+        assert_send_sync::<Arc<NeuralBridgeManager>>();
+    }
+
+    #[test]
+    fn test_config_fields_default_when_missing() {
+        for key in [
+            "model_selection",
+            "callback",
+            "performance_stats_path",
+            "performance_stats_persist_interval_seconds",
+            "accuracy_metric",
+            "accuracy_smoothing",
+            "performance.queue_capacity",
+            "performance.min_confidence",
+            "neuralforecast.backend",
+        ] {
+            let mut value = serde_json::to_value(config::NeuralBridgeConfig::default()).unwrap();
+            let (parent, field) = key.rsplit_once('.').unwrap_or(("", key));
+            let section = parent.split('.').filter(|s| !s.is_empty()).fold(&mut value, |v, s| &mut v[s]);
+            section.as_object_mut().unwrap().remove(field);
+
+            if let Err(e) = serde_json::from_value::<config::NeuralBridgeConfig>(value) {
+                panic!("config without `{}` was rejected: {}", key, e);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_predictions_through_shared_manager() {
+        let manager = Arc::new(manager().await);
+
+        let handles: Vec<_> = [3, 10, 30]
+            .into_iter()
+            .map(|horizon| {
+                let manager = Arc::clone(&manager);
+                tokio::spawn(async move { manager.predict(input(horizon)).await })
+            })
+            .collect();
+
+        for handle in handles {
+            assert!(handle.await.unwrap().is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_worker_threads_cap_concurrent_inference() {
+        let backend = Arc::new(MockBackend::default().with_latency(std::time::Duration::from_millis(20)));
+        let mut config = config::NeuralBridgeConfig::default();
+        config.performance.worker_threads = 2;
+        let manager = NeuralBridgeManager::with_backend(config, Box::new(Arc::clone(&backend))).unwrap();
+        manager.initialize().await.unwrap();
+        let manager = Arc::new(manager);
+        let warmups = backend.prediction_count();
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let manager = Arc::clone(&manager);
+                let input = PredictionInput {
+                    symbol: format!("SYM{}", i),
+                    ..input(5)
+                };
+                tokio::spawn(async move { manager.predict(input).await })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.await.unwrap().is_ok());
+        }
+
+        assert_eq!(backend.prediction_count() - warmups, 8);
+        assert_eq!(backend.peak_concurrency(), 2);
+    }
+
+    #[test]
+    fn test_predict_blocking_outside_runtime() {
+        let manager = tokio::runtime::Runtime::new().unwrap().block_on(manager());
+
+        assert_eq!(manager.predict_blocking(input(3)).unwrap().model_name, "NBEATS");
+    }
+
+    #[tokio::test]
+    async fn test_predict_selects_model_by_horizon() {
+        let manager = manager().await;
+
+        assert_eq!(manager.predict(input(3)).await.unwrap().model_name, "NBEATS");
+        assert_eq!(manager.predict(input(10)).await.unwrap().model_name, "TFT");
+        assert_eq!(manager.predict(input(30)).await.unwrap().model_name, "LSTM");
+    }
+
+    #[tokio::test]
+    async fn test_symbol_override_takes_precedence() {
+        let mut config = config::NeuralBridgeConfig::default();
+        config.neuralforecast.symbol_model_overrides.insert("*USD".to_string(), "LSTM".to_string());
+        config.neuralforecast.symbol_model_overrides.insert("BTCUSD".to_string(), "NBEATS".to_string());
+        let manager = NeuralBridgeManager::with_backend(config, Box::new(MockBackend::default())).unwrap();
+        manager.initialize().await.unwrap();
+
+        let mut eth = input(10);
+        eth.symbol = "ETHUSD".to_string();
+        let mut btc = input(10);
+        btc.symbol = "BTCUSD".to_string();
+
+        assert_eq!(manager.predict(eth).await.unwrap().model_name, "LSTM");
+        assert_eq!(manager.predict(btc).await.unwrap().model_name, "NBEATS");
+        assert_eq!(manager.predict(input(10)).await.unwrap().model_name, "TFT");
+    }
+
+    #[tokio::test]
+    async fn test_force_model_bypasses_selection() {
+        let mut config = config::NeuralBridgeConfig::default();
+        config.neuralforecast.force_model = Some("LSTM".to_string());
+        config.neuralforecast.symbol_model_overrides.insert("AAPL".to_string(), "NBEATS".to_string());
+        let manager = NeuralBridgeManager::with_backend(config, Box::new(MockBackend::default())).unwrap();
+        manager.initialize().await.unwrap();
+
+        assert_eq!(manager.predict(input(3)).await.unwrap().model_name, "LSTM");
+        assert_eq!(manager.predict(input(10)).await.unwrap().model_name, "LSTM");
+    }
+
+    #[tokio::test]
+    async fn test_reload_invalidates_model_predictions() {
+        let manager = manager().await;
+        manager.predict(input(3)).await.unwrap();
+        manager.predict(input(10)).await.unwrap();
+
+        manager.load_model("NBEATS").await.unwrap();
+
+        let cache = manager.prediction_cache.read().await;
+        assert_eq!(cache.len(), 1);
+        assert!(cache.values().all(|result| result.model_name == "TFT"));
+    }
+
+    #[tokio::test]
+    async fn test_cache_version_changes_keys() {
+        let manager = manager().await;
+        let before = manager.generate_cache_key(&input(5), "NBEATS");
+        manager.predict(input(5)).await.unwrap();
+
+        manager.set_cache_version("v2").await;
+
+        assert_ne!(manager.generate_cache_key(&input(5), "NBEATS"), before);
+        assert_eq!(manager.prediction_cache.read().await.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_prediction_cache_round_trips_through_file() {
+        let path = std::env::temp_dir().join(format!("prediction-cache-{}.json", std::process::id()));
+        let source = manager().await;
+        let fresh = source.predict(input(5)).await.unwrap();
+        let stale = PredictionResult {
+            timestamp: chrono::Utc::now() - chrono::Duration::days(1),
+            ..fresh
+        };
+        source.prediction_cache.write().await.insert("pred_stale".to_string(), stale);
+        assert_eq!(source.save_prediction_cache(&path).await.unwrap(), 2);
+
+        let restored = manager().await;
+        assert_eq!(restored.load_prediction_cache(&path).await.unwrap(), 1);
+        let key = restored.generate_cache_key(&input(5), "NBEATS");
+        assert!(restored.get_cached_prediction(&key).await.is_some());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_performance_stats_survive_restart() {
+        let path = std::env::temp_dir().join(format!("manager-stats-{}.json", std::process::id()));
+        let config = config::NeuralBridgeConfig {
+            performance_stats_path: Some(path.to_string_lossy().into_owned()),
+            ..config::NeuralBridgeConfig::default()
+        };
+        let start = |config: config::NeuralBridgeConfig| async move {
+            let backend = Box::new(MockBackend::default());
+            let manager = NeuralBridgeManager::with_backend(config, backend).unwrap();
+            manager.initialize().await.unwrap();
+            manager
+        };
+
+        let before = start(config.clone()).await;
+        let model = before.predict(input(5)).await.unwrap().model_name;
+        before.shutdown().unwrap();
+
+        let after = start(config).await;
+        assert_eq!(after.get_model_stats(&model).await.unwrap().successful_predictions, 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cache_entries_report_ages_and_survive_sweep() {
+        let manager = manager().await;
+        manager.predict(input(5)).await.unwrap();
+
+        let entries = manager.prediction_cache_entries().await;
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].1 < std::time::Duration::from_secs(manager.config.cache_ttl_seconds));
+        assert_eq!(manager.sweep_expired().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_results_report_inference_time_and_backend() {
+        let manager = manager().await;
+        let result = manager.predict(input(3)).await.unwrap();
+
+        assert_eq!(result.metadata["backend"], serde_json::json!("mock"));
+        assert_eq!(result.metadata["device"], serde_json::json!("cpu"));
+        assert!(result.metadata["inference_time_ms"].as_f64().unwrap() >= 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_runtime_is_stamped_per_call_not_cached() {
+        let manager = manager().await;
+        manager.predict(input(3)).await.unwrap();
+
+        let cache_key = manager.generate_cache_key(&input(3), "NBEATS");
+        let cached = manager.get_cached_prediction(&cache_key).await.unwrap();
+        assert!(!cached.metadata.contains_key("inference_time_ms"));
+        assert!(!cached.metadata.contains_key("backend"));
+
+        let hit = manager.predict(input(3)).await.unwrap();
+        assert_eq!(hit.metadata["backend"], serde_json::json!("mock"));
+        assert!(hit.metadata.contains_key("inference_time_ms"));
+
+        let mut config = config::NeuralBridgeConfig::default();
+        config.fallback.enabled = true;
+        let degraded = NeuralBridgeManager::with_backend(config, Box::new(MockBackend::unavailable())).unwrap();
+        degraded.initialize().await.unwrap();
+        let result = degraded.predict(input(3)).await.unwrap();
+        assert_eq!(result.metadata["backend"], serde_json::json!("fallback"));
+        assert_eq!(result.metadata["device"], serde_json::json!("cpu"));
+        assert!(result.metadata.contains_key("inference_time_ms"));
+    }
+
+    #[tokio::test]
+    async fn test_confidence_strategy_replaces_backend_value() {
+        struct Fixed;
+        impl confidence::ConfidenceEstimator for Fixed {
+            fn name(&self) -> &'static str {
+                "fixed"
+            }
+            fn estimate(&self, _: &PredictionInput, _: &PredictionResult, _: Option<&models::ModelStats>) -> Option<f64> {
+                Some(0.42)
+            }
+        }
+
+        let mut config = config::NeuralBridgeConfig::default();
+        config.neuralforecast.models.get_mut("NBEATS").unwrap().confidence =
+            confidence::ConfidenceStrategy::Volatility;
+        let manager = NeuralBridgeManager::with_backend(config, Box::new(MockBackend::default())).unwrap();
+        manager.initialize().await.unwrap();
+
+        // A steadily rising series has near-zero return volatility
+        let result = manager.predict(input(3)).await.unwrap();
+        assert_eq!(result.metadata["confidence_strategy"], serde_json::json!("volatility"));
+        assert!(result.confidence > 0.99);
+
+        manager.set_confidence_estimator("TFT", Arc::new(Fixed));
+        let result = manager.predict(input(10)).await.unwrap();
+        assert_eq!(result.metadata["confidence_strategy"], serde_json::json!("fixed"));
+        assert_eq!(result.confidence, 0.42);
+    }
+
+    #[tokio::test]
+    async fn test_model_version_stamped_and_keyed() {
+        let manager = manager().await;
+        let result = manager.predict(input(3)).await.unwrap();
+        assert_eq!(result.metadata["model_version"], serde_json::json!("1.0.0"));
+
+        let before = manager.generate_cache_key(&input(3), "NBEATS");
+        let mut model = manager.model_cache.get("NBEATS").unwrap();
+        model.metadata.version = "1.1.0".to_string();
+        manager.model_cache.insert("NBEATS".to_string(), model);
+
+        assert_ne!(manager.generate_cache_key(&input(3), "NBEATS"), before);
+    }
+
+    #[tokio::test]
+    async fn test_history_shorter_than_model_input_is_rejected() {
+        let manager = manager().await;
+        let mut short = input(10);
+        short.historical_data.truncate(50);
+        short.timestamps.truncate(50);
+        short.features.insert("volume".to_string(), vec![1_000.0; 50]);
+
+        let err = manager.predict(short.clone()).await.unwrap_err();
+        assert!(err.to_string().contains("model TFT requires at least 168"));
+
+        short.horizon = 3;
+        assert_eq!(manager.predict(short).await.unwrap().model_name, "NBEATS");
+    }
+
+    #[tokio::test]
+    async fn test_padding_accepts_short_history() {
+        let mut config = config::NeuralBridgeConfig::default();
+        config.neuralforecast.models.get_mut("TFT").unwrap().padding = transform::PaddingStrategy::Reflect;
+        let manager = NeuralBridgeManager::with_backend(config, Box::new(MockBackend::default())).unwrap();
+        manager.initialize().await.unwrap();
+
+        let mut short = input(10);
+        short.historical_data.truncate(50);
+        short.timestamps.truncate(50);
+        short.features.insert("volume".to_string(), vec![1_000.0; 50]);
+
+        assert!(manager.model_requirements("TFT").unwrap().check(&short).is_ok());
+        assert_eq!(manager.predict(short).await.unwrap().model_name, "TFT");
+    }
+
+    #[tokio::test]
+    async fn test_predict_serves_repeat_input_from_cache() {
+        let manager = manager().await;
+
+        let first = manager.predict(input(5)).await.unwrap();
+        let second = manager.predict(input(5)).await.unwrap();
+
+        assert_eq!(first.timestamp, second.timestamp);
+    }
+
+    #[tokio::test]
+    async fn test_predict_fresh_skips_but_refreshes_cache() {
+        let manager = manager().await;
+        let cached = manager.predict(input(5)).await.unwrap();
+
+        let fresh = manager.predict_fresh(input(5)).await.unwrap();
+        let again = manager.predict(input(5)).await.unwrap();
+
+        assert_ne!(fresh.timestamp, cached.timestamp);
+        assert_eq!(again.timestamp, fresh.timestamp);
+    }
+
+    #[tokio::test]
+    async fn test_batch_predict_skips_failures() {
+        let manager = manager().await;
+
+        let results = manager
+            .batch_predict(vec![input(5), input(0), input(10)])
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_multi_horizon_selects_and_caches_per_horizon() {
+        let manager = manager().await;
+
+        let results = manager.predict_multi_horizon(input(1), &[3, 5, 10, 20]).await.unwrap();
+
+        assert_eq!(results[&3].model_name, "NBEATS");
+        assert_eq!(results[&5].model_name, "NBEATS");
+        assert_eq!(results[&10].model_name, "TFT");
+        assert_eq!(results[&3].prediction.len(), 3);
+        assert_eq!(results[&20].prediction.len(), 20);
+
+        let cached = manager.predict(input(3)).await.unwrap();
+        assert_eq!(cached.timestamp, results[&3].timestamp);
+        assert_eq!(manager.prediction_cache.read().await.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_multi_horizon_caches_without_runtime_and_skips_fallback() {
+        let manager = manager().await;
+        manager.predict_multi_horizon(input(1), &[3, 5]).await.unwrap();
+
+        let key = manager.generate_cache_key(&input(3), "NBEATS");
+        let cached = manager.get_cached_prediction(&key).await.unwrap();
+        for runtime_key in RUNTIME_METADATA_KEYS {
+            assert!(!cached.metadata.contains_key(runtime_key), "{}", runtime_key);
+        }
+
+        let mut config = config::NeuralBridgeConfig::default();
+        config.fallback.enabled = true;
+        let backend = Box::new(MockBackend::unavailable());
+        let degraded = NeuralBridgeManager::with_backend(config, backend).unwrap();
+        degraded.initialize().await.unwrap();
+        let results = degraded.predict_multi_horizon(input(1), &[3, 5]).await.unwrap();
+        assert_eq!(results[&3].metadata["fallback"], serde_json::json!(true));
+        assert_eq!(degraded.prediction_cache.read().await.len(), 0);
+    }
+
+    #[test]
+    fn test_truncated_predictions_trim_interval_bounds() {
+        let result = PredictionResult {
+            model_name: "TFT".to_string(),
+            symbol: "AAPL".to_string(),
+            prediction: vec![1.0, 2.0, 3.0],
+            confidence: 0.8,
+            timestamp: chrono::Utc::now(),
+            horizon: 3,
+            metadata: HashMap::from([(
+                "interval".to_string(),
+                serde_json::json!({ "level": "90", "lower": [0.5, 1.5, 2.5], "upper": [1.5, 2.5, 3.5] }),
+            )]),
+        };
+
+        let truncated = truncate_prediction(&result, 2);
+
+        assert_eq!(truncated.metadata["interval"]["lower"], serde_json::json!([0.5, 1.5]));
+        assert_eq!(truncated.metadata["interval"]["upper"], serde_json::json!([1.5, 2.5]));
+        assert_eq!(truncated.metadata["interval"]["level"], serde_json::json!("90"));
+    }
+
+    #[tokio::test]
+    async fn test_predict_stream_yields_steps_then_result() {
+        let manager = Arc::new(manager().await);
+
+        let mut rx = manager.predict_stream(input(3)).unwrap();
+        let mut items = Vec::new();
+        while let Some(item) = rx.recv().await {
+            items.push(item.unwrap());
+        }
+
+        assert_eq!(items.len(), 4);
+        assert!(matches!(
+            &items[1],
+            prediction::PredictionStreamItem::Partial { start_step: 1, values } if values == &vec![149.0]
+        ));
+        assert!(matches!(
+            &items[3],
+            prediction::PredictionStreamItem::Complete(result) if result.prediction.len() == 3
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_predict_stream_rejects_invalid_input_up_front() {
+        let manager = Arc::new(manager().await);
+
+        assert!(matches!(
+            manager.predict_stream(PredictionInput { horizon: 0, ..input(3) }),
+            Err(NeuralBridgeError::Validation(_))
+        ));
+        let mut negative = input(3);
+        negative.historical_data[10] = -1.0;
+        assert!(matches!(manager.predict_stream(negative), Err(NeuralBridgeError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_model_requirements_report_missing_features() {
+        let manager = manager().await;
+
+        let requirements = manager.model_requirements("TFT").unwrap();
+        assert_eq!(requirements.input_length, Some(168));
+        assert_eq!(requirements.missing_features(&price_only_input(10)), vec!["volume".to_string()]);
+        assert!(requirements.check(&price_only_input(10)).is_err());
+        assert!(manager.model_requirements("NBEATS").unwrap().check(&input(3)).is_ok());
+        assert!(matches!(
+            manager.model_requirements("ARIMA"),
+            Err(NeuralBridgeError::ModelNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_selection_skips_models_missing_features() {
+        let manager = manager().await;
+
+        assert_eq!(manager.predict(price_only_input(3)).await.unwrap().model_name, "NBEATS");
+
+        let err = manager.predict(price_only_input(10)).await.unwrap_err();
+        assert!(err.to_string().contains("TFT needs volume"));
+        assert!(err.to_string().contains("LSTM needs volume"));
+    }
+
+    #[tokio::test]
+    async fn test_predict_ranked_returns_top_k_servable_models() {
+        let manager = manager().await;
+
+        let ranked = manager.predict_ranked(input(5), 2).await.unwrap();
+        assert_eq!(ranked.len(), 2);
+        assert!(ranked[0].confidence >= ranked[1].confidence);
+
+        let price_only = manager.predict_ranked(price_only_input(5), 3).await.unwrap();
+        assert_eq!(price_only.len(), 1);
+        assert_eq!(price_only[0].model_name, "NBEATS");
+    }
+
+    #[tokio::test]
+    async fn test_fallback_serves_when_backend_unavailable() {
+        let mut config = config::NeuralBridgeConfig::default();
+        config.fallback.enabled = true;
+        let manager = NeuralBridgeManager::with_backend(config, Box::new(MockBackend::unavailable())).unwrap();
+        manager.initialize().await.unwrap();
+
+        let result = manager.predict(input(3)).await.unwrap();
+
+        assert_eq!(result.prediction, vec![149.0; 3]);
+        assert_eq!(result.metadata["fallback"], serde_json::json!(true));
+        assert!(manager.health().await.fallback_active);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_sees_interpolated_input() {
+        let mut config = config::NeuralBridgeConfig::default();
+        config.fallback.enabled = true;
+        config.neuralforecast.models.get_mut("NBEATS").unwrap().interpolation.method =
+            transform::InterpolationMethod::ForwardFill;
+        let manager = NeuralBridgeManager::with_backend(config, Box::new(MockBackend::unavailable())).unwrap();
+        manager.initialize().await.unwrap();
+        let mut gapped = input(3);
+        gapped.historical_data[199] = f64::NAN;
+
+        let result = manager.predict(gapped).await.unwrap();
+
+        assert!(result.prediction.iter().all(|value| value.is_finite()));
+        assert_eq!(result.metadata["interpolation"]["filled_points"], serde_json::json!(1));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_reports_resampling() {
+        let mut config = config::NeuralBridgeConfig::default();
+        config.fallback.enabled = true;
+        let manager = NeuralBridgeManager::with_backend(config, Box::new(MockBackend::unavailable())).unwrap();
+        manager.initialize().await.unwrap();
+        let resampled =
+            prediction::resample(&input(3), chrono::Duration::minutes(1), prediction::Aggregation::Last).unwrap();
+
+        let result = manager.predict(resampled).await.unwrap();
+
+        assert_eq!(result.metadata["fallback"], serde_json::json!(true));
+        assert_eq!(result.metadata["resampling"]["interval_ms"], serde_json::json!(60_000));
+        assert_eq!(result.metadata["resampling"]["source_points"], serde_json::json!(200));
+    }
+
+    #[tokio::test]
+    async fn test_min_confidence_rejects_low_confidence_but_not_fallback() {
+        let mut config = config::NeuralBridgeConfig::default();
+        config.performance.min_confidence = 0.5;
+        let start = |config: config::NeuralBridgeConfig, backend: MockBackend| async move {
+            let manager = NeuralBridgeManager::with_backend(config, Box::new(backend)).unwrap();
+            manager.initialize().await.unwrap();
+            manager
+        };
+
+        let confident = start(config.clone(), MockBackend::new(0.9)).await;
+        assert!(confident.predict(input(3)).await.is_ok());
+
+        let unsure = start(config.clone(), MockBackend::new(0.4)).await;
+        let Err(NeuralBridgeError::LowConfidence { confidence, min_confidence, .. }) =
+            unsure.predict(input(3)).await
+        else {
+            panic!("low confidence prediction was accepted");
+        };
+        assert_eq!((confidence, min_confidence), (0.4, 0.5));
+
+        config.fallback.enabled = true;
+        config.performance.min_confidence = 1.0;
+        let degraded = start(config, MockBackend::unavailable()).await;
+        let result = degraded.predict(input(3)).await.unwrap();
+        assert_eq!(result.metadata["fallback"], serde_json::json!(true));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_ends_when_backend_recovers() {
+        let backend = Arc::new(MockBackend::unavailable());
+        let mut config = config::NeuralBridgeConfig::default();
+        config.fallback.enabled = true;
+        config.fallback.recheck_interval_secs = 0;
+        let manager = NeuralBridgeManager::with_backend(config, Box::new(Arc::clone(&backend))).unwrap();
+        manager.initialize().await.unwrap();
+
+        assert_eq!(manager.predict(input(3)).await.unwrap().metadata["fallback"], serde_json::json!(true));
+        // Invalid inputs are rejected even in degraded mode
+        assert!(manager.predict(PredictionInput { horizon: 0, ..input(3) }).await.is_err());
+
+        backend.set_available(true);
+        let result = manager.predict(input(3)).await.unwrap();
+        assert!(!result.metadata.contains_key("fallback"));
+        assert_eq!(result.model_name, "NBEATS");
+    }
+}
@@ -0,0 +1,367 @@
+//! Live predictions over WebSocket
+//!
+//! Clients connect to `/ws/predictions/:symbol` and receive a JSON
+//! `PredictionResult` each time new market data for the symbol arrives. One
+//! prediction loop runs per symbol, shared by all of its subscribers, and is
+//! stopped when the last subscriber disconnects.
+
+use crate::error::{NeuralBridgeError, Result};
+use crate::{NeuralBridgeManager, PredictionInput, PredictionResult};
+use axum::{
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Path, State,
+    },
+    response::Response,
+    routing::get,
+    Router,
+};
+use data_ingestion::{DataIngestionManager, MarketData};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Opens a market data receiver for a symbol
+pub type MarketDataSource = Arc<dyn Fn(&str) -> broadcast::Receiver<MarketData> + Send + Sync>;
+
+/// Live prediction settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveConfig {
+    /// Most recent points fed to the model; defaults to the minimum history
+    /// of the model selected for the symbol and horizon
+    #[serde(default)]
+    pub window: Option<usize>,
+
+    /// Forecast horizon of each prediction
+    pub horizon: usize,
+
+    /// Results buffered per symbol before slow subscribers skip ahead
+    pub channel_capacity: usize,
+}
+
+impl Default for LiveConfig {
+    fn default() -> Self {
+        Self {
+            window: None,
+            horizon: 10,
+            channel_capacity: 64,
+        }
+    }
+}
+
+struct SymbolStream {
+    tx: broadcast::Sender<PredictionResult>,
+    subscribers: usize,
+    task: JoinHandle<()>,
+}
+
+/// Per-symbol prediction loops fed by market data
+pub struct PredictionStream {
+    manager: Arc<NeuralBridgeManager>,
+    source: MarketDataSource,
+    config: LiveConfig,
+    streams: Mutex<HashMap<String, SymbolStream>>,
+}
+
+impl PredictionStream {
+    /// Predict from data produced by `source`
+    pub fn new(manager: Arc<NeuralBridgeManager>, source: MarketDataSource, config: LiveConfig) -> Self {
+        Self {
+            manager,
+            source,
+            config,
+            streams: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Predict from an ingestion pipeline's per-symbol channels
+    pub fn from_ingestion(
+        manager: Arc<NeuralBridgeManager>,
+        ingestion: Arc<DataIngestionManager>,
+        config: LiveConfig,
+    ) -> Self {
+        Self::new(
+            manager,
+            Arc::new(move |symbol: &str| ingestion.subscribe_symbol(symbol)),
+            config,
+        )
+    }
+
+    /// Receive predictions for `symbol`, starting its loop if needed.
+    ///
+    /// Fails when the configured window is shorter than the selected model
+    /// requires, since the loop could never predict. Every successful call
+    /// must be paired with `unsubscribe`.
+    pub fn subscribe(&self, symbol: &str) -> Result<broadcast::Receiver<PredictionResult>> {
+        let mut streams = self.streams.lock();
+        let stream = match streams.entry(symbol.to_string()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let window = self.window_for(symbol)?;
+                info!("Starting live predictions for {} over {} points", symbol, window);
+                let (tx, _) = broadcast::channel(self.config.channel_capacity);
+                let task = tokio::spawn(run_symbol(
+                    self.manager.clone(),
+                    (self.source)(symbol),
+                    tx.clone(),
+                    symbol.to_string(),
+                    window,
+                    self.config.horizon,
+                ));
+                entry.insert(SymbolStream { tx, subscribers: 0, task })
+            }
+        };
+        stream.subscribers += 1;
+        Ok(stream.tx.subscribe())
+    }
+
+    /// Window for a symbol's loop: the configured one, checked against the
+    /// selected model's minimum history, or that minimum
+    fn window_for(&self, symbol: &str) -> Result<usize> {
+        let required = self.manager.required_history(symbol, self.config.horizon, &["volume"])?;
+        match self.config.window {
+            Some(window) if window < required => Err(NeuralBridgeError::Validation(format!(
+                "Live window of {} points is shorter than the {} the model for {} requires",
+                window, required, symbol
+            ))),
+            Some(window) => Ok(window),
+            None => Ok(required),
+        }
+    }
+
+    /// Release a subscription, stopping the symbol's loop after the last one
+    pub fn unsubscribe(&self, symbol: &str) {
+        let mut streams = self.streams.lock();
+        let Some(stream) = streams.get_mut(symbol) else {
+            return;
+        };
+        stream.subscribers = stream.subscribers.saturating_sub(1);
+        if stream.subscribers == 0 {
+            info!("Stopping live predictions for {}", symbol);
+            if let Some(stream) = streams.remove(symbol) {
+                stream.task.abort();
+            }
+        }
+    }
+
+    /// Symbols with a running prediction loop
+    pub fn active_symbols(&self) -> Vec<String> {
+        self.streams.lock().keys().cloned().collect()
+    }
+}
+
+impl Drop for PredictionStream {
+    fn drop(&mut self) {
+        for stream in self.streams.lock().values() {
+            stream.task.abort();
+        }
+    }
+}
+
+/// Router serving `/ws/predictions/:symbol`
+pub fn router(stream: Arc<PredictionStream>) -> Router {
+    Router::new()
+        .route("/ws/predictions/:symbol", get(upgrade))
+        .with_state(stream)
+}
+
+async fn upgrade(
+    ws: WebSocketUpgrade,
+    State(stream): State<Arc<PredictionStream>>,
+    Path(symbol): Path<String>,
+) -> Response {
+    ws.on_upgrade(move |socket| serve_socket(socket, stream, symbol))
+}
+
+async fn serve_socket(mut socket: WebSocket, stream: Arc<PredictionStream>, symbol: String) {
+    let mut rx = match stream.subscribe(&symbol) {
+        Ok(rx) => rx,
+        Err(e) => {
+            warn!("Cannot stream live predictions for {}: {}", symbol, e);
+            let _ = socket
+                .send(Message::Close(Some(CloseFrame {
+                    code: close_code::POLICY,
+                    reason: e.to_string().into(),
+                })))
+                .await;
+            return;
+        }
+    };
+
+    loop {
+        tokio::select! {
+            result = rx.recv() => match result {
+                Ok(result) => {
+                    let text = match serde_json::to_string(&result) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            warn!("Failed to serialize prediction for {}: {}", symbol, e);
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Live client for {} lagged, skipped {} predictions", symbol, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    debug!("Live client for {} disconnected", symbol);
+    stream.unsubscribe(&symbol);
+}
+
+/// Price and volume carried by a market data event, if it has a price
+fn observation(data: &MarketData) -> Option<(f64, f64)> {
+    match data {
+        MarketData::Trade(trade) => Some((data_ingestion::price::to_f64(trade.price), trade.size as f64)),
+        MarketData::Aggregate(agg) => Some((data_ingestion::price::to_f64(agg.close), agg.volume as f64)),
+        MarketData::Quote(_) => None,
+    }
+}
+
+async fn run_symbol(
+    manager: Arc<NeuralBridgeManager>,
+    mut rx: broadcast::Receiver<MarketData>,
+    tx: broadcast::Sender<PredictionResult>,
+    symbol: String,
+    window_len: usize,
+    horizon: usize,
+) {
+    let mut window = VecDeque::with_capacity(window_len);
+
+    loop {
+        let data = match rx.recv().await {
+            Ok(data) => data,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("Live predictions for {} lagged, skipped {} events", symbol, skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        let Some((price, volume)) = observation(&data) else {
+            continue;
+        };
+        // Out-of-order events would break timestamp alignment
+        if window.back().is_some_and(|(ts, _, _)| *ts >= data.timestamp()) {
+            continue;
+        }
+        if window.len() == window_len {
+            window.pop_front();
+        }
+        window.push_back((data.timestamp(), price, volume));
+        if window.len() < window_len {
+            continue;
+        }
+
+        let input = PredictionInput {
+            symbol: symbol.clone(),
+            historical_data: window.iter().map(|(_, price, _)| *price).collect(),
+            timestamps: window.iter().map(|(ts, _, _)| *ts).collect(),
+            features: HashMap::from([(
+                "volume".to_string(),
+                window.iter().map(|(_, _, volume)| *volume).collect(),
+            )]),
+            horizon,
+            seed: None,
+            resampling: None,
+        };
+        match manager.predict(input).await {
+            Ok(result) => {
+                let _ = tx.send(result);
+            }
+            Err(e) => warn!("Live prediction for {} failed: {}", symbol, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::mock_manager;
+    use chrono::TimeZone;
+    use data_ingestion::TradeData;
+
+    fn trade(symbol: &str, price: f64, offset: i64) -> MarketData {
+        MarketData::Trade(TradeData {
+            symbol: symbol.to_string(),
+            original_symbol: None,
+            price: data_ingestion::price::from_f64(price),
+            size: 100,
+            timestamp: chrono::Utc.timestamp_opt(offset, 0).unwrap(),
+            secondary_timestamp: None,
+            exchange: "4".to_string(),
+            conditions: vec![],
+        })
+    }
+
+    #[tokio::test]
+    async fn test_stream_predicts_and_stops_after_last_subscriber() {
+        let manager = mock_manager().await;
+
+        let (data_tx, _) = broadcast::channel(256);
+        let source_tx = data_tx.clone();
+        let stream = PredictionStream::new(
+            Arc::new(manager),
+            Arc::new(move |_: &str| source_tx.subscribe()),
+            LiveConfig {
+                window: Some(20),
+                horizon: 3,
+                channel_capacity: 8,
+            },
+        );
+
+        let mut first = stream.subscribe("AAPL").unwrap();
+        let _second = stream.subscribe("AAPL").unwrap();
+        for i in 0..20 {
+            data_tx.send(trade("AAPL", 100.0 + i as f64, i)).unwrap();
+        }
+
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), first.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.symbol, "AAPL");
+        assert_eq!(result.prediction.len(), 3);
+
+        stream.unsubscribe("AAPL");
+        assert_eq!(stream.active_symbols(), vec!["AAPL".to_string()]);
+        stream.unsubscribe("AAPL");
+        assert!(stream.active_symbols().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_window_follows_model_history() {
+        let manager = mock_manager().await;
+        let manager = Arc::new(manager);
+        let (data_tx, _) = broadcast::channel::<MarketData>(8);
+        let source: MarketDataSource = Arc::new(move |_: &str| data_tx.subscribe());
+
+        // Horizon 10 selects TFT, which needs 168 points
+        let defaults = PredictionStream::new(manager.clone(), source.clone(), LiveConfig::default());
+        assert_eq!(defaults.window_for("AAPL").unwrap(), 168);
+
+        let short = PredictionStream::new(
+            manager,
+            source,
+            LiveConfig {
+                window: Some(100),
+                ..LiveConfig::default()
+            },
+        );
+        assert!(short.subscribe("AAPL").is_err());
+        assert!(short.active_symbols().is_empty());
+    }
+}
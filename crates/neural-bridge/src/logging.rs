@@ -0,0 +1,20 @@
+//! Structured logging setup
+
+use tracing_subscriber::EnvFilter;
+
+/// Install a global JSON subscriber that includes the active span chain
+/// (e.g. `request_id`) on every event.
+///
+/// `RUST_LOG` overrides `default_filter` when set.
+pub fn init_json_logging(default_filter: &str) -> anyhow::Result<()> {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default_filter));
+
+    tracing_subscriber::fmt()
+        .json()
+        .with_env_filter(filter)
+        .with_current_span(true)
+        .with_span_list(true)
+        .try_init()
+        .map_err(|e| anyhow::anyhow!(e))
+}
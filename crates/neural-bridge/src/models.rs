@@ -1,8 +1,16 @@
 //! Model management and statistics
 
-use anyhow::Result;
+use crate::config::{ModelConfig, ModelSelectionPolicy};
+use crate::error::{NeuralBridgeError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+
+/// Current on-disk format version for persisted performance stats
+pub const PERFORMANCE_SCHEMA_VERSION: u32 = 1;
+
+/// Weight of the newest outcome in the rolling accuracy unless configured
+pub const DEFAULT_ACCURACY_SMOOTHING: f64 = 0.1;
 
 /// Model performance statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +23,165 @@ pub struct ModelStats {
     pub failed_predictions: u64,
     pub last_used: chrono::DateTime<chrono::Utc>,
     pub memory_usage_mb: f64,
+    #[serde(default)]
+    pub outcomes_recorded: u64,
+    /// Rolling fraction of forecast steps with the realized direction
+    #[serde(default)]
+    pub directional_accuracy: f64,
+}
+
+impl ModelStats {
+    fn new(model_name: &str) -> Self {
+        Self {
+            model_name: model_name.to_string(),
+            accuracy: 0.0,
+            average_inference_time_ms: 0.0,
+            total_predictions: 0,
+            successful_predictions: 0,
+            failed_predictions: 0,
+            last_used: chrono::Utc::now(),
+            memory_usage_mb: 0.0,
+            outcomes_recorded: 0,
+            directional_accuracy: 0.0,
+        }
+    }
+}
+
+/// What a model needs from a `PredictionInput`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRequirements {
+    pub model_name: String,
+    pub model_type: String,
+    /// Features that must be present; `price` is always supplied by the input
+    pub required_features: Vec<String>,
+    /// Horizons the model is tuned for
+    pub optimal_horizons: Vec<usize>,
+    /// Lookback window the model was trained with, if declared
+    pub input_length: Option<usize>,
+    /// Fewest price points the model accepts unless `padded`
+    pub min_history_points: usize,
+    /// Shorter inputs are padded instead of rejected
+    #[serde(default)]
+    pub padded: bool,
+}
+
+impl ModelRequirements {
+    /// Build from a model's configuration
+    pub fn from_config(model_name: &str, config: &crate::config::ModelConfig) -> Self {
+        Self {
+            model_name: model_name.to_string(),
+            model_type: config.model_type.clone(),
+            required_features: config.required_features.clone(),
+            optimal_horizons: config.optimal_horizons.clone(),
+            input_length: config.input_size(),
+            min_history_points: config.min_history_points(),
+            padded: config.padding.is_enabled(),
+        }
+    }
+
+    /// Required features missing from an input
+    pub fn missing_features(&self, input: &crate::PredictionInput) -> Vec<String> {
+        let available = input.available_features();
+        self.required_features
+            .iter()
+            .filter(|feature| !available.contains(feature))
+            .cloned()
+            .collect()
+    }
+
+    /// Check that an input supplies the features and history this model needs
+    pub fn check(&self, input: &crate::PredictionInput) -> Result<()> {
+        let missing = self.missing_features(input);
+        if !missing.is_empty() {
+            return Err(NeuralBridgeError::Validation(format!(
+                "Model {} requires missing features: {}",
+                self.model_name,
+                missing.join(", ")
+            )));
+        }
+        
+        self.check_history(input)
+    }
+
+    /// Check that an input has enough history, unless the model pads short inputs
+    pub fn check_history(&self, input: &crate::PredictionInput) -> Result<()> {
+        if self.padded {
+            return Ok(());
+        }
+        crate::prediction::PredictionValidator::validate_history(
+            input,
+            &self.model_name,
+            self.min_history_points,
+        )
+    }
+}
+
+/// Fraction of steps whose predicted change has the sign of the realized
+/// change, each measured from the previous step (`last_known` for the first).
+///
+/// A flat step only matches a flat step. Compares the overlapping prefix and
+/// returns 0 when there is nothing to compare.
+pub fn directional_accuracy(predicted: &[f64], actual: &[f64], last_known: f64) -> f64 {
+    let direction = |change: f64| {
+        if change > 0.0 {
+            1
+        } else if change < 0.0 {
+            -1
+        } else {
+            0
+        }
+    };
+    
+    let mut previous = (last_known, last_known);
+    let mut hits = 0;
+    let mut steps = 0;
+    for (&p, &a) in predicted.iter().zip(actual) {
+        if direction(p - previous.0) == direction(a - previous.1) {
+            hits += 1;
+        }
+        steps += 1;
+        previous = (p, a);
+    }
+    
+    if steps == 0 {
+        0.0
+    } else {
+        hits as f64 / steps as f64
+    }
+}
+
+/// Error metric used to score realized forecasts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ErrorMetric {
+    /// Mean absolute percentage error; points with a zero actual are skipped
+    Mape,
+    /// Symmetric MAPE, bounded to [0, 1]; points where both values are zero are skipped
+    #[default]
+    Smape,
+}
+
+impl ErrorMetric {
+    /// Compute the error over the overlapping prefix of both series
+    pub fn compute(&self, predicted: &[f64], actual: &[f64]) -> Option<f64> {
+        let errors: Vec<f64> = predicted
+            .iter()
+            .zip(actual)
+            .filter_map(|(&p, &a)| match self {
+                ErrorMetric::Mape if a != 0.0 => Some(((a - p) / a).abs()),
+                ErrorMetric::Smape if p.abs() + a.abs() != 0.0 => {
+                    Some((a - p).abs() / (p.abs() + a.abs()))
+                }
+                _ => None,
+            })
+            .filter(|e| e.is_finite())
+            .collect();
+
+        if errors.is_empty() {
+            None
+        } else {
+            Some(errors.iter().sum::<f64>() / errors.len() as f64)
+        }
+    }
 }
 
 /// Model metadata
@@ -29,16 +196,68 @@ pub struct ModelMetadata {
     pub hyperparameters: HashMap<String, serde_json::Value>,
 }
 
+/// On-disk representation of tracked performance stats
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedPerformance {
+    schema_version: u32,
+    saved_at: chrono::DateTime<chrono::Utc>,
+    stats: HashMap<String, ModelStats>,
+}
+
 /// Model performance tracker
 pub struct ModelPerformanceTracker {
     stats: HashMap<String, ModelStats>,
+    error_metric: ErrorMetric,
+    accuracy_smoothing: f64,
 }
 
 impl ModelPerformanceTracker {
     pub fn new() -> Self {
         Self {
             stats: HashMap::new(),
+            error_metric: ErrorMetric::default(),
+            accuracy_smoothing: DEFAULT_ACCURACY_SMOOTHING,
+        }
+    }
+
+    /// Use the given error metric and smoothing factor (0-1] for rolling accuracy
+    pub fn with_error_metric(mut self, metric: ErrorMetric, smoothing: f64) -> Self {
+        self.error_metric = metric;
+        self.accuracy_smoothing = smoothing.clamp(f64::EPSILON, 1.0);
+        self
+    }
+
+    /// Write stats to a JSON file, replacing it atomically
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let persisted = PersistedPerformance {
+            schema_version: PERFORMANCE_SCHEMA_VERSION,
+            saved_at: chrono::Utc::now(),
+            stats: self.stats.clone(),
+        };
+
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(&persisted)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Load stats previously written by `save`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let persisted: PersistedPerformance = serde_json::from_slice(&bytes)?;
+
+        if persisted.schema_version != PERFORMANCE_SCHEMA_VERSION {
+            return Err(NeuralBridgeError::Persistence(format!(
+                "Unsupported performance stats schema version {} (expected {})",
+                persisted.schema_version, PERFORMANCE_SCHEMA_VERSION
+            )));
         }
+
+        Ok(Self {
+            stats: persisted.stats,
+            ..Self::new()
+        })
     }
 
     /// Record a prediction
@@ -48,16 +267,10 @@ impl ModelPerformanceTracker {
         inference_time_ms: f64,
         success: bool,
     ) {
-        let stats = self.stats.entry(model_name.to_string()).or_insert_with(|| ModelStats {
-            model_name: model_name.to_string(),
-            accuracy: 0.0,
-            average_inference_time_ms: 0.0,
-            total_predictions: 0,
-            successful_predictions: 0,
-            failed_predictions: 0,
-            last_used: chrono::Utc::now(),
-            memory_usage_mb: 0.0,
-        });
+        let stats = self
+            .stats
+            .entry(model_name.to_string())
+            .or_insert_with(|| ModelStats::new(model_name));
 
         stats.total_predictions += 1;
         if success {
@@ -85,6 +298,62 @@ impl ModelPerformanceTracker {
         &self.stats
     }
 
+    /// Fraction of successful predictions, if the model has been used
+    pub fn success_rate(&self, model_name: &str) -> Option<f64> {
+        self.stats
+            .get(model_name)
+            .filter(|stats| stats.total_predictions > 0)
+            .map(|stats| stats.successful_predictions as f64 / stats.total_predictions as f64)
+    }
+
+    /// Score a realized forecast and fold it into the rolling accuracy and
+    /// directional accuracy; `last_known` is the last price the forecast saw.
+    ///
+    /// Only the overlapping prefix is compared when lengths differ. Returns
+    /// the updated accuracy (1 - error, clamped to [0, 1]).
+    pub fn record_outcome(
+        &mut self,
+        model_name: &str,
+        predicted: &[f64],
+        actual: &[f64],
+        last_known: f64,
+    ) -> Result<f64> {
+        if predicted.len() != actual.len() {
+            tracing::debug!(
+                "Outcome length mismatch for {} (predicted {}, actual {}), comparing overlap",
+                model_name,
+                predicted.len(),
+                actual.len()
+            );
+        }
+
+        let error = self.error_metric.compute(predicted, actual).ok_or_else(|| {
+            NeuralBridgeError::Validation(format!(
+                "No comparable points in outcome for {}",
+                model_name
+            ))
+        })?;
+        let observed_accuracy = (1.0 - error).clamp(0.0, 1.0);
+        let observed_direction = directional_accuracy(predicted, actual, last_known);
+
+        let stats = self
+            .stats
+            .entry(model_name.to_string())
+            .or_insert_with(|| ModelStats::new(model_name));
+        if stats.outcomes_recorded == 0 {
+            stats.accuracy = observed_accuracy;
+            stats.directional_accuracy = observed_direction;
+        } else {
+            let smoothing = self.accuracy_smoothing;
+            stats.accuracy = smoothing * observed_accuracy + (1.0 - smoothing) * stats.accuracy;
+            stats.directional_accuracy =
+                smoothing * observed_direction + (1.0 - smoothing) * stats.directional_accuracy;
+        }
+        stats.outcomes_recorded += 1;
+
+        Ok(stats.accuracy)
+    }
+
     /// Update model accuracy
     pub fn update_accuracy(&mut self, model_name: &str, accuracy: f64) {
         if let Some(stats) = self.stats.get_mut(model_name) {
@@ -107,51 +376,128 @@ pub struct ModelSelector {
 
 impl ModelSelector {
     pub fn new() -> Self {
+        Self::with_tracker(ModelPerformanceTracker::new())
+    }
+
+    /// Create a selector around an existing performance tracker
+    pub fn with_tracker(performance_tracker: ModelPerformanceTracker) -> Self {
         Self {
-            performance_tracker: ModelPerformanceTracker::new(),
+            performance_tracker,
         }
     }
 
-    /// Select best model for given criteria
+    /// Select best model for given criteria.
+    ///
+    /// Models whose required features are not all in `features` are never
+    /// chosen; if none of the horizon's candidates can run, the error names
+    /// what each one is missing.
     pub fn select_model(
         &self,
         horizon: usize,
-        symbol_type: &str,
+        _symbol_type: &str,
         features: &[String],
+        models: &HashMap<String, ModelConfig>,
     ) -> Result<String> {
         // Model selection logic based on:
-        // 1. Prediction horizon
-        // 2. Symbol characteristics
-        // 3. Available features
-        // 4. Historical performance
-
-        match horizon {
-            1..=5 => {
-                // Short-term: prefer N-BEATS
-                if self.is_model_performing_well("NBEATS") {
-                    Ok("NBEATS".to_string())
-                } else {
-                    Ok("TFT".to_string())
-                }
-            }
-            6..=20 => {
-                // Medium-term: prefer TFT
-                if self.is_model_performing_well("TFT") {
-                    Ok("TFT".to_string())
-                } else {
-                    Ok("LSTM".to_string())
-                }
+        // 1. Available features
+        // 2. Prediction horizon
+        // 3. Historical performance
+        let preference: &[&str] = match horizon {
+            // Short-term: prefer N-BEATS
+            1..=5 => &["NBEATS", "TFT"],
+            // Medium-term: prefer TFT
+            6..=20 => &["TFT", "LSTM"],
+            // Long-term: prefer LSTM
+            21.. => &["LSTM", "TFT"],
+            _ => return Err(NeuralBridgeError::Validation(format!("Invalid horizon: {}", horizon))),
+        };
+        
+        let usable = Self::filter_by_features(
+            preference.iter().map(|model| model.to_string()).collect(),
+            models,
+            features,
+        )?;
+        
+        // Fall back from the preferred model when it is underperforming
+        match usable.get(1) {
+            Some(fallback) if !self.is_model_performing_well(&usable[0]) => Ok(fallback.clone()),
+            _ => Ok(usable[0].clone()),
+        }
+    }
+
+    /// Select a model using a configured policy.
+    ///
+    /// Candidates from matching rules (or the policy default when no rule
+    /// matches) are limited to models whose required features are present,
+    /// then ranked by live performance; ties keep the policy's preference
+    /// order.
+    pub fn select_with_policy(
+        &self,
+        policy: &ModelSelectionPolicy,
+        horizon: usize,
+        symbol: &str,
+        features: &[String],
+        models: &HashMap<String, ModelConfig>,
+    ) -> Result<String> {
+        let mut candidates = policy.candidates(horizon, symbol, features);
+        if candidates.is_empty() {
+            candidates.push(policy.default_model.clone());
+        }
+        let candidates = Self::filter_by_features(candidates, models, features)?;
+        
+        let mut best: Option<(&String, (bool, f64))> = None;
+        for candidate in &candidates {
+            let score = (
+                self.is_model_performing_well(candidate),
+                self.performance_tracker.success_rate(candidate).unwrap_or(0.0),
+            );
+            if best.as_ref().map_or(true, |(_, best_score)| score > *best_score) {
+                best = Some((candidate, score));
             }
-            21.. => {
-                // Long-term: prefer LSTM
-                if self.is_model_performing_well("LSTM") {
-                    Ok("LSTM".to_string())
-                } else {
-                    Ok("TFT".to_string())
+        }
+        
+        Ok(best.map_or_else(|| policy.default_model.clone(), |(model, _)| model.clone()))
+    }
+
+    /// Keep candidates whose required features are all available.
+    ///
+    /// Models without a configuration declare no requirements.
+    fn filter_by_features(
+        candidates: Vec<String>,
+        models: &HashMap<String, ModelConfig>,
+        features: &[String],
+    ) -> Result<Vec<String>> {
+        let mut unmet = Vec::new();
+        let usable: Vec<String> = candidates
+            .into_iter()
+            .filter(|candidate| {
+                let missing: Vec<&str> = models
+                    .get(candidate)
+                    .map(|config| {
+                        config
+                            .required_features
+                            .iter()
+                            .filter(|feature| !features.contains(feature))
+                            .map(String::as_str)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if missing.is_empty() {
+                    return true;
                 }
-            }
-            _ => Err(anyhow::anyhow!("Invalid horizon: {}", horizon)),
+                unmet.push(format!("{} needs {}", candidate, missing.join(", ")));
+                false
+            })
+            .collect();
+        
+        if usable.is_empty() {
+            return Err(NeuralBridgeError::Validation(format!(
+                "No model can run with features [{}]: {}",
+                features.join(", "),
+                unmet.join("; ")
+            )));
         }
+        Ok(usable)
     }
 
     /// Check if model is performing well
@@ -186,4 +532,60 @@ impl ModelSelector {
     pub fn get_performance_tracker_mut(&mut self) -> &mut ModelPerformanceTracker {
         &mut self.performance_tracker
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_metrics_skip_zero_denominators() {
+        assert_eq!(ErrorMetric::Mape.compute(&[90.0, 5.0], &[100.0, 0.0]), Some(0.1));
+        assert_eq!(ErrorMetric::Smape.compute(&[0.0], &[0.0]), None);
+        assert_eq!(ErrorMetric::Smape.compute(&[50.0, 7.0], &[50.0]), Some(0.0));
+    }
+
+    #[test]
+    fn test_record_outcome_rolls_accuracy() {
+        let mut tracker = ModelPerformanceTracker::new().with_error_metric(ErrorMetric::Mape, 0.5);
+
+        let first = tracker.record_outcome("TFT", &[90.0], &[100.0], 95.0).unwrap();
+        assert!((first - 0.9).abs() < 1e-9);
+
+        let second = tracker.record_outcome("TFT", &[100.0], &[100.0], 95.0).unwrap();
+        assert!((second - 0.95).abs() < 1e-9);
+        assert_eq!(tracker.get_stats("TFT").unwrap().outcomes_recorded, 2);
+
+        assert!(tracker.record_outcome("TFT", &[], &[], 95.0).is_err());
+    }
+
+    #[test]
+    fn test_directional_accuracy_compares_step_changes() {
+        // Up then down predicted; up then up realized
+        assert_eq!(directional_accuracy(&[101.0, 100.5], &[102.0, 103.0], 100.0), 0.5);
+        assert_eq!(directional_accuracy(&[100.0], &[100.0], 100.0), 1.0);
+        assert_eq!(directional_accuracy(&[], &[], 100.0), 0.0);
+    }
+
+    #[test]
+    fn test_performance_stats_round_trip_and_reject_other_schema_versions() {
+        let path = std::env::temp_dir().join(format!("performance-stats-{}.json", std::process::id()));
+        let mut tracker = ModelPerformanceTracker::new();
+        tracker.record_prediction("TFT", 12.0, true);
+        tracker.record_prediction("TFT", 18.0, false);
+        tracker.save(&path).unwrap();
+
+        let restored = ModelPerformanceTracker::load(&path).unwrap();
+        let stats = restored.get_stats("TFT").unwrap();
+        assert_eq!((stats.successful_predictions, stats.failed_predictions), (1, 1));
+        assert_eq!(stats.average_inference_time_ms, 15.0);
+
+        let mut persisted: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        persisted["schema_version"] = (PERFORMANCE_SCHEMA_VERSION + 1).into();
+        std::fs::write(&path, serde_json::to_vec(&persisted).unwrap()).unwrap();
+        assert!(matches!(ModelPerformanceTracker::load(&path), Err(NeuralBridgeError::Persistence(_))));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,203 +1,256 @@
 //! NeuralForecast client implementation
 
-use crate::{config::NeuralBridgeConfig, models::ModelStats, PredictionInput, PredictionResult};
-use anyhow::Result;
-use pyo3::prelude::*;
-use std::collections::HashMap;
-use tracing::{debug, error, info, warn};
+use crate::{
+    backend::{BackendKind, InferenceBackend, PythonBackend},
+    config::NeuralBridgeConfig,
+    error::{NeuralBridgeError, Result},
+    models::ModelStats,
+    pool::InferencePool,
+    transform::{InterpolationMethod, PreTransform},
+    PredictionInput, PredictionResult,
+};
+use std::sync::Arc;
+use tracing::{debug, info};
 
-/// NeuralForecast client for model operations
+/// Warmup series length when the model doesn't declare `input_size`
+const WARMUP_INPUT_LENGTH: usize = 20;
+
+/// NeuralForecast client for model operations.
+///
+/// Backend calls run on an `InferencePool` sized by `worker_threads`.
+/// Cloning is cheap and shares the backend and pool.
+#[derive(Clone)]
 pub struct NeuralForecastClient {
-    config: crate::config::NeuralForecastConfig,
-    python_module: Option<PyObject>,
-    models: HashMap<String, PyObject>,
+    config: Arc<crate::config::NeuralForecastConfig>,
+    backend: Arc<dyn InferenceBackend>,
+    pool: InferencePool,
 }
 
 impl NeuralForecastClient {
-    /// Create new NeuralForecast client
+    /// Create new NeuralForecast client using the configured backend
     pub fn new(config: &NeuralBridgeConfig) -> Result<Self> {
-        Ok(Self {
-            config: config.neuralforecast.clone(),
-            python_module: None,
-            models: HashMap::new(),
-        })
+        let backend: Box<dyn InferenceBackend> = match config.neuralforecast.backend {
+            BackendKind::Python => Box::new(PythonBackend::new()),
+            BackendKind::Baseline => Box::new(crate::baselines::BaselineBackend::new()),
+            #[cfg(feature = "onnx")]
+            BackendKind::Onnx => Box::new(crate::backend::OnnxBackend::new(
+                config.performance.worker_threads,
+            )),
+            #[cfg(not(feature = "onnx"))]
+            BackendKind::Onnx => {
+                return Err(NeuralBridgeError::Backend(
+                    "ONNX backend requires the `onnx` feature".to_string(),
+                ));
+            }
+        };
+
+        Ok(Self::with_backend(config, backend))
     }
 
-    /// Initialize NeuralForecast Python environment
-    pub async fn initialize(&mut self) -> Result<()> {
-        info!("Initializing NeuralForecast Python environment");
-        
-        Python::with_gil(|py| -> Result<()> {
-            // Import required Python modules
-            let sys = py.import("sys")?;
-            let path = sys.getattr("path")?;
-            path.call_method1("append", ("/path/to/neuralforecast",))?;
-            
-            // Import NeuralForecast
-            let neuralforecast_module = py.import("neuralforecast")?;
-            self.python_module = Some(neuralforecast_module.into());
-            
-            info!("NeuralForecast environment initialized");
-            Ok(())
-        })
+    /// Create a client around an explicit backend
+    pub fn with_backend(config: &NeuralBridgeConfig, backend: Box<dyn InferenceBackend>) -> Self {
+        Self {
+            config: Arc::new(config.neuralforecast.clone()),
+            backend: Arc::from(backend),
+            pool: InferencePool::new(config.performance.worker_threads),
+        }
     }
 
-    /// Load a specific model
-    pub async fn load_model(&mut self, model_name: &str) -> Result<crate::cache::CachedModel> {
+    /// Pool that inference and model loading run on
+    pub fn pool(&self) -> &InferencePool {
+        &self.pool
+    }
+
+    /// Initialize the inference backend
+    pub async fn initialize(&self) -> Result<()> {
+        info!("Initializing {} inference backend", self.backend.name());
+        self.backend.initialize()
+    }
+
+    /// Load a specific model.
+    ///
+    /// The backend load (which may hold the GIL) runs on the inference pool;
+    /// the caller caches the returned model once it completes.
+    pub async fn load_model(&self, model_name: &str) -> Result<crate::cache::CachedModel> {
         info!("Loading NeuralForecast model: {}", model_name);
-        
+
         let model_config = self.config.models
             .get(model_name)
-            .ok_or_else(|| anyhow::anyhow!("Model {} not found in configuration", model_name))?;
-        
-        let model_data = Python::with_gil(|py| -> Result<Vec<u8>> {
-            let module = self.python_module
-                .as_ref()
-                .ok_or_else(|| anyhow::anyhow!("NeuralForecast not initialized"))?;
-            
-            // Load model based on type
-            let model = match model_config.model_type.as_str() {
-                "TemporalFusionTransformer" => {
-                    let tft_class = module.getattr(py, "TFT")?;
-                    tft_class.call_method1(py, "load", (&model_config.model_path,))?
-                }
-                "NBEATS" => {
-                    let nbeats_class = module.getattr(py, "NBEATS")?;
-                    nbeats_class.call_method1(py, "load", (&model_config.model_path,))?
-                }
-                "LSTM" => {
-                    let lstm_class = module.getattr(py, "LSTM")?;
-                    lstm_class.call_method1(py, "load", (&model_config.model_path,))?
-                }
-                _ => {
-                    return Err(anyhow::anyhow!("Unsupported model type: {}", model_config.model_type));
-                }
-            };
-            
-            // Store model for later use
-            self.models.insert(model_name.to_string(), model);
-            
-            // Serialize model data (placeholder)
-            Ok(vec![0u8; 1024]) // Placeholder serialized data
-        })?;
-        
+            .ok_or_else(|| NeuralBridgeError::ModelNotFound(model_name.to_string()))?;
+
+        let backend = Arc::clone(&self.backend);
+        let name = model_name.to_string();
+        let load_config = model_config.clone();
+        let size_bytes = self
+            .pool
+            .run(move || {
+                backend.load(&name, &load_config)?;
+                Ok::<_, NeuralBridgeError>(crate::cache::checkpoint_size(std::path::Path::new(
+                    &load_config.model_path,
+                )))
+            })
+            .await??;
+
         let metadata = crate::models::ModelMetadata {
             name: model_name.to_string(),
             model_type: model_config.model_type.clone(),
-            version: "1.0.0".to_string(),
+            version: model_config.version.clone(),
             created_at: chrono::Utc::now(),
             trained_on: "historical_market_data".to_string(),
             features: model_config.required_features.clone(),
             hyperparameters: model_config.parameters.clone(),
         };
-        
+
         let cached_model = crate::cache::CachedModel {
             name: model_name.to_string(),
-            model_data: std::sync::Arc::new(model_data),
+            size_bytes,
             metadata,
             last_accessed: std::time::Instant::now(),
             access_count: 0,
         };
-        
+
         info!("Model {} loaded successfully", model_name);
         Ok(cached_model)
     }
 
+    /// Run a dummy prediction shaped like real input so the first live
+    /// request doesn't pay the compilation/graph-build cost
+    pub async fn warmup(&self, model_name: &str) -> Result<std::time::Duration> {
+        let model_config = self.config.models
+            .get(model_name)
+            .ok_or_else(|| NeuralBridgeError::ModelNotFound(model_name.to_string()))?;
+
+        let input_size = model_config.input_size().unwrap_or(WARMUP_INPUT_LENGTH);
+        let horizon = model_config.optimal_horizons.first().copied().unwrap_or(1);
+
+        let now = chrono::Utc::now();
+        let timestamps = (0..input_size)
+            .rev()
+            .map(|i| now - chrono::Duration::minutes(i as i64))
+            .collect();
+        // Price is the target series, not an exogenous column
+        let features = model_config.required_features
+            .iter()
+            .filter(|name| *name != "price")
+            .map(|name| (name.clone(), vec![0.0; input_size]))
+            .collect();
+
+        let input = PredictionInput {
+            symbol: "WARMUP".to_string(),
+            historical_data: vec![1.0; input_size],
+            timestamps,
+            features,
+            horizon,
+            seed: None,
+            resampling: None,
+        };
+
+        let backend = Arc::clone(&self.backend);
+        let model_name = model_name.to_string();
+        self.pool
+            .run(move || {
+                let start_time = std::time::Instant::now();
+                backend.predict(&model_name, &input)?;
+                Ok::<_, NeuralBridgeError>(start_time.elapsed())
+            })
+            .await?
+    }
+
     /// Generate prediction using specified model
+    #[tracing::instrument(skip_all, fields(backend = self.backend.name(), model = model_name))]
     pub async fn predict(
         &self,
         input: &PredictionInput,
         model_name: &str,
     ) -> Result<PredictionResult> {
-        let start_time = std::time::Instant::now();
-        
-        debug!("Generating prediction for {} using {}", input.symbol, model_name);
-        
-        let result = Python::with_gil(|py| -> Result<PredictionResult> {
-            let model = self.models
-                .get(model_name)
-                .ok_or_else(|| anyhow::anyhow!("Model {} not loaded", model_name))?;
-            
-            // Convert input data to Python format
-            let py_data = self.convert_input_to_python(py, input)?;
-            
-            // Generate prediction
-            let prediction = model.call_method1(py, "predict", (py_data,))?;
-            
-            // Convert result back to Rust format
-            self.convert_prediction_from_python(py, prediction, input, model_name)
-        })?;
-        
-        let elapsed = start_time.elapsed();
-        debug!("Prediction completed in {}μs", elapsed.as_micros());
-        
-        Ok(result)
+        let client = self.clone();
+        let input = input.clone();
+        let model_name = model_name.to_string();
+        self.pool
+            .run(move || client.predict_streaming(&input, &model_name, &mut |_| {}))
+            .await?
     }
 
-    /// Convert Rust input to Python format
-    fn convert_input_to_python(&self, py: Python, input: &PredictionInput) -> Result<PyObject> {
-        // Convert historical data to numpy array
-        let numpy = py.import("numpy")?;
-        let py_data = numpy.call_method1("array", (input.historical_data.clone(),))?;
-        
-        // Create input dictionary
-        let input_dict = pyo3::types::PyDict::new(py);
-        input_dict.set_item("data", py_data)?;
-        input_dict.set_item("horizon", input.horizon)?;
-        input_dict.set_item("symbol", &input.symbol)?;
-        
-        Ok(input_dict.into())
-    }
-
-    /// Convert Python prediction result to Rust format
-    fn convert_prediction_from_python(
+    /// Generate a prediction, passing each block of forecast prices to `emit`
+    /// as the backend produces it. Blocks the calling thread; async callers
+    /// should run it on `pool()`.
+    pub fn predict_streaming(
         &self,
-        py: Python,
-        prediction: PyObject,
         input: &PredictionInput,
         model_name: &str,
+        emit: &mut dyn FnMut(&[f64]),
     ) -> Result<PredictionResult> {
-        // Extract prediction values (assuming numpy array)
-        let prediction_array = prediction.extract::<Vec<f64>>(py)?;
-        
-        // Calculate confidence (placeholder logic)
-        let confidence = 0.85; // Would be calculated based on model uncertainty
-        
-        let mut metadata = HashMap::new();
-        metadata.insert(
-            "input_length".to_string(),
-            serde_json::Value::Number(serde_json::Number::from(input.historical_data.len())),
-        );
-        metadata.insert(
-            "model_type".to_string(),
-            serde_json::Value::String(model_name.to_string()),
+        let start_time = std::time::Instant::now();
+
+        debug!("Generating prediction for {} using {}", input.symbol, model_name);
+
+        let model_config = self.config.models.get(model_name);
+        let interpolation = model_config.map(|model| model.interpolation).unwrap_or_default();
+        let (input, interpolated) = interpolation.apply(input)?;
+        let input = input.as_ref();
+        let pre_transform = model_config.map(|model| model.pre_transform).unwrap_or_default();
+        let last_price = input.historical_data.last().copied().unwrap_or_default();
+
+        let mut result = match pre_transform {
+            PreTransform::None => self.backend.predict_streaming(model_name, input, emit)?,
+            transform => {
+                // Blocks are inverted as they arrive, continuing from the
+                // last price emitted so far
+                let mut block_start = last_price;
+                let mut emit_prices = |values: &[f64]| {
+                    let prices = transform.inverse(block_start, values);
+                    if let Some(&price) = prices.last() {
+                        block_start = price;
+                    }
+                    emit(&prices);
+                };
+                let mut result = self.backend.predict_streaming(
+                    model_name,
+                    &transform.apply(input)?,
+                    &mut emit_prices,
+                )?;
+                result.prediction = transform.inverse(last_price, &result.prediction);
+                // Interval bounds are treated as paths, which approximates
+                // the price-space interval
+                if let Some(interval) = result.metadata.get_mut("interval") {
+                    for key in ["lower", "upper"] {
+                        let bounds = interval.get(key).and_then(|v| serde_json::from_value::<Vec<f64>>(v.clone()).ok());
+                        if let Some(bounds) = bounds {
+                            interval[key] = serde_json::json!(transform.inverse(last_price, &bounds));
+                        }
+                    }
+                }
+                result
+            }
+        };
+        result.metadata.insert(
+            "pre_transform".to_string(),
+            serde_json::json!(pre_transform.as_str()),
         );
-        
-        Ok(PredictionResult {
-            model_name: model_name.to_string(),
-            symbol: input.symbol.clone(),
-            prediction: prediction_array,
-            confidence,
-            timestamp: chrono::Utc::now(),
-            horizon: input.horizon,
-            metadata,
-        })
+        if let Some(seed) = input.seed {
+            result.metadata.insert("seed".to_string(), serde_json::json!(seed));
+        }
+        if interpolation.method != InterpolationMethod::None {
+            result.metadata.insert(
+                "interpolation".to_string(),
+                serde_json::json!({ "method": interpolation.method.as_str(), "filled_points": interpolated }),
+            );
+        }
+        if let Some(resampling) = &input.resampling {
+            result.metadata.insert("resampling".to_string(), serde_json::json!(resampling));
+        }
+        if let Some(model) = model_config {
+            result.metadata.insert("model_version".to_string(), serde_json::json!(model.version));
+        }
+
+        debug!("Prediction completed in {}μs", start_time.elapsed().as_micros());
+
+        Ok(result)
     }
 
     /// Get model performance statistics
     pub async fn get_model_stats(&self, model_name: &str) -> Result<ModelStats> {
-        // Placeholder implementation
-        // In practice, this would query the model's performance metrics
-        Ok(ModelStats {
-            model_name: model_name.to_string(),
-            accuracy: 0.80,
-            average_inference_time_ms: 8.5,
-            total_predictions: 1000,
-            successful_predictions: 950,
-            failed_predictions: 50,
-            last_used: chrono::Utc::now(),
-            memory_usage_mb: 256.0,
-        })
+        self.backend.stats(model_name)
     }
 
     /// Get available models
@@ -205,16 +258,18 @@ impl NeuralForecastClient {
         self.config.models.keys().cloned().collect()
     }
 
-    /// Health check for NeuralForecast environment
+    /// Name of the active inference backend
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Device the active backend runs inference on
+    pub fn device(&self) -> &'static str {
+        self.backend.device()
+    }
+
+    /// Health check for the inference backend
     pub async fn health_check(&self) -> Result<bool> {
-        Python::with_gil(|py| -> Result<bool> {
-            if let Some(module) = &self.python_module {
-                // Try to access the module
-                let _version = module.getattr(py, "__version__")?;
-                Ok(true)
-            } else {
-                Ok(false)
-            }
-        })
+        self.backend.health()
     }
-}
\ No newline at end of file
+}
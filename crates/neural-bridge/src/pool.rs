@@ -0,0 +1,55 @@
+//! Bounded pool for blocking inference work
+
+use crate::error::{NeuralBridgeError, Result};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Runs blocking work on Tokio's blocking threads with at most `size` jobs
+/// in flight; callers beyond that wait for a slot.
+///
+/// Sized by `PerformanceConfig::worker_threads`, so the number of
+/// concurrent inferences is capped independently of Tokio's own pool.
+#[derive(Debug, Clone)]
+pub struct InferencePool {
+    permits: Arc<Semaphore>,
+    size: usize,
+}
+
+impl InferencePool {
+    /// Create a pool running up to `size` jobs at once (at least one)
+    pub fn new(size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(size)),
+            size,
+        }
+    }
+
+    /// Maximum concurrent jobs
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Jobs that could start now without waiting
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// Wait for a slot, then run `job` on the blocking pool
+    pub async fn run<T, F>(&self, job: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|_| NeuralBridgeError::Backend("Inference pool closed".to_string()))?;
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            job()
+        })
+        .await
+        .map_err(|e| NeuralBridgeError::Backend(format!("Inference task failed: {}", e)))
+    }
+}
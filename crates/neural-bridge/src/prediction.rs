@@ -1,7 +1,9 @@
 //! Prediction utilities and helpers
 
-use crate::{PredictionInput, PredictionResult};
-use anyhow::Result;
+use crate::{
+    error::{NeuralBridgeError, Result},
+    PredictionInput, PredictionResult,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -13,10 +15,13 @@ pub struct PredictionRequest {
     pub priority: PredictionPriority,
     pub callback_url: Option<String>,
     pub request_id: String,
+    /// Skip the cache lookup; the fresh result is still cached
+    #[serde(default)]
+    pub bypass_cache: bool,
 }
 
-/// Prediction priority levels
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Prediction priority levels, ordered lowest to highest
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum PredictionPriority {
     Low,
     Normal,
@@ -42,6 +47,44 @@ pub struct PredictionResponse {
     pub status: PredictionStatus,
 }
 
+impl PredictionResponse {
+    /// Build a failed response carrying an empty result
+    pub fn failed(
+        request: &PredictionRequest,
+        error: &NeuralBridgeError,
+        processing_time_ms: u64,
+        queue_time_ms: u64,
+    ) -> Self {
+        Self {
+            result: PredictionResult {
+                model_name: request.model_preference.clone().unwrap_or_default(),
+                symbol: request.input.symbol.clone(),
+                prediction: vec![],
+                confidence: 0.0,
+                timestamp: chrono::Utc::now(),
+                horizon: request.input.horizon,
+                metadata: HashMap::new(),
+            },
+            request_id: request.request_id.clone(),
+            processing_time_ms,
+            queue_time_ms,
+            status: PredictionStatus::Failed {
+                error: error.to_string(),
+            },
+        }
+    }
+}
+
+/// Item yielded by a streaming prediction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PredictionStreamItem {
+    /// Forecast steps `start_step..start_step + values.len()`
+    Partial { start_step: usize, values: Vec<f64> },
+    /// Final result covering the whole horizon
+    Complete(PredictionResult),
+}
+
 /// Prediction status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PredictionStatus {
@@ -61,6 +104,219 @@ pub struct PredictionQuality {
     pub model_agreement: f64, // If ensemble is used
 }
 
+/// Confidence scaled by ensemble agreement when the result carries a
+/// `model_agreement` metadata entry; single-model results are unchanged
+pub fn effective_confidence(result: &PredictionResult) -> f64 {
+    let agreement = result
+        .metadata
+        .get("model_agreement")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(1.0);
+    result.confidence * agreement.clamp(0.0, 1.0)
+}
+
+/// Minimum price points for models that declare neither `min_history`
+/// nor `input_size`
+pub const MIN_HISTORY_POINTS: usize = 20;
+
+/// Builds a [`PredictionInput`] one observation at a time so the series stay aligned
+#[derive(Debug, Clone)]
+pub struct PredictionInputBuilder {
+    input: PredictionInput,
+}
+
+impl PredictionInputBuilder {
+    /// Start an input for a symbol with a horizon of 1
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self {
+            input: PredictionInput {
+                symbol: symbol.into(),
+                historical_data: Vec::new(),
+                timestamps: Vec::new(),
+                features: HashMap::new(),
+                horizon: 1,
+                seed: None,
+                resampling: None,
+            },
+        }
+    }
+
+    /// Append one price observation
+    pub fn add_price(mut self, timestamp: chrono::DateTime<chrono::Utc>, value: f64) -> Self {
+        self.input.timestamps.push(timestamp);
+        self.input.historical_data.push(value);
+        self
+    }
+
+    /// Add a feature series; it must cover every price observation
+    pub fn add_feature(mut self, name: impl Into<String>, values: Vec<f64>) -> Self {
+        self.input.features.insert(name.into(), values);
+        self
+    }
+
+    /// Set the number of steps to forecast
+    pub fn horizon(mut self, horizon: usize) -> Self {
+        self.input.horizon = horizon;
+        self
+    }
+
+    /// Seed the model RNGs for reproducible output
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.input.seed = Some(seed);
+        self
+    }
+
+    /// Validate alignment and values, and return the input. History length
+    /// is checked against the selected model at prediction time.
+    pub fn build(self) -> Result<PredictionInput> {
+        PredictionValidator::validate_input(&self.input)?;
+        Ok(self.input)
+    }
+}
+
+/// How values falling in one resampling interval are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    /// Last value in the interval
+    Last,
+    /// Mean of the values in the interval
+    Mean,
+    /// Close as the price, with `open`, `high` and `low` added as features
+    Ohlc,
+}
+
+/// Features added by `Aggregation::Ohlc`
+const OHLC_FEATURES: [&str; 3] = ["open", "high", "low"];
+
+/// Most intervals `resample` will produce, counting filled gaps
+pub const MAX_RESAMPLED_POINTS: usize = 100_000;
+
+/// Record of a `resample` call, carried on the input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Resampling {
+    pub interval_ms: i64,
+    pub aggregation: Aggregation,
+    /// Observations before resampling
+    pub source_points: usize,
+}
+
+/// Bucket an irregular series into evenly spaced intervals aligned to the
+/// Unix epoch, each stamped with its start time.
+///
+/// Features are aggregated like the price, except that `Ohlc` keeps their
+/// last value. Intervals without observations repeat the previous
+/// interval's closing values so the output has no gaps; a series spanning
+/// more than `MAX_RESAMPLED_POINTS` intervals is rejected.
+pub fn resample(
+    input: &PredictionInput,
+    interval: chrono::Duration,
+    aggregation: Aggregation,
+) -> Result<PredictionInput> {
+    let interval_ms = interval.num_milliseconds();
+    if interval_ms <= 0 {
+        return Err(NeuralBridgeError::Validation(format!(
+            "Resampling interval must be positive, got {}ms",
+            interval_ms
+        )));
+    }
+    PredictionValidator::validate_alignment(input)?;
+
+    let mut order: Vec<usize> = (0..input.timestamps.len()).collect();
+    order.sort_by_key(|&i| input.timestamps[i]);
+    let bucket = |i: usize| input.timestamps[i].timestamp_millis().div_euclid(interval_ms);
+
+    // Indices of the observations in each occupied interval, in time order
+    let mut groups: Vec<(i64, Vec<usize>)> = Vec::new();
+    for i in order {
+        match groups.last_mut() {
+            Some((b, members)) if *b == bucket(i) => members.push(i),
+            _ => groups.push((bucket(i), vec![i])),
+        }
+    }
+
+    if let (Some((first, _)), Some((last, _))) = (groups.first(), groups.last()) {
+        let intervals = last.saturating_sub(*first).saturating_add(1);
+        if intervals > MAX_RESAMPLED_POINTS as i64 {
+            return Err(NeuralBridgeError::Validation(format!(
+                "Resampling at {}ms spans {} intervals, more than the {} allowed",
+                interval_ms, intervals, MAX_RESAMPLED_POINTS
+            )));
+        }
+    }
+
+    // Derived OHLC features replace any the input already had
+    let feature_names: Vec<&String> = input
+        .features
+        .keys()
+        .filter(|name| aggregation != Aggregation::Ohlc || !OHLC_FEATURES.contains(&name.as_str()))
+        .collect();
+    let mut output = PredictionInput {
+        historical_data: Vec::new(),
+        timestamps: Vec::new(),
+        features: HashMap::new(),
+        resampling: Some(Resampling {
+            interval_ms,
+            aggregation,
+            source_points: input.historical_data.len(),
+        }),
+        ..input.clone()
+    };
+    let mut push = |b: i64, price: f64, ohlc: Option<[f64; 3]>, features: Vec<f64>| {
+        output.historical_data.push(price);
+        output.timestamps.push(
+            chrono::DateTime::from_timestamp_millis(b * interval_ms).unwrap_or_default(),
+        );
+        if let Some(ohlc) = ohlc {
+            for (name, value) in OHLC_FEATURES.into_iter().zip(ohlc) {
+                output.features.entry(name.to_string()).or_default().push(value);
+            }
+        }
+        for (name, value) in feature_names.iter().zip(features) {
+            output.features.entry(name.to_string()).or_default().push(value);
+        }
+    };
+
+    let mut previous: Option<(i64, f64, Vec<f64>)> = None;
+    for (b, members) in groups {
+        if let Some((last_bucket, close, features)) = &previous {
+            for gap in last_bucket + 1..b {
+                let ohlc = (aggregation == Aggregation::Ohlc).then_some([*close; 3]);
+                push(gap, *close, ohlc, features.clone());
+            }
+        }
+
+        let prices: Vec<f64> = members.iter().map(|&i| input.historical_data[i]).collect();
+        let close = prices[prices.len() - 1];
+        let (price, ohlc) = match aggregation {
+            Aggregation::Last => (close, None),
+            Aggregation::Mean => (prices.iter().sum::<f64>() / prices.len() as f64, None),
+            Aggregation::Ohlc => {
+                let high = prices.iter().copied().fold(f64::MIN, f64::max);
+                let low = prices.iter().copied().fold(f64::MAX, f64::min);
+                (close, Some([prices[0], high, low]))
+            }
+        };
+        let features: Vec<f64> = feature_names
+            .iter()
+            .map(|name| {
+                let values = &input.features[*name];
+                match aggregation {
+                    Aggregation::Mean => {
+                        members.iter().map(|&i| values[i]).sum::<f64>() / members.len() as f64
+                    }
+                    Aggregation::Last | Aggregation::Ohlc => values[members[members.len() - 1]],
+                }
+            })
+            .collect();
+
+        push(b, price, ohlc, features.clone());
+        previous = Some((b, close, features));
+    }
+
+    Ok(output)
+}
+
 /// Prediction validator
 pub struct PredictionValidator;
 
@@ -69,33 +325,69 @@ impl PredictionValidator {
     pub fn validate_input(input: &PredictionInput) -> Result<()> {
         // Check symbol
         if input.symbol.is_empty() {
-            return Err(anyhow::anyhow!("Empty symbol"));
+            return Err(NeuralBridgeError::Validation("Empty symbol".to_string()));
         }
         
         // Check historical data
         if input.historical_data.is_empty() {
-            return Err(anyhow::anyhow!("Empty historical data"));
-        }
-        
-        if input.historical_data.len() < 20 {
-            return Err(anyhow::anyhow!("Insufficient historical data (minimum 20 points)"));
+            return Err(NeuralBridgeError::Validation("Empty historical data".to_string()));
         }
         
-        // Check for invalid values
+        // Check for invalid values; NaN marks a missing price, filled by
+        // the model's interpolation
         for &value in &input.historical_data {
-            if value.is_nan() || value.is_infinite() || value < 0.0 {
-                return Err(anyhow::anyhow!("Invalid data point: {}", value));
+            if value.is_infinite() || value < 0.0 {
+                return Err(NeuralBridgeError::Validation(format!(
+                    "Invalid data point: {}",
+                    value
+                )));
             }
         }
         
-        // Check timestamps
-        if input.timestamps.len() != input.historical_data.len() {
-            return Err(anyhow::anyhow!("Timestamp and data length mismatch"));
-        }
+        Self::validate_alignment(input)?;
         
         // Check horizon
         if input.horizon == 0 || input.horizon > 100 {
-            return Err(anyhow::anyhow!("Invalid prediction horizon: {}", input.horizon));
+            return Err(NeuralBridgeError::Validation(format!(
+                "Invalid prediction horizon: {}",
+                input.horizon
+            )));
+        }
+        
+        Ok(())
+    }
+    
+    /// Check that the input has at least the history a model needs
+    pub fn validate_history(input: &PredictionInput, model_name: &str, min_points: usize) -> Result<()> {
+        if input.historical_data.len() < min_points {
+            return Err(NeuralBridgeError::Validation(format!(
+                "Insufficient historical data: {} points, model {} requires at least {}",
+                input.historical_data.len(),
+                model_name,
+                min_points
+            )));
+        }
+        
+        Ok(())
+    }
+    
+    /// Check that timestamps and every feature vector line up with the price series
+    pub fn validate_alignment(input: &PredictionInput) -> Result<()> {
+        if input.timestamps.len() != input.historical_data.len() {
+            return Err(NeuralBridgeError::Validation(
+                "Timestamp and data length mismatch".to_string(),
+            ));
+        }
+        
+        for (name, values) in &input.features {
+            if values.len() != input.historical_data.len() {
+                return Err(NeuralBridgeError::Validation(format!(
+                    "Feature {} has {} points, expected {}",
+                    name,
+                    values.len(),
+                    input.historical_data.len()
+                )));
+            }
         }
         
         Ok(())
@@ -105,34 +397,61 @@ impl PredictionValidator {
     pub fn validate_result(result: &PredictionResult) -> Result<()> {
         // Check prediction values
         if result.prediction.is_empty() {
-            return Err(anyhow::anyhow!("Empty prediction"));
+            return Err(NeuralBridgeError::Validation("Empty prediction".to_string()));
         }
         
         // Check for invalid predictions
         for &value in &result.prediction {
             if value.is_nan() || value.is_infinite() {
-                return Err(anyhow::anyhow!("Invalid prediction value: {}", value));
+                return Err(NeuralBridgeError::Validation(format!(
+                    "Invalid prediction value: {}",
+                    value
+                )));
             }
         }
         
         // Check confidence
         if result.confidence < 0.0 || result.confidence > 1.0 {
-            return Err(anyhow::anyhow!("Invalid confidence: {}", result.confidence));
+            return Err(NeuralBridgeError::Validation(format!(
+                "Invalid confidence: {}",
+                result.confidence
+            )));
         }
         
         // Check horizon matches prediction length
         if result.prediction.len() != result.horizon {
-            return Err(anyhow::anyhow!(
+            return Err(NeuralBridgeError::Validation(format!(
                 "Prediction length ({}) doesn't match horizon ({})",
                 result.prediction.len(),
                 result.horizon
-            ));
+            )));
         }
         
         Ok(())
     }
 }
 
+/// Decimal places used when formatting predictions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormatOptions {
+    /// Places for predicted values; raise for low-priced instruments,
+    /// lower for index levels
+    pub value_decimals: usize,
+
+    /// Places for confidence as a fraction. Summaries show it as a
+    /// percentage, two places fewer.
+    pub confidence_decimals: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            value_decimals: 6,
+            confidence_decimals: 4,
+        }
+    }
+}
+
 /// Prediction formatter for different output formats
 pub struct PredictionFormatter;
 
@@ -144,6 +463,11 @@ impl PredictionFormatter {
     
     /// Format prediction as CSV
     pub fn to_csv(result: &PredictionResult) -> Result<String> {
+        Self::to_csv_with(result, &FormatOptions::default())
+    }
+    
+    /// Format prediction as CSV with the given precision
+    pub fn to_csv_with(result: &PredictionResult, options: &FormatOptions) -> Result<String> {
         let mut csv = String::new();
         csv.push_str("timestamp,symbol,value,confidence\n");
         
@@ -151,10 +475,12 @@ impl PredictionFormatter {
         for (i, &value) in result.prediction.iter().enumerate() {
             let timestamp = base_time + chrono::Duration::minutes(i as i64);
             csv.push_str(&format!(
-                "{},{},{:.6},{:.4}\n",
+                "{},{},{:.*},{:.*}\n",
                 timestamp.format("%Y-%m-%d %H:%M:%S"),
                 result.symbol,
+                options.value_decimals,
                 value,
+                options.confidence_decimals,
                 result.confidence
             ));
         }
@@ -164,24 +490,34 @@ impl PredictionFormatter {
     
     /// Format prediction as summary
     pub fn to_summary(result: &PredictionResult) -> String {
+        Self::to_summary_with(result, &FormatOptions::default())
+    }
+    
+    /// Format prediction as summary with the given precision
+    pub fn to_summary_with(result: &PredictionResult, options: &FormatOptions) -> String {
         let avg_prediction = result.prediction.iter().sum::<f64>() / result.prediction.len() as f64;
         let min_prediction = result.prediction.iter().fold(f64::INFINITY, |a, &b| a.min(b));
         let max_prediction = result.prediction.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let places = options.value_decimals;
         
         format!(
             "Prediction Summary for {}:\n\
             Model: {}\n\
             Horizon: {} periods\n\
-            Confidence: {:.2}%\n\
-            Average: {:.6}\n\
-            Range: {:.6} - {:.6}\n\
+            Confidence: {:.*}%\n\
+            Average: {:.*}\n\
+            Range: {:.*} - {:.*}\n\
             Generated: {}",
             result.symbol,
             result.model_name,
             result.horizon,
+            options.confidence_decimals.saturating_sub(2),
             result.confidence * 100.0,
+            places,
             avg_prediction,
+            places,
             min_prediction,
+            places,
             max_prediction,
             result.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
         )
@@ -201,6 +537,8 @@ mod tests {
             timestamps: (0..50).map(|i| Utc::now() - chrono::Duration::minutes(50 - i)).collect(),
             features: HashMap::new(),
             horizon: 10,
+            seed: None,
+            resampling: None,
         };
         
         assert!(PredictionValidator::validate_input(&input).is_ok());
@@ -214,8 +552,112 @@ mod tests {
             timestamps: vec![],
             features: HashMap::new(),
             horizon: 10,
+            seed: None,
+            resampling: None,
         };
         
         assert!(PredictionValidator::validate_input(&input).is_err());
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_builder_checks_feature_alignment() {
+        let start = Utc::now();
+        let builder = (0..30).fold(PredictionInputBuilder::new("AAPL"), |b, i| {
+            b.add_price(start + chrono::Duration::minutes(i), 100.0 + i as f64)
+        });
+        
+        let input = builder.clone().add_feature("volume", vec![1.0; 30]).horizon(5).build().unwrap();
+        assert_eq!(input.historical_data.len(), 30);
+        assert_eq!(input.horizon, 5);
+        
+        let err = builder.add_feature("volume", vec![1.0; 29]).build().unwrap_err();
+        assert!(err.to_string().contains("volume has 29 points, expected 30"));
+    }
+    
+    #[test]
+    fn test_resample_buckets_and_fills_gaps() {
+        // 20s past a minute boundary
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let at = |seconds: i64| start + chrono::Duration::seconds(seconds);
+        let input = PredictionInputBuilder::new("AAPL")
+            .add_price(at(5), 101.0)
+            .add_price(at(1), 100.0)
+            .add_price(at(40), 104.0)
+            .add_price(at(170), 103.0)
+            .add_price(at(30), 99.0)
+            .add_feature("volume", vec![10.0, 20.0, 30.0, 40.0, 60.0])
+            .build()
+            .unwrap();
+        let minute = chrono::Duration::minutes(1);
+
+        let last = resample(&input, minute, Aggregation::Last).unwrap();
+        assert_eq!(last.historical_data, vec![99.0, 104.0, 104.0, 103.0]);
+        assert_eq!(last.timestamps, vec![at(-20), at(40), at(100), at(160)]);
+        assert_eq!(last.features["volume"], vec![60.0, 30.0, 30.0, 40.0]);
+        assert_eq!(
+            last.resampling,
+            Some(Resampling { interval_ms: 60_000, aggregation: Aggregation::Last, source_points: 5 })
+        );
+
+        let mean = resample(&input, minute, Aggregation::Mean).unwrap();
+        assert_eq!(mean.historical_data[0], 100.0);
+        assert_eq!(mean.features["volume"][0], 30.0);
+
+        let ohlc = resample(&input, minute, Aggregation::Ohlc).unwrap();
+        assert_eq!(ohlc.historical_data, vec![99.0, 104.0, 104.0, 103.0]);
+        assert_eq!(ohlc.features["open"], vec![100.0, 104.0, 104.0, 103.0]);
+        assert_eq!(ohlc.features["high"], vec![101.0, 104.0, 104.0, 103.0]);
+        assert_eq!(ohlc.features["low"], vec![99.0, 104.0, 104.0, 103.0]);
+    }
+
+    #[test]
+    fn test_resample_rejects_oversized_output() {
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let input = PredictionInputBuilder::new("AAPL")
+            .add_price(start, 100.0)
+            .add_price(start + chrono::Duration::days(365), 101.0)
+            .build()
+            .unwrap();
+
+        let err = resample(&input, chrono::Duration::seconds(1), Aggregation::Last).unwrap_err();
+        assert!(err.to_string().contains("more than the 100000 allowed"));
+        assert!(resample(&input, chrono::Duration::days(1), Aggregation::Last).is_ok());
+    }
+
+    #[test]
+    fn test_history_checked_against_model_minimum() {
+        let input = PredictionInputBuilder::new("AAPL")
+            .add_price(Utc::now(), 100.0)
+            .build()
+            .unwrap();
+        
+        assert!(PredictionValidator::validate_history(&input, "NAIVE", 1).is_ok());
+        let err = PredictionValidator::validate_history(&input, "TFT", 168).unwrap_err();
+        assert!(err.to_string().contains("1 points, model TFT requires at least 168"));
+    }
+    
+    #[test]
+    fn test_format_options_control_precision() {
+        let result = PredictionResult {
+            model_name: "NBEATS".to_string(),
+            symbol: "PENNY".to_string(),
+            prediction: vec![0.00012345],
+            confidence: 0.87654,
+            timestamp: Utc::now(),
+            horizon: 1,
+            metadata: HashMap::new(),
+        };
+        let options = FormatOptions {
+            value_decimals: 8,
+            confidence_decimals: 3,
+        };
+        
+        let csv = PredictionFormatter::to_csv_with(&result, &options).unwrap();
+        assert!(csv.lines().nth(1).unwrap().ends_with(",PENNY,0.00012345,0.877"));
+        assert!(PredictionFormatter::to_csv(&result).unwrap().contains(",0.000123,0.8765"));
+        
+        let summary = PredictionFormatter::to_summary_with(&result, &options);
+        assert!(summary.contains("Confidence: 87.7%"));
+        assert!(summary.contains("Average: 0.00012345"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Priority queue for prediction requests
+
+use crate::error::{NeuralBridgeError, Result};
+use crate::prediction::{PredictionPriority, PredictionRequest, PredictionResponse};
+use parking_lot::Mutex;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::time::Instant;
+use tokio::sync::{oneshot, Notify};
+
+/// Request waiting for a worker
+pub struct QueuedRequest {
+    pub request: PredictionRequest,
+    pub enqueued_at: Instant,
+    pub responder: oneshot::Sender<PredictionResponse>,
+    sequence: u64,
+}
+
+impl QueuedRequest {
+    fn priority(&self) -> &PredictionPriority {
+        &self.request.priority
+    }
+}
+
+impl PartialEq for QueuedRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedRequest {}
+
+impl PartialOrd for QueuedRequest {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedRequest {
+    /// Higher priority first, then earlier submission first
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority()
+            .cmp(other.priority())
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// Bounded priority queue; equal priorities are served FIFO
+pub struct PredictionQueue {
+    heap: Mutex<BinaryHeap<QueuedRequest>>,
+    notify: Notify,
+    next_sequence: AtomicU64,
+    capacity: usize,
+}
+
+impl PredictionQueue {
+    /// Create a queue holding at most `capacity` pending requests
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            heap: Mutex::new(BinaryHeap::new()),
+            notify: Notify::new(),
+            next_sequence: AtomicU64::new(0),
+            capacity,
+        }
+    }
+
+    /// Enqueue a request, returning a receiver for its response
+    pub fn push(&self, request: PredictionRequest) -> Result<oneshot::Receiver<PredictionResponse>> {
+        let (responder, receiver) = oneshot::channel();
+
+        {
+            let mut heap = self.heap.lock();
+            if heap.len() >= self.capacity {
+                return Err(NeuralBridgeError::QueueFull(self.capacity));
+            }
+
+            heap.push(QueuedRequest {
+                request,
+                enqueued_at: Instant::now(),
+                responder,
+                sequence: self.next_sequence.fetch_add(1, AtomicOrdering::Relaxed),
+            });
+        }
+
+        self.notify.notify_one();
+        Ok(receiver)
+    }
+
+    /// Wait for the highest-priority pending request
+    pub async fn pop(&self) -> QueuedRequest {
+        loop {
+            if let Some(queued) = self.heap.lock().pop() {
+                return queued;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Number of pending requests
+    pub fn len(&self) -> usize {
+        self.heap.lock().len()
+    }
+
+    /// Whether no requests are pending
+    pub fn is_empty(&self) -> bool {
+        self.heap.lock().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PredictionInput;
+    use std::collections::HashMap;
+
+    fn request(id: &str, priority: PredictionPriority) -> PredictionRequest {
+        PredictionRequest {
+            input: PredictionInput {
+                symbol: "AAPL".to_string(),
+                historical_data: vec![],
+                timestamps: vec![],
+                features: HashMap::new(),
+                horizon: 5,
+                seed: None,
+                resampling: None,
+            },
+            model_preference: None,
+            priority,
+            callback_url: None,
+            request_id: id.to_string(),
+            bypass_cache: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_critical_preempts_and_equal_priority_is_fifo() {
+        let queue = PredictionQueue::new(10);
+        queue.push(request("low", PredictionPriority::Low)).unwrap();
+        queue.push(request("normal-1", PredictionPriority::Normal)).unwrap();
+        queue.push(request("normal-2", PredictionPriority::Normal)).unwrap();
+        queue.push(request("critical", PredictionPriority::Critical)).unwrap();
+
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            order.push(queue.pop().await.request.request_id);
+        }
+
+        assert_eq!(order, vec!["critical", "normal-1", "normal-2", "low"]);
+    }
+
+    #[test]
+    fn test_push_rejects_when_full() {
+        let queue = PredictionQueue::new(1);
+        queue.push(request("first", PredictionPriority::Normal)).unwrap();
+
+        assert!(matches!(
+            queue.push(request("second", PredictionPriority::Normal)),
+            Err(NeuralBridgeError::QueueFull(1))
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+//! HTTP/JSON API over a shared `NeuralBridgeManager`
+//!
+//! - `POST /predict`: `PredictionRequest` -> `PredictionResponse`
+//! - `POST /batch`: `BatchPredictionRequest` -> `[PredictionResponse]`
+//! - `GET /models`: available model names
+//! - `GET /models/:name/stats`: `ModelStats`
+//!
+//! Failed predictions still return a `PredictionResponse`, with a status
+//! code derived from the error.
+
+use crate::{
+    prediction::{BatchPredictionRequest, PredictionRequest, PredictionResponse, PredictionStatus},
+    NeuralBridgeError, NeuralBridgeManager,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tracing::error;
+
+/// Router serving predictions from `manager`
+pub fn router(manager: Arc<NeuralBridgeManager>) -> Router {
+    Router::new()
+        .route("/predict", post(predict))
+        .route("/batch", post(batch))
+        .route("/models", get(models))
+        .route("/models/:name/stats", get(model_stats))
+        .with_state(manager)
+}
+
+/// HTTP status for a failed operation
+pub fn status_code(err: &NeuralBridgeError) -> StatusCode {
+    match err {
+        NeuralBridgeError::Validation(_) | NeuralBridgeError::UnsupportedModelType(_) => {
+            StatusCode::BAD_REQUEST
+        }
+        NeuralBridgeError::ModelNotFound(_) => StatusCode::NOT_FOUND,
+        NeuralBridgeError::LowConfidence { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+        NeuralBridgeError::PythonInitFailed(_)
+        | NeuralBridgeError::Python(_)
+        | NeuralBridgeError::ModelNotLoaded(_)
+        | NeuralBridgeError::WorkerStopped => StatusCode::SERVICE_UNAVAILABLE,
+        NeuralBridgeError::InferenceTimeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+        NeuralBridgeError::QueueFull(_) => StatusCode::TOO_MANY_REQUESTS,
+        NeuralBridgeError::Backend(_)
+        | NeuralBridgeError::Persistence(_)
+        | NeuralBridgeError::Io(_)
+        | NeuralBridgeError::Serialization(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+impl IntoResponse for NeuralBridgeError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({ "error": self.to_string() });
+        (status_code(&self), Json(body)).into_response()
+    }
+}
+
+async fn predict(
+    State(manager): State<Arc<NeuralBridgeManager>>,
+    Json(request): Json<PredictionRequest>,
+) -> Response {
+    let start_time = std::time::Instant::now();
+    let outcome = manager
+        .predict_with_model(
+            request.input.clone(),
+            request.model_preference.as_deref(),
+            crate::CacheMode::bypassing(request.bypass_cache),
+        )
+        .await;
+    let processing_time_ms = start_time.elapsed().as_millis() as u64;
+
+    match outcome {
+        Ok(result) => Json(PredictionResponse {
+            result,
+            request_id: request.request_id,
+            processing_time_ms,
+            queue_time_ms: 0,
+            status: PredictionStatus::Success,
+        })
+        .into_response(),
+        Err(e) => {
+            error!("Prediction request {} failed: {:?}", request.request_id, e);
+            let response = PredictionResponse::failed(&request, &e, processing_time_ms, 0);
+            (status_code(&e), Json(response)).into_response()
+        }
+    }
+}
+
+async fn batch(
+    State(manager): State<Arc<NeuralBridgeManager>>,
+    Json(batch): Json<BatchPredictionRequest>,
+) -> Json<Vec<PredictionResponse>> {
+    let mut responses = Vec::with_capacity(batch.requests.len());
+    for request in batch.requests {
+        responses.push(manager.predict_request(request).await);
+    }
+    Json(responses)
+}
+
+async fn models(State(manager): State<Arc<NeuralBridgeManager>>) -> Json<Vec<String>> {
+    let mut models = manager.get_available_models();
+    models.sort();
+    Json(models)
+}
+
+async fn model_stats(
+    State(manager): State<Arc<NeuralBridgeManager>>,
+    Path(name): Path<String>,
+) -> Result<Json<crate::models::ModelStats>, NeuralBridgeError> {
+    Ok(Json(manager.get_model_stats(&name).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prediction::PredictionPriority;
+    use crate::test_support::{aapl_input, mock_manager};
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn app() -> Router {
+        router(Arc::new(mock_manager().await))
+    }
+
+    fn request(horizon: usize) -> PredictionRequest {
+        PredictionRequest {
+            input: aapl_input(30, horizon),
+            model_preference: None,
+            priority: PredictionPriority::Normal,
+            callback_url: None,
+            request_id: "req-1".to_string(),
+            bypass_cache: false,
+        }
+    }
+
+    async fn post_predict(app: Router, request: &PredictionRequest) -> (StatusCode, PredictionResponse) {
+        let response = app
+            .oneshot(
+                Request::post("/predict")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(request).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_predict_returns_response() {
+        let (status, response) = post_predict(app().await, &request(3)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(matches!(response.status, PredictionStatus::Success));
+        assert_eq!(response.result.prediction.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_input_is_bad_request() {
+        let (status, response) = post_predict(app().await, &request(0)).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(matches!(response.status, PredictionStatus::Failed { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_model_stats_is_not_found() {
+        let response = app()
+            .await
+            .oneshot(Request::get("/models/NOPE/stats").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_backend_down_is_unavailable() {
+        let err = NeuralBridgeError::PythonInitFailed("no interpreter".to_string());
+        assert_eq!(status_code(&err), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -0,0 +1,29 @@
+//! Fixtures shared by the unit tests of several modules
+
+use crate::backend::MockBackend;
+use crate::{config::NeuralBridgeConfig, NeuralBridgeManager, PredictionInput};
+
+/// `points` one-minute AAPL prices rising from 100 and ending now, with a
+/// constant `volume` feature
+pub(crate) fn aapl_input(points: usize, horizon: usize) -> PredictionInput {
+    let now = chrono::Utc::now();
+    PredictionInput {
+        symbol: "AAPL".to_string(),
+        historical_data: (0..points).map(|i| 100.0 + i as f64).collect(),
+        timestamps: (0..points)
+            .map(|i| now - chrono::Duration::minutes((points - 1 - i) as i64))
+            .collect(),
+        features: [("volume".to_string(), vec![1_000.0; points])].into_iter().collect(),
+        horizon,
+        seed: None,
+        resampling: None,
+    }
+}
+
+/// Initialized manager over the default mock backend
+pub(crate) async fn mock_manager() -> NeuralBridgeManager {
+    let manager =
+        NeuralBridgeManager::with_backend(NeuralBridgeConfig::default(), Box::new(MockBackend::default())).unwrap();
+    manager.initialize().await.unwrap();
+    manager
+}
@@ -0,0 +1,590 @@
+//! Input transformations applied before inference and inverted on output
+
+use crate::{
+    error::{NeuralBridgeError, Result},
+    PredictionInput,
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How a model's price input is scaled
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScalerConfig {
+    /// Feed raw values
+    #[default]
+    None,
+    /// Standardize with the mean and standard deviation of the input window
+    ZScore,
+    /// Map the input window's range onto [0, 1]
+    MinMax,
+    /// Fixed parameters saved with the model at training time
+    Fixed { offset: f64, scale: f64 },
+}
+
+/// Fitted affine scaler: `scaled = (value - offset) / scale`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Scaler {
+    pub kind: ScalerConfig,
+    pub offset: f64,
+    pub scale: f64,
+}
+
+impl Scaler {
+    /// Fit to a window; returns `None` when the config leaves values raw.
+    ///
+    /// A flat window has zero spread, so its scale falls back to 1.
+    pub fn fit(config: ScalerConfig, values: &[f64]) -> Option<Self> {
+        let (offset, scale) = match config {
+            ScalerConfig::None => return None,
+            ScalerConfig::ZScore => {
+                let n = values.len().max(1) as f64;
+                let mean = values.iter().sum::<f64>() / n;
+                let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                (mean, variance.sqrt())
+            }
+            ScalerConfig::MinMax => {
+                let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                if min.is_finite() {
+                    (min, max - min)
+                } else {
+                    (0.0, 1.0)
+                }
+            }
+            ScalerConfig::Fixed { offset, scale } => (offset, scale),
+        };
+
+        let scale = if scale.is_finite() && scale.abs() > f64::EPSILON { scale } else { 1.0 };
+        Some(Self { kind: config, offset, scale })
+    }
+
+    /// Scale values into model space
+    pub fn transform(&self, values: &[f64]) -> Vec<f64> {
+        values.iter().map(|v| (v - self.offset) / self.scale).collect()
+    }
+
+    /// Map model output back to the original units
+    pub fn inverse(&self, values: &[f64]) -> Vec<f64> {
+        values.iter().map(|v| v * self.scale + self.offset).collect()
+    }
+}
+
+/// Transform of the price series into the space a model was trained on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreTransform {
+    /// Raw prices
+    #[default]
+    None,
+    /// First differences, `p[t] - p[t-1]`
+    Diff,
+    /// Log returns, `ln(p[t] / p[t-1])`
+    LogReturn,
+}
+
+impl PreTransform {
+    /// Name recorded in prediction metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PreTransform::None => "none",
+            PreTransform::Diff => "diff",
+            PreTransform::LogReturn => "log_return",
+        }
+    }
+
+    /// Transform a price series; the result is one point shorter
+    pub fn forward(&self, prices: &[f64]) -> Result<Vec<f64>> {
+        match self {
+            PreTransform::None => Ok(prices.to_vec()),
+            PreTransform::Diff => Ok(prices.windows(2).map(|w| w[1] - w[0]).collect()),
+            PreTransform::LogReturn => {
+                if let Some(price) = prices.iter().find(|price| **price <= 0.0) {
+                    return Err(NeuralBridgeError::Validation(format!(
+                        "Log returns need positive prices, got {}",
+                        price
+                    )));
+                }
+                Ok(prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect())
+            }
+        }
+    }
+
+    /// Rebuild forecast prices from transformed outputs, starting at the
+    /// last observed price
+    pub fn inverse(&self, last_price: f64, outputs: &[f64]) -> Vec<f64> {
+        match self {
+            PreTransform::None => outputs.to_vec(),
+            PreTransform::Diff => outputs
+                .iter()
+                .scan(last_price, |price, delta| {
+                    *price += delta;
+                    Some(*price)
+                })
+                .collect(),
+            PreTransform::LogReturn => outputs
+                .iter()
+                .scan(last_price.ln(), |log_price, ret| {
+                    *log_price += ret;
+                    Some(log_price.exp())
+                })
+                .collect(),
+        }
+    }
+
+    /// Input in transformed space, with timestamps and features trimmed to
+    /// stay aligned with the shorter series
+    pub fn apply(&self, input: &PredictionInput) -> Result<PredictionInput> {
+        if *self == PreTransform::None {
+            return Ok(input.clone());
+        }
+
+        let mut transformed = input.clone();
+        transformed.historical_data = self.forward(&input.historical_data)?;
+        transformed.timestamps = input.timestamps.iter().skip(1).copied().collect();
+        for values in transformed.features.values_mut() {
+            values.drain(..values.len().min(1));
+        }
+        Ok(transformed)
+    }
+}
+
+/// How inputs shorter than a model's window are padded at the start
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaddingStrategy {
+    /// Reject short inputs
+    #[default]
+    None,
+    /// Repeat the first observation
+    FirstValue,
+    /// Mirror the series about its first observation
+    Reflect,
+    /// Fill with the series mean
+    Mean,
+}
+
+impl PaddingStrategy {
+    /// Name recorded in prediction metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaddingStrategy::None => "none",
+            PaddingStrategy::FirstValue => "first_value",
+            PaddingStrategy::Reflect => "reflect",
+            PaddingStrategy::Mean => "mean",
+        }
+    }
+
+    /// Whether short inputs are padded rather than rejected
+    pub fn is_enabled(&self) -> bool {
+        *self != PaddingStrategy::None
+    }
+
+    /// Left-pad `values` to `target` points; longer series are unchanged
+    pub fn pad(&self, values: &[f64], target: usize) -> Vec<f64> {
+        let missing = target.saturating_sub(values.len());
+        let (Some(&first), true) = (values.first(), missing > 0 && self.is_enabled()) else {
+            return values.to_vec();
+        };
+
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let period = 2 * (values.len() - 1);
+        let mut padded: Vec<f64> = (1..=missing)
+            .rev()
+            .map(|distance| match self {
+                PaddingStrategy::None | PaddingStrategy::FirstValue => first,
+                PaddingStrategy::Mean => mean,
+                PaddingStrategy::Reflect if period == 0 => first,
+                PaddingStrategy::Reflect => {
+                    let offset = distance % period;
+                    values[if offset < values.len() { offset } else { period - offset }]
+                }
+            })
+            .collect();
+        padded.extend_from_slice(values);
+        padded
+    }
+
+    /// Input padded to `target` points, with timestamps extended backwards
+    /// at the first observed interval (one minute for a single point)
+    pub fn pad_input(&self, input: &PredictionInput, target: usize) -> PredictionInput {
+        let missing = target.saturating_sub(input.historical_data.len());
+        if missing == 0 || !self.is_enabled() || input.historical_data.is_empty() {
+            return input.clone();
+        }
+
+        let mut padded = input.clone();
+        padded.historical_data = self.pad(&input.historical_data, target);
+        for values in padded.features.values_mut() {
+            *values = self.pad(values, target);
+        }
+        if let Some(&first) = input.timestamps.first() {
+            let step = match input.timestamps.get(1) {
+                Some(&second) if second > first => second - first,
+                _ => chrono::Duration::minutes(1),
+            };
+            padded.timestamps = (1..=missing as i32)
+                .rev()
+                .map(|i| first - step * i)
+                .chain(input.timestamps.iter().copied())
+                .collect();
+        }
+        padded
+    }
+}
+
+/// How missing prices and feature values are filled before inference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterpolationMethod {
+    /// Reject inputs with missing values
+    #[default]
+    None,
+    /// Repeat the last observed value
+    ForwardFill,
+    /// Interpolate linearly between the surrounding observations
+    Linear,
+}
+
+impl InterpolationMethod {
+    /// Name recorded in prediction metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InterpolationMethod::None => "none",
+            InterpolationMethod::ForwardFill => "forward_fill",
+            InterpolationMethod::Linear => "linear",
+        }
+    }
+}
+
+/// Filling of gapped inputs: NaN values, and intervals missing from an
+/// otherwise regular timestamp sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterpolationConfig {
+    #[serde(default)]
+    pub method: InterpolationMethod,
+    /// Longest run of consecutive missing points that is filled; longer
+    /// runs reject the input as too sparse
+    #[serde(default = "default_max_gap")]
+    pub max_gap: usize,
+    /// Timestamp gaps at least this long that exceed `max_gap` are session
+    /// breaks (overnight, weekends) and are joined without filling; `None`
+    /// rejects every gap longer than `max_gap`
+    #[serde(default = "default_session_break_secs")]
+    pub session_break_secs: Option<u64>,
+}
+
+fn default_max_gap() -> usize {
+    5
+}
+
+fn default_session_break_secs() -> Option<u64> {
+    Some(3600)
+}
+
+impl Default for InterpolationConfig {
+    fn default() -> Self {
+        Self {
+            method: InterpolationMethod::None,
+            max_gap: default_max_gap(),
+            session_break_secs: default_session_break_secs(),
+        }
+    }
+}
+
+impl InterpolationConfig {
+    /// Fill gaps in `input`, returning it with the number of price points
+    /// filled. The input is borrowed unchanged when nothing is missing.
+    ///
+    /// Missing intervals are found from the median timestamp spacing. Gaps
+    /// at the start are filled with the first observation and, for
+    /// `Linear`, gaps at the end with the last. Session breaks are left as
+    /// they are.
+    pub fn apply<'a>(&self, input: &'a PredictionInput) -> Result<(Cow<'a, PredictionInput>, usize)> {
+        if self.method == InterpolationMethod::None {
+            if input.historical_data.iter().any(|value| value.is_nan()) {
+                return Err(NeuralBridgeError::Validation(
+                    "Input has missing prices and the model does not interpolate".to_string(),
+                ));
+            }
+            return Ok((Cow::Borrowed(input), 0));
+        }
+
+        let mut filled = self.insert_missing_intervals(input)?;
+        let missing = filled.historical_data.iter().filter(|value| value.is_nan()).count();
+        let features_missing = filled.features.values().flatten().any(|value| value.is_nan());
+        if missing == 0 && !features_missing {
+            return Ok((Cow::Borrowed(input), 0));
+        }
+
+        self.fill(&mut filled.historical_data, "price")?;
+        for (name, values) in filled.features.iter_mut() {
+            self.fill(values, name)?;
+        }
+        Ok((Cow::Owned(filled), missing))
+    }
+
+    /// Like `apply`, taking ownership so an unchanged input is not copied
+    pub fn apply_owned(&self, input: PredictionInput) -> Result<(PredictionInput, usize)> {
+        let filled = match self.apply(&input)? {
+            (Cow::Owned(filled), count) => Some((filled, count)),
+            (Cow::Borrowed(_), _) => None,
+        };
+        Ok(filled.unwrap_or((input, 0)))
+    }
+
+    /// Fill NaN runs in one series in place
+    fn fill(&self, values: &mut [f64], name: &str) -> Result<()> {
+        let mut i = 0;
+        while i < values.len() {
+            if !values[i].is_nan() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < values.len() && values[i].is_nan() {
+                i += 1;
+            }
+            if i - start > self.max_gap {
+                return Err(NeuralBridgeError::Validation(format!(
+                    "{} has {} consecutive missing points, more than the {} that can be filled",
+                    name,
+                    i - start,
+                    self.max_gap
+                )));
+            }
+
+            let before = start.checked_sub(1).map(|j| values[j]);
+            let after = values.get(i).copied();
+            for (offset, j) in (start..i).enumerate() {
+                values[j] = match (self.method, before, after) {
+                    (InterpolationMethod::Linear, Some(a), Some(b)) => {
+                        a + (b - a) * (offset + 1) as f64 / (i - start + 1) as f64
+                    }
+                    (_, Some(a), _) => a,
+                    (_, None, Some(b)) => b,
+                    (_, None, None) => {
+                        return Err(NeuralBridgeError::Validation(format!("{} has no observed values", name)))
+                    }
+                };
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy of `input` with NaN points inserted where timestamps skip whole
+    /// multiples of the median spacing. Each gap is measured before anything
+    /// is allocated for it.
+    fn insert_missing_intervals(&self, input: &PredictionInput) -> Result<PredictionInput> {
+        let mut steps_ms: Vec<i64> = input
+            .timestamps
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).num_milliseconds())
+            .filter(|step_ms| *step_ms > 0)
+            .collect();
+        steps_ms.sort_unstable();
+        let Some(&step_ms) = steps_ms.get(steps_ms.len() / 2) else {
+            return Ok(input.clone());
+        };
+        let step = chrono::Duration::milliseconds(step_ms);
+        let session_break_ms = self
+            .session_break_secs
+            .map(|secs| i64::try_from(secs.saturating_mul(1000)).unwrap_or(i64::MAX));
+
+        let mut output = PredictionInput {
+            historical_data: Vec::with_capacity(input.historical_data.len()),
+            timestamps: Vec::with_capacity(input.timestamps.len()),
+            features: input.features.keys().map(|name| (name.clone(), Vec::new())).collect(),
+            ..input.clone()
+        };
+        for (i, &timestamp) in input.timestamps.iter().enumerate() {
+            if let Some(&previous) = output.timestamps.last() {
+                let gap_ms = (timestamp - previous).num_milliseconds();
+                let missing = gap_ms.saturating_add(step_ms / 2) / step_ms - 1;
+                let session_break = session_break_ms.is_some_and(|break_ms| gap_ms >= break_ms);
+                if missing > self.max_gap as i64 && !session_break {
+                    return Err(NeuralBridgeError::Validation(format!(
+                        "{} intervals missing after {}, more than the {} that can be filled",
+                        missing, previous, self.max_gap
+                    )));
+                }
+                if missing <= self.max_gap as i64 {
+                    for k in 1..=missing.max(0) {
+                        output.timestamps.push(previous + step * k as i32);
+                        output.historical_data.push(f64::NAN);
+                        for values in output.features.values_mut() {
+                            values.push(f64::NAN);
+                        }
+                    }
+                }
+            }
+            output.timestamps.push(timestamp);
+            output.historical_data.push(input.historical_data[i]);
+            for (name, values) in output.features.iter_mut() {
+                values.push(input.features[name].get(i).copied().unwrap_or(f64::NAN));
+            }
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gapped_input(prices: Vec<f64>, minutes: &[i64]) -> PredictionInput {
+        let start = chrono::Utc::now();
+        PredictionInput {
+            symbol: "AAPL".to_string(),
+            features: [("volume".to_string(), vec![10.0; prices.len()])].into_iter().collect(),
+            historical_data: prices,
+            timestamps: minutes.iter().map(|m| start + chrono::Duration::minutes(*m)).collect(),
+            horizon: 1,
+            seed: None,
+            resampling: None,
+        }
+    }
+
+    #[test]
+    fn test_interpolation_fills_nans_and_missing_intervals() {
+        // NaN at minute 1, minutes 4 and 5 missing entirely
+        let input = gapped_input(vec![100.0, f64::NAN, 102.0, 103.0, 106.0], &[0, 1, 2, 3, 6]);
+        let linear = InterpolationConfig {
+            method: InterpolationMethod::Linear,
+            max_gap: 2,
+            ..Default::default()
+        };
+
+        let (filled, count) = linear.apply(&input).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(filled.historical_data, vec![100.0, 101.0, 102.0, 103.0, 104.0, 105.0, 106.0]);
+        assert_eq!(filled.timestamps.len(), 7);
+        assert_eq!(filled.features["volume"], vec![10.0; 7]);
+
+        let forward_fill = InterpolationConfig {
+            method: InterpolationMethod::ForwardFill,
+            ..linear
+        };
+        let (filled, _) = forward_fill.apply(&input).unwrap();
+        assert_eq!(filled.historical_data[4..6], [103.0, 103.0]);
+    }
+
+    #[test]
+    fn test_interpolation_rejects_sparse_or_unfilled_inputs() {
+        let input = gapped_input(vec![100.0, f64::NAN, f64::NAN, f64::NAN, 104.0], &[0, 1, 2, 3, 4]);
+        let linear = InterpolationConfig {
+            method: InterpolationMethod::Linear,
+            max_gap: 2,
+            ..Default::default()
+        };
+
+        let err = linear.apply(&input).unwrap_err();
+        assert!(err.to_string().contains("price has 3 consecutive missing points"));
+        assert!(InterpolationConfig::default().apply(&input).is_err());
+
+        let complete = gapped_input(vec![100.0, 101.0], &[0, 1]);
+        assert!(matches!(linear.apply(&complete).unwrap(), (Cow::Borrowed(_), 0)));
+    }
+
+    #[test]
+    fn test_interpolation_bounds_gaps_and_skips_session_breaks() {
+        let linear = InterpolationConfig {
+            method: InterpolationMethod::Linear,
+            max_gap: 2,
+            ..Default::default()
+        };
+
+        // Overnight break is joined as-is, not filled or rejected
+        let overnight = gapped_input(vec![100.0, f64::NAN, 102.0, 110.0], &[0, 1, 2, 1080]);
+        let (filled, count) = linear.apply(&overnight).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(filled.historical_data, vec![100.0, 101.0, 102.0, 110.0]);
+
+        // A gap of ten intervals inside the session is rejected up front
+        let outage = gapped_input(vec![100.0, f64::NAN, 102.0], &[0, 1, 12]);
+        assert!(linear.apply(&outage).unwrap_err().to_string().contains("10 intervals missing"));
+
+        // Far-off timestamps are rejected without allocating the gap
+        let mut ancient = gapped_input(vec![100.0, f64::NAN, 102.0, 103.0], &[0, 1, 2, 3]);
+        ancient.timestamps[0] = chrono::DateTime::<chrono::Utc>::default();
+        let strict = InterpolationConfig {
+            session_break_secs: None,
+            ..linear
+        };
+        assert!(strict.apply(&ancient).is_err());
+    }
+
+    #[test]
+    fn test_zscore_roundtrip() {
+        let values = [100.0, 102.0, 98.0, 104.0];
+        let scaler = Scaler::fit(ScalerConfig::ZScore, &values).unwrap();
+
+        let scaled = scaler.transform(&values);
+        assert!(scaled.iter().sum::<f64>().abs() < 1e-9);
+
+        let restored = scaler.inverse(&scaled);
+        for (a, b) in values.iter().zip(&restored) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_minmax_flat_window_keeps_unit_scale() {
+        let scaler = Scaler::fit(ScalerConfig::MinMax, &[50.0, 50.0]).unwrap();
+
+        assert_eq!(scaler.scale, 1.0);
+        assert_eq!(scaler.transform(&[50.0]), vec![0.0]);
+        assert!(Scaler::fit(ScalerConfig::None, &[50.0]).is_none());
+    }
+
+    #[test]
+    fn test_pre_transforms_invert_to_prices() {
+        let prices = [100.0, 101.0, 99.5, 102.0];
+
+        for transform in [PreTransform::Diff, PreTransform::LogReturn] {
+            let forward = transform.forward(&prices).unwrap();
+            assert_eq!(forward.len(), 3);
+
+            let rebuilt = transform.inverse(prices[0], &forward);
+            for (a, b) in prices[1..].iter().zip(&rebuilt) {
+                assert!((a - b).abs() < 1e-9, "{:?}: {} vs {}", transform, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_log_return_rejects_non_positive_prices() {
+        assert!(PreTransform::LogReturn.forward(&[100.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn test_padding_strategies() {
+        let values = [1.0, 2.0, 3.0];
+
+        assert_eq!(PaddingStrategy::FirstValue.pad(&values, 5), vec![1.0, 1.0, 1.0, 2.0, 3.0]);
+        assert_eq!(PaddingStrategy::Reflect.pad(&values, 5), vec![3.0, 2.0, 1.0, 2.0, 3.0]);
+        assert_eq!(PaddingStrategy::Mean.pad(&values, 4), vec![2.0, 1.0, 2.0, 3.0]);
+        assert_eq!(PaddingStrategy::None.pad(&values, 5), values.to_vec());
+        assert_eq!(PaddingStrategy::Mean.pad(&values, 2), values.to_vec());
+    }
+
+    #[test]
+    fn test_pad_input_extends_timestamps_backwards() {
+        let start = chrono::Utc::now();
+        let input = PredictionInput {
+            symbol: "NEW".to_string(),
+            historical_data: vec![10.0, 11.0],
+            timestamps: vec![start, start + chrono::Duration::minutes(5)],
+            features: [("volume".to_string(), vec![100.0, 200.0])].into_iter().collect(),
+            horizon: 1,
+            seed: None,
+            resampling: None,
+        };
+
+        let padded = PaddingStrategy::FirstValue.pad_input(&input, 4);
+        assert_eq!(padded.historical_data, vec![10.0, 10.0, 10.0, 11.0]);
+        assert_eq!(padded.features["volume"], vec![100.0, 100.0, 100.0, 200.0]);
+        assert_eq!(padded.timestamps[0], start - chrono::Duration::minutes(10));
+        assert_eq!(padded.timestamps.len(), 4);
+    }
+}